@@ -10,7 +10,7 @@ use hpl_interface::{
 use crate::{
     error::ContractError,
     event::emit_instantiated,
-    state::{Config, CONFIG, NONCE},
+    state::{Config, CONFIG, NONCE, PROCESS_FEE},
     CONTRACT_NAME, CONTRACT_VERSION,
 };
 
@@ -41,6 +41,7 @@ pub fn instantiate(
 
     CONFIG.save(deps.storage, &config)?;
     NONCE.save(deps.storage, &0u32)?;
+    PROCESS_FEE.save(deps.storage, &None)?;
 
     hpl_ownable::initialize(deps.storage, &owner)?;
 
@@ -60,9 +61,16 @@ pub fn execute(
     match msg {
         Ownable(msg) => Ok(hpl_ownable::handle(deps, env, info, msg)?),
 
-        SetDefaultIsm { ism } => execute::set_default_ism(deps, info, ism),
+        SetDefaultIsm { ism } => execute::set_default_ism(deps, env, info, ism),
+        ApplyDefaultIsm {} => execute::apply_default_ism(deps, env, info),
+        CancelDefaultIsm {} => execute::cancel_default_ism(deps, info),
+        SetDefaultIsmDelay { delay } => execute::set_default_ism_delay(deps, env, info, delay),
+        ApplyDefaultIsmDelay {} => execute::apply_default_ism_delay(deps, env, info),
         SetDefaultHook { hook } => execute::set_default_hook(deps, info, hook),
         SetRequiredHook { hook } => execute::set_required_hook(deps, info, hook),
+        SetProcessFee { fee } => execute::set_process_fee(deps, info, fee),
+        ClaimProcessFees {} => execute::claim_process_fees(deps, env, info),
+        SetStoreDispatches { enabled } => execute::set_store_dispatches(deps, info, enabled),
 
         Dispatch(msg) => execute::dispatch(deps, info, msg),
         Process { metadata, message } => execute::process(deps, env, info, metadata, message),
@@ -85,12 +93,19 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, Contr
             Hrp {} => to_binary(get_hrp(deps)),
             LocalDomain {} => to_binary(get_local_domain(deps)),
             DefaultIsm {} => to_binary(get_default_ism(deps)),
+            PendingDefaultIsm {} => to_binary(get_pending_default_ism(deps)),
             DefaultHook {} => to_binary(get_default_hook(deps)),
             RequiredHook {} => to_binary(get_required_hook(deps)),
             MessageDelivered { id } => to_binary(get_delivered(deps, id)),
             RecipientIsm { recipient_addr } => to_binary(get_recipient_ism(deps, recipient_addr)),
+            SecurityPath { origin, recipient } => {
+                to_binary(get_security_path(deps, origin, recipient))
+            }
             Nonce {} => to_binary(get_nonce(deps)),
+            ProcessFee {} => to_binary(get_process_fee(deps)),
             LatestDispatchId {} => to_binary(get_latest_dispatch_id(deps)),
+            LatestProcessId {} => to_binary(get_latest_process_id(deps)),
+            Dispatched { nonce } => to_binary(get_dispatched(deps, nonce)),
         },
     }
 }
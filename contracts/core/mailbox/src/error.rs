@@ -15,6 +15,9 @@ pub enum ContractError {
         received: Vec<Coin>,
     },
 
+    #[error("insufficient process fee: wanted {wanted:?}, received {received:?}")]
+    ProcessFee { wanted: Coin, received: Vec<Coin> },
+
     #[error("{0}")]
     CoinsError(#[from] cosmwasm_std::CoinsError),
 
@@ -39,6 +42,21 @@ pub enum ContractError {
     #[error("invalid destination domain: {domain:?}")]
     InvalidDestinationDomain { domain: u32 },
 
+    #[error("no pending default ism")]
+    NoPendingDefaultIsm {},
+
+    #[error("default ism change not effective until {effective_at}")]
+    DefaultIsmNotEffective { effective_at: u64 },
+
+    #[error("no pending default ism delay")]
+    NoPendingDefaultIsmDelay {},
+
+    #[error("default ism delay change not effective until {effective_at}")]
+    DefaultIsmDelayNotEffective { effective_at: u64 },
+
+    #[error("ism cycle detected at {ism}")]
+    IsmCycle { ism: String },
+
     #[error("message already delivered")]
     AlreadyDeliveredMessage {},
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Addr, Event, HexBinary};
+use cosmwasm_std::{Addr, Coin, Event, HexBinary};
 use hpl_interface::types::Message;
 
 pub fn emit_instantiated(owner: Addr) -> Event {
@@ -11,6 +11,32 @@ pub fn emit_default_ism_set(owner: Addr, new_default_ism: Addr) -> Event {
         .add_attribute("new_default_ism", new_default_ism)
 }
 
+pub fn emit_default_ism_scheduled(owner: Addr, new_default_ism: Addr, effective_at: u64) -> Event {
+    Event::new("mailbox_default_ism_scheduled")
+        .add_attribute("owner", owner)
+        .add_attribute("new_default_ism", new_default_ism)
+        .add_attribute("effective_at", effective_at.to_string())
+}
+
+pub fn emit_default_ism_cancelled(owner: Addr, cancelled_ism: Addr) -> Event {
+    Event::new("mailbox_default_ism_cancelled")
+        .add_attribute("owner", owner)
+        .add_attribute("cancelled_ism", cancelled_ism)
+}
+
+pub fn emit_default_ism_delay_set(owner: Addr, delay: u64) -> Event {
+    Event::new("mailbox_default_ism_delay_set")
+        .add_attribute("owner", owner)
+        .add_attribute("delay", delay.to_string())
+}
+
+pub fn emit_default_ism_delay_scheduled(owner: Addr, delay: u64, effective_at: u64) -> Event {
+    Event::new("mailbox_default_ism_delay_scheduled")
+        .add_attribute("owner", owner)
+        .add_attribute("delay", delay.to_string())
+        .add_attribute("effective_at", effective_at.to_string())
+}
+
 pub fn emit_default_hook_set(owner: Addr, new_default_hook: Addr) -> Event {
     Event::new("mailbox_default_hook_set")
         .add_attribute("owner", owner)
@@ -23,6 +49,35 @@ pub fn emit_required_hook_set(owner: Addr, new_required_hook: Addr) -> Event {
         .add_attribute("new_required_hook", new_required_hook)
 }
 
+pub fn emit_process_fee_set(owner: Addr, fee: Option<Coin>) -> Event {
+    Event::new("mailbox_process_fee_set")
+        .add_attribute("owner", owner)
+        .add_attribute(
+            "fee",
+            fee.map(|v| v.to_string())
+                .unwrap_or_else(|| "none".to_string()),
+        )
+}
+
+pub fn emit_store_dispatches_set(owner: Addr, enabled: bool) -> Event {
+    Event::new("mailbox_store_dispatches_set")
+        .add_attribute("owner", owner)
+        .add_attribute("enabled", enabled.to_string())
+}
+
+pub fn emit_process_fees_claimed(owner: Addr, amount: Vec<Coin>) -> Event {
+    Event::new("mailbox_process_fees_claimed")
+        .add_attribute("owner", owner)
+        .add_attribute(
+            "amount",
+            amount
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+}
+
 pub fn emit_dispatch_id(id: HexBinary) -> Event {
     Event::new("mailbox_dispatch_id").add_attribute("message_id", id.to_hex())
 }
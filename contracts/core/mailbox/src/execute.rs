@@ -1,6 +1,6 @@
 use cosmwasm_std::{
-    ensure, ensure_eq, to_json_binary, wasm_execute, Coin, Coins, DepsMut, Env, HexBinary,
-    MessageInfo, Response,
+    ensure, ensure_eq, to_json_binary, wasm_execute, BankMsg, Coin, Coins, DepsMut, Env, HexBinary,
+    MessageInfo, OverflowError, OverflowOperation, Response, StdError,
 };
 use hpl_interface::{
     core::{
@@ -16,15 +16,31 @@ use hpl_ownable::get_owner;
 
 use crate::{
     event::{
-        emit_default_hook_set, emit_default_ism_set, emit_dispatch, emit_dispatch_id, emit_process,
-        emit_process_id, emit_required_hook_set,
+        emit_default_hook_set, emit_default_ism_cancelled, emit_default_ism_delay_scheduled,
+        emit_default_ism_delay_set, emit_default_ism_scheduled, emit_default_ism_set,
+        emit_dispatch, emit_dispatch_id, emit_process, emit_process_fee_set,
+        emit_process_fees_claimed, emit_process_id, emit_required_hook_set,
+        emit_store_dispatches_set,
+    },
+    state::{
+        Delivery, PendingIsm, PendingIsmDelay, CONFIG, DEFAULT_ISM_DELAY, DELIVERIES, DISPATCHES,
+        LATEST_DISPATCHED_ID, LATEST_PROCESSED_ID, NONCE, PENDING_DEFAULT_ISM,
+        PENDING_DEFAULT_ISM_DELAY, PROCESS_FEE, STORE_DISPATCHES,
     },
-    state::{Delivery, CONFIG, DELIVERIES, LATEST_DISPATCHED_ID, NONCE},
     ContractError, MAILBOX_VERSION,
 };
 
+fn effective_at(env: &Env, delay: u64) -> Result<u64, ContractError> {
+    let now = env.block.time.seconds();
+
+    now.checked_add(delay).ok_or_else(|| {
+        StdError::overflow(OverflowError::new(OverflowOperation::Add, now, delay)).into()
+    })
+}
+
 pub fn set_default_ism(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     new_default_ism: String,
 ) -> Result<Response, ContractError> {
@@ -35,15 +51,158 @@ pub fn set_default_ism(
     );
 
     let new_default_ism = deps.api.addr_validate(&new_default_ism)?;
-    let event = emit_default_ism_set(info.sender, new_default_ism.clone());
 
-    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // the first default ism is not a change, so there is nothing to wait for
+    if config.default_ism.is_none() {
+        let event = emit_default_ism_set(info.sender, new_default_ism.clone());
+
         config.default_ism = Some(new_default_ism);
+        CONFIG.save(deps.storage, &config)?;
+
+        return Ok(Response::new().add_event(event));
+    }
+
+    let delay = DEFAULT_ISM_DELAY
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let effective_at = effective_at(&env, delay)?;
+
+    PENDING_DEFAULT_ISM.save(
+        deps.storage,
+        &PendingIsm {
+            ism: new_default_ism.clone(),
+            effective_at,
+        },
+    )?;
+
+    Ok(Response::new().add_event(emit_default_ism_scheduled(
+        info.sender,
+        new_default_ism,
+        effective_at,
+    )))
+}
+
+pub fn apply_default_ism(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    ensure_eq!(
+        get_owner(deps.storage)?,
+        info.sender,
+        ContractError::Unauthorized {}
+    );
+
+    let pending = PENDING_DEFAULT_ISM
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingDefaultIsm {})?;
+
+    ensure!(
+        env.block.time.seconds() >= pending.effective_at,
+        ContractError::DefaultIsmNotEffective {
+            effective_at: pending.effective_at
+        }
+    );
+
+    PENDING_DEFAULT_ISM.remove(deps.storage);
+    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
+        config.default_ism = Some(pending.ism.clone());
 
         Ok(config)
     })?;
 
-    Ok(Response::new().add_event(event))
+    Ok(Response::new().add_event(emit_default_ism_set(info.sender, pending.ism)))
+}
+
+pub fn cancel_default_ism(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    ensure_eq!(
+        get_owner(deps.storage)?,
+        info.sender,
+        ContractError::Unauthorized {}
+    );
+
+    let pending = PENDING_DEFAULT_ISM
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingDefaultIsm {})?;
+
+    PENDING_DEFAULT_ISM.remove(deps.storage);
+
+    Ok(Response::new().add_event(emit_default_ism_cancelled(info.sender, pending.ism)))
+}
+
+pub fn set_default_ism_delay(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delay: u64,
+) -> Result<Response, ContractError> {
+    ensure_eq!(
+        get_owner(deps.storage)?,
+        info.sender,
+        ContractError::Unauthorized {}
+    );
+
+    let current = DEFAULT_ISM_DELAY
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+
+    // a shorter delay would let the owner skip the timelock it weakens,
+    // so it has to wait out the current one first
+    if delay < current {
+        let effective_at = effective_at(&env, current)?;
+
+        PENDING_DEFAULT_ISM_DELAY.save(
+            deps.storage,
+            &PendingIsmDelay {
+                delay,
+                effective_at,
+            },
+        )?;
+
+        return Ok(Response::new().add_event(emit_default_ism_delay_scheduled(
+            info.sender,
+            delay,
+            effective_at,
+        )));
+    }
+
+    // a delay that can't be scheduled would lock the default ism for good
+    effective_at(&env, delay)?;
+
+    DEFAULT_ISM_DELAY.save(deps.storage, &delay)?;
+    PENDING_DEFAULT_ISM_DELAY.remove(deps.storage);
+
+    Ok(Response::new().add_event(emit_default_ism_delay_set(info.sender, delay)))
+}
+
+pub fn apply_default_ism_delay(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    ensure_eq!(
+        get_owner(deps.storage)?,
+        info.sender,
+        ContractError::Unauthorized {}
+    );
+
+    let pending = PENDING_DEFAULT_ISM_DELAY
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingDefaultIsmDelay {})?;
+
+    ensure!(
+        env.block.time.seconds() >= pending.effective_at,
+        ContractError::DefaultIsmDelayNotEffective {
+            effective_at: pending.effective_at
+        }
+    );
+
+    PENDING_DEFAULT_ISM_DELAY.remove(deps.storage);
+    DEFAULT_ISM_DELAY.save(deps.storage, &pending.delay)?;
+
+    Ok(Response::new().add_event(emit_default_ism_delay_set(info.sender, pending.delay)))
 }
 
 pub fn set_default_hook(
@@ -92,6 +251,61 @@ pub fn set_required_hook(
     Ok(Response::new().add_event(event))
 }
 
+pub fn set_process_fee(
+    deps: DepsMut,
+    info: MessageInfo,
+    fee: Option<Coin>,
+) -> Result<Response, ContractError> {
+    ensure_eq!(
+        get_owner(deps.storage)?,
+        info.sender,
+        ContractError::Unauthorized {}
+    );
+
+    PROCESS_FEE.save(deps.storage, &fee)?;
+
+    Ok(Response::new().add_event(emit_process_fee_set(info.sender, fee)))
+}
+
+pub fn set_store_dispatches(
+    deps: DepsMut,
+    info: MessageInfo,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    ensure_eq!(
+        get_owner(deps.storage)?,
+        info.sender,
+        ContractError::Unauthorized {}
+    );
+
+    STORE_DISPATCHES.save(deps.storage, &enabled)?;
+
+    Ok(Response::new().add_event(emit_store_dispatches_set(info.sender, enabled)))
+}
+
+pub fn claim_process_fees(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let owner = get_owner(deps.storage)?;
+    ensure_eq!(owner, info.sender, ContractError::Unauthorized {});
+
+    // dispatch forwards every coin it receives to the hooks,
+    // so whatever the mailbox holds was collected by process
+    let balances = deps.querier.query_all_balances(env.contract.address)?;
+
+    let mut resp = Response::new();
+    if !balances.is_empty() {
+        resp = resp.add_message(BankMsg::Send {
+            to_address: owner.to_string(),
+            amount: balances.clone(),
+        });
+    }
+
+    Ok(resp.add_event(emit_process_fees_claimed(owner, balances)))
+}
+
 pub fn dispatch(
     deps: DepsMut,
     info: MessageInfo,
@@ -134,6 +348,10 @@ pub fn dispatch(
     let msg_id = msg.id();
     NONCE.save(deps.storage, &(nonce + 1))?;
     LATEST_DISPATCHED_ID.save(deps.storage, &msg_id.to_vec())?;
+    // full messages add up, so keeping them is opt-in
+    if STORE_DISPATCHES.may_load(deps.storage)?.unwrap_or_default() {
+        DISPATCHES.save(deps.storage, nonce, &HexBinary::from(msg.clone()).to_vec())?;
+    }
 
     // build post dispatch calls
     let post_dispatch_msgs = vec![
@@ -180,6 +398,17 @@ pub fn process(
         }
     );
 
+    if let Some(fee) = PROCESS_FEE.may_load(deps.storage)?.flatten() {
+        let funds = Coins::try_from(info.funds.clone())?;
+        ensure!(
+            funds.amount_of(&fee.denom) >= fee.amount,
+            ContractError::ProcessFee {
+                wanted: fee,
+                received: info.funds
+            }
+        );
+    }
+
     let id = decoded_msg.id();
     let ism = ism::recipient(&deps.querier, &recipient)?.unwrap_or(config.get_default_ism());
 
@@ -204,6 +433,9 @@ pub fn process(
 
     ensure!(verify, ContractError::VerifyFailed {});
 
+    // lets the recipient look up the id of the message it is handling
+    LATEST_PROCESSED_ID.save(deps.storage, &id.to_vec())?;
+
     let handle_msg = wasm_execute(
         recipient,
         &HandleMsg {
@@ -332,6 +564,7 @@ mod tests {
     ) {
         let res = set_default_ism(
             deps.as_mut(),
+            mock_env(),
             mock_info(sender.as_str(), &[]),
             new_default_ism.to_string(),
         )
@@ -344,6 +577,155 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case(100)]
+    #[should_panic(expected = "default ism change not effective until")]
+    #[case(99)]
+    fn test_default_ism_timelock(mut deps: TestDeps, #[case] elapsed: u64) {
+        let owner = mock_info(OWNER, &[]);
+        let mut env = mock_env();
+        let scheduled_at = env.block.time.seconds();
+
+        set_default_ism(deps.as_mut(), env.clone(), owner.clone(), "ism_a".into()).unwrap();
+        set_default_ism_delay(deps.as_mut(), env.clone(), owner.clone(), 100).unwrap();
+
+        let res =
+            set_default_ism(deps.as_mut(), env.clone(), owner.clone(), "ism_b".into()).unwrap();
+        assert_eq!(
+            res,
+            Response::new().add_event(emit_default_ism_scheduled(
+                addr(OWNER),
+                addr("ism_b"),
+                scheduled_at + 100
+            ))
+        );
+
+        let config = CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(config.default_ism, Some(addr("ism_a")));
+
+        let pending = crate::query::get_pending_default_ism(deps.as_ref()).unwrap();
+        assert_eq!(pending.ism, Some("ism_b".to_string()));
+        assert_eq!(pending.effective_at, Some(scheduled_at + 100));
+        assert_eq!(pending.delay, 100);
+
+        // only the owner can apply
+        let err =
+            apply_default_ism(deps.as_mut(), env.clone(), mock_info(NOT_OWNER, &[])).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        env.block.time = env.block.time.plus_seconds(elapsed);
+        apply_default_ism(deps.as_mut(), env, owner)
+            .map_err(|e| e.to_string())
+            .unwrap();
+
+        let config = CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(config.default_ism, Some(addr("ism_b")));
+        assert!(!PENDING_DEFAULT_ISM.exists(deps.as_ref().storage));
+    }
+
+    #[rstest]
+    fn test_apply_default_ism_without_pending(mut deps: TestDeps) {
+        let err = apply_default_ism(deps.as_mut(), mock_env(), mock_info(OWNER, &[])).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingDefaultIsm {});
+    }
+
+    #[rstest]
+    fn test_cancel_default_ism(mut deps: TestDeps) {
+        let owner = mock_info(OWNER, &[]);
+        let env = mock_env();
+
+        set_default_ism(deps.as_mut(), env.clone(), owner.clone(), "ism_a".into()).unwrap();
+        set_default_ism(deps.as_mut(), env.clone(), owner.clone(), "ism_b".into()).unwrap();
+
+        let err = cancel_default_ism(deps.as_mut(), mock_info(NOT_OWNER, &[])).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let res = cancel_default_ism(deps.as_mut(), owner.clone()).unwrap();
+        assert_eq!(
+            res,
+            Response::new().add_event(emit_default_ism_cancelled(addr(OWNER), addr("ism_b")))
+        );
+        assert!(!PENDING_DEFAULT_ISM.exists(deps.as_ref().storage));
+
+        let err = apply_default_ism(deps.as_mut(), env, owner.clone()).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingDefaultIsm {});
+
+        let err = cancel_default_ism(deps.as_mut(), owner).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingDefaultIsm {});
+
+        let config = CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(config.default_ism, Some(addr("ism_a")));
+    }
+
+    #[rstest]
+    #[case(100)]
+    #[should_panic(expected = "default ism delay change not effective until")]
+    #[case(99)]
+    fn test_default_ism_delay_decrease(mut deps: TestDeps, #[case] elapsed: u64) {
+        let owner = mock_info(OWNER, &[]);
+        let mut env = mock_env();
+        let scheduled_at = env.block.time.seconds();
+
+        // increases apply right away
+        let res = set_default_ism_delay(deps.as_mut(), env.clone(), owner.clone(), 100).unwrap();
+        assert_eq!(
+            res,
+            Response::new().add_event(emit_default_ism_delay_set(addr(OWNER), 100))
+        );
+
+        let res = set_default_ism_delay(deps.as_mut(), env.clone(), owner.clone(), 10).unwrap();
+        assert_eq!(
+            res,
+            Response::new().add_event(emit_default_ism_delay_scheduled(
+                addr(OWNER),
+                10,
+                scheduled_at + 100
+            ))
+        );
+
+        let pending = crate::query::get_pending_default_ism(deps.as_ref()).unwrap();
+        assert_eq!(pending.delay, 100);
+        assert_eq!(pending.pending_delay, Some(10));
+        assert_eq!(pending.pending_delay_effective_at, Some(scheduled_at + 100));
+
+        let err = apply_default_ism_delay(deps.as_mut(), env.clone(), mock_info(NOT_OWNER, &[]))
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        env.block.time = env.block.time.plus_seconds(elapsed);
+        apply_default_ism_delay(deps.as_mut(), env, owner)
+            .map_err(|e| e.to_string())
+            .unwrap();
+
+        assert_eq!(DEFAULT_ISM_DELAY.load(deps.as_ref().storage).unwrap(), 10);
+        assert!(!PENDING_DEFAULT_ISM_DELAY.exists(deps.as_ref().storage));
+    }
+
+    #[rstest]
+    fn test_default_ism_delay_increase_drops_pending_decrease(mut deps: TestDeps) {
+        let owner = mock_info(OWNER, &[]);
+        let env = mock_env();
+
+        set_default_ism_delay(deps.as_mut(), env.clone(), owner.clone(), 100).unwrap();
+        set_default_ism_delay(deps.as_mut(), env.clone(), owner.clone(), 10).unwrap();
+        set_default_ism_delay(deps.as_mut(), env.clone(), owner.clone(), 200).unwrap();
+
+        assert_eq!(DEFAULT_ISM_DELAY.load(deps.as_ref().storage).unwrap(), 200);
+
+        let err = apply_default_ism_delay(deps.as_mut(), env, owner).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingDefaultIsmDelay {});
+    }
+
+    #[rstest]
+    fn test_default_ism_delay_overflow(mut deps: TestDeps) {
+        let owner = mock_info(OWNER, &[]);
+        let env = mock_env();
+
+        let err = set_default_ism_delay(deps.as_mut(), env, owner, u64::MAX).unwrap_err();
+        assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+        assert!(!DEFAULT_ISM_DELAY.exists(deps.as_ref().storage));
+    }
+
     #[rstest]
     #[case(addr(OWNER), addr("default_hook"))]
     #[should_panic(expected = "unauthorized")]
@@ -396,6 +778,7 @@ mod tests {
     #[case(DEST_DOMAIN, gen_bz(20), gen_bz(33))]
     fn test_dispatch(
         #[values("osmo", "neutron")] hrp: &str,
+        #[values(true, false)] store_dispatches: bool,
         #[case] dest_domain: u32,
         #[case] sender: HexBinary,
         #[case] recipient_addr: HexBinary,
@@ -419,9 +802,16 @@ mod tests {
         )
         .unwrap();
 
-        set_default_ism(deps.as_mut(), mock_info(OWNER, &[]), "default_ism".into()).unwrap();
+        set_default_ism(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(OWNER, &[]),
+            "default_ism".into(),
+        )
+        .unwrap();
         set_default_hook(deps.as_mut(), mock_info(OWNER, &[]), "default_hook".into()).unwrap();
         set_required_hook(deps.as_mut(), mock_info(OWNER, &[]), "required_hook".into()).unwrap();
+        set_store_dispatches(deps.as_mut(), mock_info(OWNER, &[]), store_dispatches).unwrap();
 
         let dispatch_msg = DispatchMsg::new(dest_domain, recipient_addr, msg_body)
             .with_metadata(1500u32.to_be_bytes().to_vec());
@@ -436,7 +826,7 @@ mod tests {
             )
             .unwrap();
 
-        let _res = dispatch(
+        let res = dispatch(
             deps.as_mut(),
             mock_info(sender.as_str(), &[coin(1500, "utest")]),
             dispatch_msg,
@@ -449,6 +839,21 @@ mod tests {
             LATEST_DISPATCHED_ID.load(deps.as_ref().storage).unwrap(),
             msg.id().to_vec()
         );
+
+        if !store_dispatches {
+            assert!(crate::query::get_dispatched(deps.as_ref(), 0).is_err());
+            return;
+        }
+
+        // the stored dispatch replays the emitted events
+        let dispatched = crate::query::get_dispatched(deps.as_ref(), 0).unwrap();
+        assert_eq!(dispatched.message_id, msg.id());
+        assert_eq!(
+            res.events[0],
+            emit_dispatch_id(dispatched.message_id.clone())
+        );
+        assert_eq!(res.events[1], emit_dispatch(dispatched.message.into()));
+        assert_eq!(dispatched.destination, dest_domain);
     }
 
     #[rstest]
@@ -660,5 +1065,111 @@ mod tests {
             .load(deps.as_ref().storage, msg_id.to_vec())
             .unwrap();
         assert_eq!(delivery.sender, sender_addr);
+        assert_eq!(
+            LATEST_PROCESSED_ID.load(deps.as_ref().storage).unwrap(),
+            msg_id.to_vec()
+        );
+    }
+
+    #[rstest]
+    #[case(None, vec![])]
+    #[case(Some(coin(100, "utest")), vec![coin(100, "utest")])]
+    #[case(Some(coin(100, "utest")), vec![coin(150, "utest"), coin(10, "uosmo")])]
+    #[should_panic(expected = "insufficient process fee")]
+    #[case(Some(coin(100, "utest")), vec![coin(50, "utest")])]
+    #[should_panic(expected = "insufficient process fee")]
+    #[case(Some(coin(100, "utest")), vec![coin(100, "uosmo")])]
+    fn test_process_fee(mut deps: TestDeps, #[case] fee: Option<Coin>, #[case] funds: Vec<Coin>) {
+        deps.querier.update_wasm(test_process_query_handler);
+
+        set_default_ism(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(OWNER, &[]),
+            "default_ism".into(),
+        )
+        .unwrap();
+
+        let res = set_process_fee(deps.as_mut(), mock_info(OWNER, &[]), fee.clone()).unwrap();
+        assert_eq!(
+            res,
+            Response::new().add_event(emit_process_fee_set(addr(OWNER), fee.clone()))
+        );
+
+        let fee_res = crate::query::get_process_fee(deps.as_ref()).unwrap();
+        assert_eq!(fee_res.fee, fee);
+
+        let msg = Message {
+            version: MAILBOX_VERSION,
+            nonce: 123,
+            origin_domain: DEST_DOMAIN,
+            sender: gen_bz(32),
+            dest_domain: LOCAL_DOMAIN,
+            recipient: gen_bz(32),
+            body: gen_bz(123),
+        };
+
+        process(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("relayer", &funds),
+            vec![1].into(),
+            msg.into(),
+        )
+        .map_err(|e| e.to_string())
+        .unwrap();
+    }
+
+    #[rstest]
+    fn test_set_store_dispatches_unauthorized(mut deps: TestDeps) {
+        let err = set_store_dispatches(deps.as_mut(), mock_info(NOT_OWNER, &[]), true).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    #[rstest]
+    fn test_set_process_fee_unauthorized(mut deps: TestDeps) {
+        let err = set_process_fee(
+            deps.as_mut(),
+            mock_info(NOT_OWNER, &[]),
+            Some(coin(100, "utest")),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    #[rstest]
+    #[case(addr(OWNER), vec![coin(300, "utest")])]
+    #[case(addr(OWNER), vec![])]
+    #[should_panic(expected = "unauthorized")]
+    #[case(addr(NOT_OWNER), vec![coin(300, "utest")])]
+    fn test_claim_process_fees(
+        mut deps: TestDeps,
+        #[case] sender: Addr,
+        #[case] balances: Vec<Coin>,
+    ) {
+        let env = mock_env();
+        deps.querier
+            .update_balance(env.contract.address.clone(), balances.clone());
+
+        let res = claim_process_fees(deps.as_mut(), env, mock_info(sender.as_str(), &[]))
+            .map_err(|e| e.to_string())
+            .unwrap();
+
+        let msgs: Vec<_> = res.messages.iter().map(|v| v.msg.clone()).collect();
+        if balances.is_empty() {
+            assert!(msgs.is_empty());
+        } else {
+            assert_eq!(
+                msgs,
+                vec![CosmosMsg::Bank(BankMsg::Send {
+                    to_address: OWNER.to_string(),
+                    amount: balances.clone(),
+                })]
+            );
+        }
+        assert_eq!(
+            res.events,
+            vec![emit_process_fees_claimed(addr(OWNER), balances)]
+        );
     }
 }
@@ -1,16 +1,26 @@
-use cosmwasm_std::{Coins, Deps, HexBinary, StdResult};
+use cosmwasm_std::{ensure, Coins, Deps, HexBinary, StdResult};
 use hpl_interface::{
     core::mailbox::{
-        DefaultHookResponse, DefaultIsmResponse, DispatchMsg, HrpResponse,
-        LatestDispatchedIdResponse, LocalDomainResponse, MessageDeliveredResponse, NonceResponse,
-        RecipientIsmResponse, RequiredHookResponse,
+        DefaultHookResponse, DefaultIsmResponse, DispatchMsg, DispatchedResponse, HrpResponse,
+        LatestDispatchedIdResponse, LatestProcessedIdResponse, LocalDomainResponse,
+        MessageDeliveredResponse, NonceResponse, PendingDefaultIsmResponse, ProcessFeeResponse,
+        RecipientIsmResponse, RequiredHookResponse, SecurityPathResponse,
     },
     hook::{self, QuoteDispatchResponse},
-    ism,
+    ism::{
+        self,
+        aggregate::{AggregateIsmQueryMsg, IsmsResponse},
+        routing::{RouteResponse, RoutingIsmQueryMsg},
+        IsmQueryMsg, IsmType, ModuleTypeResponse,
+    },
+    types::{bech32_to_h256, Message},
 };
 
 use crate::{
-    state::{CONFIG, DELIVERIES, LATEST_DISPATCHED_ID, NONCE},
+    state::{
+        CONFIG, DEFAULT_ISM_DELAY, DELIVERIES, DISPATCHES, LATEST_DISPATCHED_ID,
+        LATEST_PROCESSED_ID, NONCE, PENDING_DEFAULT_ISM, PENDING_DEFAULT_ISM_DELAY, PROCESS_FEE,
+    },
     ContractError, MAILBOX_VERSION,
 };
 
@@ -36,6 +46,28 @@ pub fn get_default_ism(deps: Deps) -> Result<DefaultIsmResponse, ContractError>
     })
 }
 
+pub fn get_pending_default_ism(deps: Deps) -> Result<PendingDefaultIsmResponse, ContractError> {
+    let pending = PENDING_DEFAULT_ISM.may_load(deps.storage)?;
+    let delay = DEFAULT_ISM_DELAY
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let pending_delay = PENDING_DEFAULT_ISM_DELAY.may_load(deps.storage)?;
+
+    Ok(PendingDefaultIsmResponse {
+        ism: pending.as_ref().map(|v| v.ism.to_string()),
+        effective_at: pending.map(|v| v.effective_at),
+        delay,
+        pending_delay: pending_delay.as_ref().map(|v| v.delay),
+        pending_delay_effective_at: pending_delay.map(|v| v.effective_at),
+    })
+}
+
+pub fn get_process_fee(deps: Deps) -> Result<ProcessFeeResponse, ContractError> {
+    let fee = PROCESS_FEE.may_load(deps.storage)?.flatten();
+
+    Ok(ProcessFeeResponse { fee })
+}
+
 pub fn get_default_hook(deps: Deps) -> Result<DefaultHookResponse, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -77,6 +109,83 @@ pub fn get_recipient_ism(
     Ok(RecipientIsmResponse { ism: ism.into() })
 }
 
+pub fn get_security_path(
+    deps: Deps,
+    origin: u32,
+    recipient: String,
+) -> Result<SecurityPathResponse, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let root = ism::recipient(&deps.querier, &recipient)?.unwrap_or(config.get_default_ism());
+
+    // routing isms only look at the origin, the rest is filled for completeness
+    let message: HexBinary = Message {
+        version: MAILBOX_VERSION,
+        nonce: 0,
+        origin_domain: origin,
+        sender: HexBinary::from(vec![0u8; 32]),
+        dest_domain: config.local_domain,
+        recipient: bech32_to_h256(recipient.as_str())?.to_vec().into(),
+        body: HexBinary::default(),
+    }
+    .into();
+
+    let mut isms = vec![];
+    resolve_ism(deps, root.into(), &message, &mut isms, &mut vec![])?;
+
+    Ok(SecurityPathResponse { isms })
+}
+
+fn resolve_ism(
+    deps: Deps,
+    ism: String,
+    message: &HexBinary,
+    path: &mut Vec<String>,
+    ancestors: &mut Vec<String>,
+) -> Result<(), ContractError> {
+    // a misconfigured routing or aggregation ism must not recurse forever.
+    // only the current branch is tracked, so an ism shared by two branches is fine
+    ensure!(!ancestors.contains(&ism), ContractError::IsmCycle { ism });
+
+    let module_type: ModuleTypeResponse = deps
+        .querier
+        .query_wasm_smart(&ism, &IsmQueryMsg::ModuleType {}.wrap())?;
+
+    path.push(ism.clone());
+    ancestors.push(ism.clone());
+
+    let res = match module_type.typ {
+        IsmType::Routing => {
+            let route: RouteResponse = deps.querier.query_wasm_smart(
+                &ism,
+                &ism::routing::QueryMsg::RoutingIsm(RoutingIsmQueryMsg::Route {
+                    message: message.clone(),
+                }),
+            )?;
+
+            resolve_ism(deps, route.ism, message, path, ancestors)
+        }
+        IsmType::Aggregation => {
+            let set: IsmsResponse = deps.querier.query_wasm_smart(
+                &ism,
+                &ism::aggregate::QueryMsg::AggregateIsm(AggregateIsmQueryMsg::Isms {}),
+            )?;
+
+            for sub_ism in set.isms {
+                resolve_ism(deps, sub_ism, message, path, ancestors)?;
+            }
+
+            Ok(())
+        }
+        _ => Ok(()),
+    };
+
+    ancestors.pop();
+
+    res
+}
+
 pub fn get_latest_dispatch_id(deps: Deps) -> Result<LatestDispatchedIdResponse, ContractError> {
     let latest_dispatched_id = LATEST_DISPATCHED_ID.load(deps.storage)?.into();
 
@@ -85,6 +194,27 @@ pub fn get_latest_dispatch_id(deps: Deps) -> Result<LatestDispatchedIdResponse,
     })
 }
 
+pub fn get_latest_process_id(deps: Deps) -> Result<LatestProcessedIdResponse, ContractError> {
+    let latest_processed_id = LATEST_PROCESSED_ID.load(deps.storage)?.into();
+
+    Ok(LatestProcessedIdResponse {
+        message_id: latest_processed_id,
+    })
+}
+
+pub fn get_dispatched(deps: Deps, nonce: u32) -> Result<DispatchedResponse, ContractError> {
+    let message: HexBinary = DISPATCHES.load(deps.storage, nonce)?.into();
+    let decoded: Message = message.clone().into();
+
+    Ok(DispatchedResponse {
+        message_id: decoded.id(),
+        sender: decoded.sender,
+        destination: decoded.dest_domain,
+        recipient: decoded.recipient,
+        message,
+    })
+}
+
 pub fn quote_dispatch(
     deps: Deps,
     sender: String,
@@ -137,7 +267,7 @@ mod test {
     use cosmwasm_std::{
         from_json,
         testing::{mock_dependencies, mock_env},
-        Addr,
+        to_json_binary, Addr, ContractResult, SystemResult, WasmQuery,
     };
     use hpl_interface::core::mailbox::MailboxQueryMsg;
     use ibcx_test_utils::{gen_addr, gen_bz, hex};
@@ -262,6 +392,140 @@ mod test {
         );
     }
 
+    #[rstest]
+    #[case(true, vec!["ism_own"])]
+    #[case(false, vec!["ism_routing", "ism_aggregate", "ism_multisig_a", "ism_multisig_b"])]
+    fn test_query_security_path(#[case] own_ism: bool, #[case] expected: Vec<&str>) {
+        const ORIGIN: u32 = 26657;
+
+        let mut deps = mock_dependencies();
+
+        let recipient = gen_addr("osmo");
+        let recipient_addr = recipient.to_string();
+
+        deps.querier.update_wasm(move |query| {
+            let WasmQuery::Smart { contract_addr, msg } = query else {
+                unreachable!("only for smart query")
+            };
+
+            let res = if contract_addr == &recipient_addr {
+                to_json_binary(&ism::InterchainSecurityModuleResponse {
+                    ism: own_ism.then(|| Addr::unchecked("ism_own")),
+                })
+            } else if let Ok(ism::ExpectedIsmQueryMsg::Ism(IsmQueryMsg::ModuleType {})) =
+                from_json(msg)
+            {
+                let typ = match contract_addr.as_str() {
+                    "ism_routing" => IsmType::Routing,
+                    "ism_aggregate" => IsmType::Aggregation,
+                    _ => IsmType::MessageIdMultisig,
+                };
+                to_json_binary(&ModuleTypeResponse { typ })
+            } else if let Ok(ism::routing::QueryMsg::RoutingIsm(RoutingIsmQueryMsg::Route {
+                message,
+            })) = from_json(msg)
+            {
+                assert_eq!(Message::from(message).origin_domain, ORIGIN);
+                to_json_binary(&RouteResponse {
+                    ism: "ism_aggregate".into(),
+                })
+            } else if let Ok(ism::aggregate::QueryMsg::AggregateIsm(
+                AggregateIsmQueryMsg::Isms {},
+            )) = from_json(msg)
+            {
+                to_json_binary(&IsmsResponse {
+                    isms: vec!["ism_multisig_a".into(), "ism_multisig_b".into()],
+                    threshold: 2,
+                })
+            } else {
+                unreachable!("unexpected query")
+            };
+
+            SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+        });
+
+        CONFIG
+            .save(
+                deps.as_mut().storage,
+                &Config::new("osmo", 123).with_ism(Addr::unchecked("ism_routing")),
+            )
+            .unwrap();
+
+        let res: SecurityPathResponse = query(
+            deps.as_ref(),
+            MailboxQueryMsg::SecurityPath {
+                origin: ORIGIN,
+                recipient: recipient.to_string(),
+            },
+        );
+        assert_eq!(res.isms, expected);
+    }
+
+    #[rstest]
+    #[case("ism_routing")]
+    #[case("ism_aggregate")]
+    fn test_query_security_path_cycle(#[case] looped: &'static str) {
+        let mut deps = mock_dependencies();
+
+        let recipient = gen_addr("osmo");
+        let recipient_addr = recipient.to_string();
+
+        // routing -> aggregate -> [multisig, multisig, looped]
+        deps.querier.update_wasm(move |query| {
+            let WasmQuery::Smart { contract_addr, msg } = query else {
+                unreachable!("only for smart query")
+            };
+
+            let res = if contract_addr == &recipient_addr {
+                to_json_binary(&ism::InterchainSecurityModuleResponse { ism: None })
+            } else if let Ok(ism::ExpectedIsmQueryMsg::Ism(IsmQueryMsg::ModuleType {})) =
+                from_json(msg)
+            {
+                let typ = match contract_addr.as_str() {
+                    "ism_routing" => IsmType::Routing,
+                    "ism_aggregate" => IsmType::Aggregation,
+                    _ => IsmType::MessageIdMultisig,
+                };
+                to_json_binary(&ModuleTypeResponse { typ })
+            } else if let Ok(ism::routing::QueryMsg::RoutingIsm(RoutingIsmQueryMsg::Route {
+                ..
+            })) = from_json(msg)
+            {
+                to_json_binary(&RouteResponse {
+                    ism: "ism_aggregate".into(),
+                })
+            } else if let Ok(ism::aggregate::QueryMsg::AggregateIsm(
+                AggregateIsmQueryMsg::Isms {},
+            )) = from_json(msg)
+            {
+                // the same multisig twice is shared, not a cycle
+                to_json_binary(&IsmsResponse {
+                    isms: vec!["ism_multisig".into(), "ism_multisig".into(), looped.into()],
+                    threshold: 2,
+                })
+            } else {
+                unreachable!("unexpected query")
+            };
+
+            SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+        });
+
+        CONFIG
+            .save(
+                deps.as_mut().storage,
+                &Config::new("osmo", 123).with_ism(Addr::unchecked("ism_routing")),
+            )
+            .unwrap();
+
+        let err = get_security_path(deps.as_ref(), 26657, recipient.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::IsmCycle {
+                ism: looped.to_string()
+            }
+        );
+    }
+
     #[rstest]
     fn test_query_latest_dispatched_id() {
         let mut deps = mock_dependencies();
@@ -276,4 +540,19 @@ mod test {
             query(deps.as_ref(), MailboxQueryMsg::LatestDispatchId {});
         assert_eq!(res.message_id, rand_id);
     }
+
+    #[rstest]
+    fn test_query_latest_processed_id() {
+        let mut deps = mock_dependencies();
+
+        let rand_id = gen_bz(32).to_vec();
+
+        LATEST_PROCESSED_ID
+            .save(deps.as_mut().storage, &rand_id)
+            .unwrap();
+
+        let res: LatestProcessedIdResponse =
+            query(deps.as_ref(), MailboxQueryMsg::LatestProcessId {});
+        assert_eq!(res.message_id, rand_id);
+    }
 }
@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Coin};
 use cw_storage_plus::{Item, Map};
 
 #[cw_serde]
@@ -46,6 +46,18 @@ impl Config {
     }
 }
 
+#[cw_serde]
+pub struct PendingIsm {
+    pub ism: Addr,
+    pub effective_at: u64,
+}
+
+#[cw_serde]
+pub struct PendingIsmDelay {
+    pub delay: u64,
+    pub effective_at: u64,
+}
+
 #[cw_serde]
 pub struct Delivery {
     pub sender: Addr,
@@ -55,11 +67,33 @@ pub struct Delivery {
 pub const CONFIG_KEY: &str = "config";
 pub const CONFIG: Item<Config> = Item::new(CONFIG_KEY);
 
+pub const DEFAULT_ISM_DELAY_KEY: &str = "default_ism_delay";
+pub const DEFAULT_ISM_DELAY: Item<u64> = Item::new(DEFAULT_ISM_DELAY_KEY);
+
+pub const PENDING_DEFAULT_ISM_KEY: &str = "pending_default_ism";
+pub const PENDING_DEFAULT_ISM: Item<PendingIsm> = Item::new(PENDING_DEFAULT_ISM_KEY);
+
+pub const PENDING_DEFAULT_ISM_DELAY_KEY: &str = "pending_default_ism_delay";
+pub const PENDING_DEFAULT_ISM_DELAY: Item<PendingIsmDelay> =
+    Item::new(PENDING_DEFAULT_ISM_DELAY_KEY);
+
+pub const PROCESS_FEE_KEY: &str = "process_fee";
+pub const PROCESS_FEE: Item<Option<Coin>> = Item::new(PROCESS_FEE_KEY);
+
 pub const NONCE_KEY: &str = "nonce";
 pub const NONCE: Item<u32> = Item::new(NONCE_KEY);
 
 pub const LATEST_DISPATCHED_ID_KEY: &str = "latest_dispatched_id";
 pub const LATEST_DISPATCHED_ID: Item<Vec<u8>> = Item::new(LATEST_DISPATCHED_ID_KEY);
 
+pub const LATEST_PROCESSED_ID_KEY: &str = "latest_processed_id";
+pub const LATEST_PROCESSED_ID: Item<Vec<u8>> = Item::new(LATEST_PROCESSED_ID_KEY);
+
+pub const STORE_DISPATCHES_KEY: &str = "store_dispatches";
+pub const STORE_DISPATCHES: Item<bool> = Item::new(STORE_DISPATCHES_KEY);
+
+pub const DISPATCHES_PREFIX: &str = "dispatches";
+pub const DISPATCHES: Map<u32, Vec<u8>> = Map::new(DISPATCHES_PREFIX);
+
 pub const DELIVERIES_PREFIX: &str = "deliveries";
 pub const DELIVERIES: Map<Vec<u8>, Delivery> = Map::new(DELIVERIES_PREFIX);
@@ -279,7 +279,7 @@ mod test {
         fn preset_20_byte_address() -> Self {
             let mut p = Announcement::preset();
             p.mailbox = "49cfd6ef774acab14814d699e3f7ee36fdfba932".into();
-            return p;
+            p
         }
 
         fn rand() -> Self {
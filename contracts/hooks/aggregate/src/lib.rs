@@ -1,18 +1,26 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use cosmwasm_schema::cw_serde;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure_eq, Addr, Coins, CosmosMsg, Deps, DepsMut, Empty, Env, Event, HexBinary, MessageInfo,
-    QueryResponse, Response, StdError, StdResult,
+    ensure, ensure_eq, Addr, Coins, CosmosMsg, Deps, DepsMut, Empty, Env, Event, HexBinary,
+    MessageInfo, Order, QueryResponse, Reply, Response, StdError, StdResult, Storage, SubMsg,
+    SubMsgResult,
 };
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 use hpl_interface::{
     hook::{
-        aggregate::{AggregateHookQueryMsg, ExecuteMsg, HooksResponse, InstantiateMsg, QueryMsg},
-        post_dispatch, HookQueryMsg, MailboxResponse, PostDispatchMsg, QuoteDispatchMsg,
-        QuoteDispatchResponse,
+        aggregate::{
+            AggregateHookQueryMsg, ContinueOnErrorResponse, ExecuteMsg, ForwardFundsResponse,
+            HookMetadataResponse, HookOutcome, HooksResponse, InstantiateMsg, LastOutcome,
+            LastOutcomesResponse, QueryMsg, QuoteDenomsResponse,
+        },
+        post_dispatch, HookQueryMsg, MailboxResponse, MetadataFormat, MetadataFormatResponse,
+        PostDispatchMsg, QuoteDispatchMsg, QuoteDispatchResponse,
     },
     to_binary,
-    types::Message,
+    types::{AggregateMetadata, Message},
 };
 use hpl_ownable::get_owner;
 
@@ -32,6 +40,21 @@ pub enum ContractError {
 
     #[error("unauthorized")]
     Unauthorized {},
+
+    #[error("invalid reply id: {0}")]
+    InvalidReplyId(u64),
+
+    #[error("hook not found: {0}")]
+    HookNotFound(String),
+
+    #[error("unexpected funds")]
+    UnexpectedFunds {},
+
+    #[error("hook list must not be empty")]
+    EmptyHooks {},
+
+    #[error("cooldown active: {seconds_remaining} seconds remaining")]
+    CooldownActive { seconds_remaining: u64 },
 }
 
 // version info for migration info
@@ -41,10 +64,106 @@ pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const HOOKS_KEY: &str = "hooks";
 pub const HOOKS: Item<Vec<Addr>> = Item::new(HOOKS_KEY);
 
+pub const HOOK_METADATA_PREFIX: &str = "hook_metadata";
+pub const HOOK_METADATA: Map<&Addr, HexBinary> = Map::new(HOOK_METADATA_PREFIX);
+
+pub const CONTINUE_ON_ERROR_KEY: &str = "continue_on_error";
+pub const CONTINUE_ON_ERROR: Item<bool> = Item::new(CONTINUE_ON_ERROR_KEY);
+
+pub const FORWARD_FUNDS_KEY: &str = "forward_funds";
+pub const FORWARD_FUNDS: Item<bool> = Item::new(FORWARD_FUNDS_KEY);
+
+pub const ALLOW_EMPTY_KEY: &str = "allow_empty";
+pub const ALLOW_EMPTY: Item<bool> = Item::new(ALLOW_EMPTY_KEY);
+
+pub const AGGREGATE_METADATA_KEY: &str = "aggregate_metadata";
+pub const AGGREGATE_METADATA: Item<bool> = Item::new(AGGREGATE_METADATA_KEY);
+
+pub const LAST_OUTCOMES_PREFIX: &str = "last_outcomes";
+pub const LAST_OUTCOMES: Map<&Addr, LastOutcome> = Map::new(LAST_OUTCOMES_PREFIX);
+
+pub const PENDING_DISPATCH_KEY: &str = "pending_dispatch";
+pub const PENDING_DISPATCH: Item<PendingDispatch> = Item::new(PENDING_DISPATCH_KEY);
+
+/// Outcomes of an in-flight post_dispatch, filled by replies in continue-on-error mode
+#[cw_serde]
+pub struct PendingDispatch {
+    pub message_id: HexBinary,
+    pub hooks: Vec<Addr>,
+    pub outcomes: Vec<HookOutcome>,
+}
+
+impl PendingDispatch {
+    fn is_done(&self) -> bool {
+        self.outcomes.len() == self.hooks.len()
+    }
+
+    fn record(&mut self, hook: &Addr, error: Option<String>) {
+        self.outcomes.push(HookOutcome {
+            hook: hook.to_string(),
+            success: error.is_none(),
+            error,
+        });
+    }
+
+    fn save_outcomes(&self, storage: &mut dyn Storage, height: u64) -> StdResult<()> {
+        for outcome in self.outcomes.iter() {
+            LAST_OUTCOMES.save(
+                storage,
+                &Addr::unchecked(&outcome.hook),
+                &LastOutcome {
+                    hook: outcome.hook.clone(),
+                    success: outcome.success,
+                    height,
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn result_event(&self) -> StdResult<Event> {
+        let join = |success: bool| {
+            self.outcomes
+                .iter()
+                .filter(|v| v.success == success)
+                .map(|v| v.hook.as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        Ok(new_event("post_dispatch_result")
+            .add_attribute("message_id", self.message_id.to_hex())
+            .add_attribute("succeeded", join(true))
+            .add_attribute("failed", join(false))
+            .add_attribute(
+                "outcomes",
+                serde_json_wasm::to_string(&self.outcomes)
+                    .map_err(|_| StdError::generic_err("encoding failed"))?,
+            ))
+    }
+}
+
 fn new_event(name: &str) -> Event {
     Event::new(format!("hpl_hook_aggregate::{}", name))
 }
 
+fn ensure_owner_change(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+) -> Result<(), ContractError> {
+    ensure_eq!(get_owner(storage)?, sender, ContractError::Unauthorized {});
+
+    let seconds_remaining = hpl_ownable::consume_cooldown(storage, env)?;
+    ensure!(
+        seconds_remaining == 0,
+        ContractError::CooldownActive { seconds_remaining }
+    );
+
+    Ok(())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -55,21 +174,34 @@ pub fn instantiate(
     cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     let owner = deps.api.addr_validate(&msg.owner)?;
-    let hooks = msg
+    let hooks: Vec<Addr> = msg
         .hooks
         .iter()
         .map(|v| deps.api.addr_validate(v))
         .collect::<StdResult<_>>()?;
 
+    ensure!(
+        msg.allow_empty || !hooks.is_empty(),
+        ContractError::EmptyHooks {}
+    );
+
     hpl_ownable::initialize(deps.storage, &owner)?;
 
     HOOKS.save(deps.storage, &hooks)?;
+    CONTINUE_ON_ERROR.save(deps.storage, &msg.continue_on_error)?;
+    FORWARD_FUNDS.save(deps.storage, &msg.forward_funds)?;
+    ALLOW_EMPTY.save(deps.storage, &msg.allow_empty)?;
+    AGGREGATE_METADATA.save(deps.storage, &msg.aggregate_metadata)?;
 
     Ok(Response::new().add_event(
         new_event("initialize")
             .add_attribute("sender", info.sender)
             .add_attribute("owner", owner)
-            .add_attribute("hooks", msg.hooks.join(",")),
+            .add_attribute("hooks", msg.hooks.join(","))
+            .add_attribute("continue_on_error", msg.continue_on_error.to_string())
+            .add_attribute("forward_funds", msg.forward_funds.to_string())
+            .add_attribute("allow_empty", msg.allow_empty.to_string())
+            .add_attribute("aggregate_metadata", msg.aggregate_metadata.to_string()),
     ))
 }
 
@@ -83,21 +215,32 @@ pub fn execute(
     match msg {
         ExecuteMsg::Ownable(msg) => Ok(hpl_ownable::handle(deps, env, info, msg)?),
         ExecuteMsg::PostDispatch(PostDispatchMsg { message, metadata }) => {
+            ensure!(
+                info.funds.is_empty() || get_forward_funds(deps.as_ref())?.forward_funds,
+                ContractError::UnexpectedFunds {}
+            );
+
+            if CONTINUE_ON_ERROR
+                .may_load(deps.storage)?
+                .unwrap_or_default()
+            {
+                return post_dispatch_continue_on_error(deps, env, metadata, message);
+            }
+
             // aggregate it
             let hooks = HOOKS.load(deps.storage)?;
 
-            let msgs: Vec<CosmosMsg> = hooks
+            let msgs: Vec<CosmosMsg> = hook_metadata(deps.storage, &hooks, metadata)?
                 .into_iter()
-                .map(|v| {
+                .map(|(hook, metadata)| {
                     let quote = hpl_interface::hook::quote_dispatch(
                         &deps.querier,
-                        &v,
+                        &hook,
                         metadata.clone(),
                         message.clone(),
                     )?;
                     let msg =
-                        post_dispatch(v, metadata.clone(), message.clone(), Some(quote.fees))?
-                            .into();
+                        post_dispatch(hook, metadata, message.clone(), Some(quote.fees))?.into();
 
                     Ok(msg)
                 })
@@ -111,17 +254,26 @@ pub fn execute(
             ))
         }
         ExecuteMsg::SetHooks { hooks } => {
-            ensure_eq!(
-                get_owner(deps.storage)?,
-                info.sender,
-                ContractError::Unauthorized {}
-            );
+            ensure_owner_change(deps.storage, &env, &info.sender)?;
 
-            let parsed_hooks = hooks
+            let parsed_hooks: Vec<Addr> = hooks
                 .iter()
                 .map(|v| deps.api.addr_validate(v))
                 .collect::<StdResult<_>>()?;
 
+            ensure!(
+                !parsed_hooks.is_empty() || ALLOW_EMPTY.may_load(deps.storage)?.unwrap_or_default(),
+                ContractError::EmptyHooks {}
+            );
+
+            // drop static metadata of the hooks being removed
+            for hook in HOOKS.load(deps.storage)? {
+                if !parsed_hooks.contains(&hook) {
+                    HOOK_METADATA.remove(deps.storage, &hook);
+                    LAST_OUTCOMES.remove(deps.storage, &hook);
+                }
+            }
+
             HOOKS.save(deps.storage, &parsed_hooks)?;
 
             Ok(Response::new().add_event(
@@ -130,7 +282,176 @@ pub fn execute(
                     .add_attribute("hooks", hooks.join(",")),
             ))
         }
+        ExecuteMsg::SetContinueOnError { continue_on_error } => {
+            ensure_owner_change(deps.storage, &env, &info.sender)?;
+
+            CONTINUE_ON_ERROR.save(deps.storage, &continue_on_error)?;
+
+            Ok(Response::new().add_event(
+                new_event("set_continue_on_error")
+                    .add_attribute("sender", info.sender)
+                    .add_attribute("continue_on_error", continue_on_error.to_string()),
+            ))
+        }
+        ExecuteMsg::SetForwardFunds { forward_funds } => {
+            ensure_owner_change(deps.storage, &env, &info.sender)?;
+
+            FORWARD_FUNDS.save(deps.storage, &forward_funds)?;
+
+            Ok(Response::new().add_event(
+                new_event("set_forward_funds")
+                    .add_attribute("sender", info.sender)
+                    .add_attribute("forward_funds", forward_funds.to_string()),
+            ))
+        }
+        ExecuteMsg::SetHookMetadata { hook, metadata } => {
+            ensure_owner_change(deps.storage, &env, &info.sender)?;
+
+            let hook_addr = deps.api.addr_validate(&hook)?;
+            if !HOOKS.load(deps.storage)?.contains(&hook_addr) {
+                return Err(ContractError::HookNotFound(hook));
+            }
+
+            if metadata.is_empty() {
+                HOOK_METADATA.remove(deps.storage, &hook_addr);
+            } else {
+                HOOK_METADATA.save(deps.storage, &hook_addr, &metadata)?;
+            }
+
+            Ok(Response::new().add_event(
+                new_event("set_hook_metadata")
+                    .add_attribute("sender", info.sender)
+                    .add_attribute("hook", hook)
+                    .add_attribute("metadata", metadata.to_hex()),
+            ))
+        }
+    }
+}
+
+/// Resolves the metadata forwarded to each hook, prefixed with the static metadata
+/// configured for the hook. In aggregate mode every hook gets its own segment of
+/// the `AggregateMetadata`, otherwise the metadata is forwarded to every hook as is.
+fn hook_metadata(
+    storage: &dyn Storage,
+    hooks: &[Addr],
+    metadata: HexBinary,
+) -> Result<Vec<(Addr, HexBinary)>, ContractError> {
+    let aggregate = AGGREGATE_METADATA.may_load(storage)?.unwrap_or_default();
+
+    let mut segments: BTreeMap<Addr, HexBinary> = if !aggregate || metadata.is_empty() {
+        BTreeMap::new()
+    } else {
+        AggregateMetadata::try_from_hex(metadata.clone(), hooks.to_vec())?.collect()
+    };
+
+    hooks
+        .iter()
+        .map(|hook| {
+            let prefix = HOOK_METADATA.may_load(storage, hook)?.unwrap_or_default();
+            let segment = match aggregate {
+                true => segments.remove(hook).unwrap_or_default(),
+                false => metadata.clone(),
+            };
+
+            Ok((
+                hook.clone(),
+                [prefix.as_slice(), segment.as_slice()].concat().into(),
+            ))
+        })
+        .collect()
+}
+
+fn post_dispatch_continue_on_error(
+    deps: DepsMut,
+    env: Env,
+    metadata: HexBinary,
+    message: HexBinary,
+) -> Result<Response, ContractError> {
+    let hooks = HOOKS.load(deps.storage)?;
+    let decoded_msg: Message = message.clone().into();
+
+    let mut pending = PendingDispatch {
+        message_id: decoded_msg.id(),
+        hooks: hooks.clone(),
+        outcomes: vec![],
+    };
+
+    let mut msgs = vec![];
+
+    let hook_metadata = hook_metadata(deps.storage, &hooks, metadata)?;
+
+    let mut skipped = vec![];
+
+    for (idx, (hook, metadata)) in hook_metadata.iter().enumerate() {
+        // pre-flight. the hook could have been turned into a non-contract address
+        if let Err(err) = deps.querier.query_wasm_contract_info(hook) {
+            pending.record(hook, Some(format!("invalid hook: {err}")));
+            skipped.push(
+                new_event("skipped_invalid_hook")
+                    .add_attribute("message_id", decoded_msg.id().to_hex())
+                    .add_attribute("hook", hook),
+            );
+            continue;
+        }
+
+        let quote = hpl_interface::hook::quote_dispatch(
+            &deps.querier,
+            hook,
+            metadata.clone(),
+            message.clone(),
+        );
+
+        match quote {
+            Ok(quote) => {
+                let msg = post_dispatch(hook, metadata.clone(), message.clone(), Some(quote.fees))?;
+                msgs.push(SubMsg::reply_always(msg, idx as u64));
+            }
+            // the hook can't even be quoted, so there is nothing to dispatch
+            Err(err) => pending.record(hook, Some(err.to_string())),
+        }
     }
+
+    let mut resp = Response::new()
+        .add_submessages(msgs)
+        .add_event(
+            new_event("post_dispatch").add_attribute("message_id", decoded_msg.id().to_hex()),
+        )
+        .add_events(skipped);
+
+    if pending.is_done() {
+        pending.save_outcomes(deps.storage, env.block.height)?;
+        resp = resp.add_event(pending.result_event()?);
+    } else {
+        PENDING_DISPATCH.save(deps.storage, &pending)?;
+    }
+
+    Ok(resp)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let mut pending = PENDING_DISPATCH.load(deps.storage)?;
+
+    let hook = pending
+        .hooks
+        .get(msg.id as usize)
+        .cloned()
+        .ok_or(ContractError::InvalidReplyId(msg.id))?;
+
+    match msg.result {
+        SubMsgResult::Ok(_) => pending.record(&hook, None),
+        SubMsgResult::Err(err) => pending.record(&hook, Some(err)),
+    }
+
+    if !pending.is_done() {
+        PENDING_DISPATCH.save(deps.storage, &pending)?;
+        return Ok(Response::new());
+    }
+
+    PENDING_DISPATCH.remove(deps.storage);
+    pending.save_outcomes(deps.storage, env.block.height)?;
+
+    Ok(Response::new().add_event(pending.result_event()?))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -139,12 +460,22 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, Contr
         QueryMsg::Ownable(msg) => Ok(hpl_ownable::handle_query(deps, env, msg)?),
         QueryMsg::Hook(msg) => match msg {
             HookQueryMsg::Mailbox {} => to_binary(get_mailbox(deps)),
+            HookQueryMsg::MetadataFormat {} => to_binary(get_metadata_format(deps)),
             HookQueryMsg::QuoteDispatch(QuoteDispatchMsg { metadata, message }) => {
                 to_binary(quote_dispatch(deps, metadata, message))
             }
         },
         QueryMsg::AggregateHook(msg) => match msg {
             AggregateHookQueryMsg::Hooks {} => to_binary(get_hooks(deps)),
+            AggregateHookQueryMsg::ContinueOnError {} => to_binary(get_continue_on_error(deps)),
+            AggregateHookQueryMsg::ForwardFunds {} => to_binary(get_forward_funds(deps)),
+            AggregateHookQueryMsg::HookMetadata { hook } => {
+                to_binary(get_hook_metadata(deps, hook))
+            }
+            AggregateHookQueryMsg::QuoteDenoms { message } => {
+                to_binary(get_quote_denoms(deps, message))
+            }
+            AggregateHookQueryMsg::LastOutcomes {} => to_binary(get_last_outcomes(deps)),
         },
     }
 }
@@ -155,6 +486,18 @@ fn get_mailbox(_deps: Deps) -> Result<MailboxResponse, ContractError> {
     })
 }
 
+fn get_metadata_format(deps: Deps) -> Result<MetadataFormatResponse, ContractError> {
+    let format = match AGGREGATE_METADATA
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+    {
+        true => MetadataFormat::Aggregate,
+        false => MetadataFormat::Passthrough,
+    };
+
+    Ok(format.into())
+}
+
 fn quote_dispatch(
     deps: Deps,
     metadata: HexBinary,
@@ -162,13 +505,13 @@ fn quote_dispatch(
 ) -> Result<QuoteDispatchResponse, ContractError> {
     let hooks = HOOKS.load(deps.storage)?;
 
-    let total = hooks
+    let total = hook_metadata(deps.storage, &hooks, metadata)?
         .into_iter()
-        .try_fold(Coins::default(), |mut acc, hook| {
+        .try_fold(Coins::default(), |mut acc, (hook, metadata)| {
             let res = hpl_interface::hook::quote_dispatch(
                 &deps.querier,
                 hook,
-                metadata.clone(),
+                metadata,
                 message.clone(),
             )?;
 
@@ -184,6 +527,46 @@ fn quote_dispatch(
     })
 }
 
+fn get_quote_denoms(
+    deps: Deps,
+    message: Option<HexBinary>,
+) -> Result<QuoteDenomsResponse, ContractError> {
+    let hooks = HOOKS.load(deps.storage)?;
+
+    // only the denoms matter here, not the amounts. sub-hooks like the igp decode the
+    // message, so the placeholder has to be well-formed
+    let message = message.unwrap_or_else(|| {
+        Message {
+            version: 0,
+            nonce: 0,
+            origin_domain: 0,
+            sender: vec![0u8; 32].into(),
+            dest_domain: 0,
+            recipient: vec![0u8; 32].into(),
+            body: HexBinary::default(),
+        }
+        .into()
+    });
+
+    let mut denoms = BTreeSet::new();
+
+    for (hook, metadata) in hook_metadata(deps.storage, &hooks, HexBinary::default())? {
+        // a sub-hook can refuse to quote the message, e.g. an igp without a gas oracle
+        // for its destination. it has no denom to report then
+        let Ok(quote) =
+            hpl_interface::hook::quote_dispatch(&deps.querier, hook, metadata, message.clone())
+        else {
+            continue;
+        };
+
+        denoms.extend(quote.fees.into_iter().map(|v| v.denom));
+    }
+
+    Ok(QuoteDenomsResponse {
+        denoms: denoms.into_iter().collect(),
+    })
+}
+
 fn get_hooks(deps: Deps) -> Result<HooksResponse, ContractError> {
     Ok(HooksResponse {
         hooks: HOOKS
@@ -194,8 +577,742 @@ fn get_hooks(deps: Deps) -> Result<HooksResponse, ContractError> {
     })
 }
 
+fn get_hook_metadata(deps: Deps, hook: String) -> Result<HookMetadataResponse, ContractError> {
+    let hook_addr = deps.api.addr_validate(&hook)?;
+
+    Ok(HookMetadataResponse {
+        metadata: HOOK_METADATA
+            .may_load(deps.storage, &hook_addr)?
+            .unwrap_or_default(),
+    })
+}
+
+fn get_last_outcomes(deps: Deps) -> Result<LastOutcomesResponse, ContractError> {
+    let outcomes = LAST_OUTCOMES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|v| v.map(|(_, outcome)| outcome))
+        .collect::<StdResult<_>>()?;
+
+    Ok(LastOutcomesResponse { outcomes })
+}
+
+fn get_continue_on_error(deps: Deps) -> Result<ContinueOnErrorResponse, ContractError> {
+    Ok(ContinueOnErrorResponse {
+        continue_on_error: CONTINUE_ON_ERROR
+            .may_load(deps.storage)?
+            .unwrap_or_default(),
+    })
+}
+
+// contracts instantiated before the flag existed always forwarded funds
+fn get_forward_funds(deps: Deps) -> Result<ForwardFundsResponse, ContractError> {
+    Ok(ForwardFundsResponse {
+        forward_funds: FORWARD_FUNDS.may_load(deps.storage)?.unwrap_or(true),
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
     hpl_utils::migrate(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     Ok(Response::default())
 }
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{
+        coin, from_json,
+        testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
+        to_json_binary, Coin, ContractInfoResponse, ContractResult, OwnedDeps, ReplyOn,
+        SubMsgResponse, SystemError, SystemResult, WasmMsg, WasmQuery,
+    };
+    use hpl_interface::{hook::ExpectedHookQueryMsg, ownable::OwnableMsg};
+    use ibcx_test_utils::{addr, hex};
+    use rstest::{fixture, rstest};
+
+    use super::*;
+
+    type TestDeps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+    const TEST_MESSAGE: &str = "dc7b240deb74cca40636435ade8514b7ac35176e085f810e92dbc8bdb54a3d554ef32b9f724df19861d7e9b89a8ed11a4ecb35512f58b18b6607689cb9ba36dcf0f4af3cc1c7128c6cf0b47ea1f1aa07a4fe64502edd9a2b2e2dddf770776040efa24f19";
+
+    #[fixture]
+    fn deps(
+        #[default(vec!["hook_a", "hook_b"])] hooks: Vec<&str>,
+        #[default(true)] continue_on_error: bool,
+        #[default(true)] forward_funds: bool,
+        #[default(false)] allow_empty: bool,
+        #[default(false)] aggregate_metadata: bool,
+    ) -> TestDeps {
+        let mut deps = mock_dependencies();
+
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::ContractInfo { contract_addr } => {
+                if contract_addr == "hook_eoa" {
+                    return SystemResult::Err(SystemError::NoSuchContract {
+                        addr: contract_addr.clone(),
+                    });
+                }
+
+                let res = ContractInfoResponse::default();
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&res).unwrap()))
+            }
+            WasmQuery::Smart { contract_addr, msg } => {
+                let ExpectedHookQueryMsg::Hook(HookQueryMsg::QuoteDispatch(req)) =
+                    from_json(msg).unwrap()
+                else {
+                    unreachable!("unexpected query")
+                };
+
+                if contract_addr == "hook_broken" {
+                    return SystemResult::Ok(ContractResult::Err("quote failed".into()));
+                }
+
+                // decodes the message like the igp does, with no gas oracle for domain 0
+                if contract_addr == "hook_igp" {
+                    let message: Message = req.message.into();
+                    if message.dest_domain == 0 {
+                        return SystemResult::Ok(ContractResult::Err(
+                            "gas oracle not found for 0".into(),
+                        ));
+                    }
+
+                    let res = QuoteDispatchResponse {
+                        fees: vec![coin(100, "uigp")],
+                    };
+                    return SystemResult::Ok(ContractResult::Ok(to_json_binary(&res).unwrap()));
+                }
+
+                // "hook_fee_{denom}" quotes in `denom`
+                let fees = match contract_addr.strip_prefix("hook_fee_") {
+                    Some(denom) => vec![coin(100, denom)],
+                    None => vec![],
+                };
+
+                let res = QuoteDispatchResponse { fees };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&res).unwrap()))
+            }
+            _ => unreachable!("unexpected query"),
+        });
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("deployer", &[]),
+            InstantiateMsg {
+                owner: "owner".to_string(),
+                hooks: hooks.into_iter().map(|v| v.to_string()).collect(),
+                continue_on_error,
+                forward_funds,
+                allow_empty,
+                aggregate_metadata,
+            },
+        )
+        .unwrap();
+
+        deps
+    }
+
+    fn post_dispatch_msg() -> ExecuteMsg {
+        ExecuteMsg::PostDispatch(PostDispatchMsg {
+            metadata: HexBinary::default(),
+            message: hex(TEST_MESSAGE),
+        })
+    }
+
+    fn reply_with(deps: DepsMut, id: u64, result: Result<(), &str>) -> Response {
+        let result = match result {
+            Ok(_) => SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+            Err(err) => SubMsgResult::Err(err.to_string()),
+        };
+
+        reply(deps, mock_env(), Reply { id, result }).unwrap()
+    }
+
+    fn find_attr<'a>(event: &'a Event, key: &str) -> &'a str {
+        event
+            .attributes
+            .iter()
+            .find(|v| v.key == key)
+            .map(|v| v.value.as_str())
+            .unwrap()
+    }
+
+    #[rstest]
+    fn test_post_dispatch_partial_success(mut deps: TestDeps) {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("mailbox", &[]),
+            post_dispatch_msg(),
+        )
+        .unwrap();
+
+        assert_eq!(res.messages.len(), 2);
+        for (idx, msg) in res.messages.iter().enumerate() {
+            assert_eq!(msg.id, idx as u64);
+            assert_eq!(msg.reply_on, ReplyOn::Always);
+        }
+
+        let res = reply_with(deps.as_mut(), 0, Ok(()));
+        assert!(res.events.is_empty());
+
+        let res = reply_with(deps.as_mut(), 1, Err("hook_b failed"));
+        let event = res
+            .events
+            .iter()
+            .find(|v| v.ty == "hpl_hook_aggregate::post_dispatch_result")
+            .unwrap();
+
+        assert_eq!(find_attr(event, "succeeded"), "hook_a");
+        assert_eq!(find_attr(event, "failed"), "hook_b");
+
+        let outcomes: Vec<HookOutcome> =
+            serde_json_wasm::from_str(find_attr(event, "outcomes")).unwrap();
+        assert_eq!(
+            outcomes,
+            vec![
+                HookOutcome {
+                    hook: "hook_a".to_string(),
+                    success: true,
+                    error: None,
+                },
+                HookOutcome {
+                    hook: "hook_b".to_string(),
+                    success: false,
+                    error: Some("hook_b failed".to_string()),
+                },
+            ]
+        );
+
+        assert!(PENDING_DISPATCH
+            .may_load(deps.as_ref().storage)
+            .unwrap()
+            .is_none());
+    }
+
+    #[rstest]
+    fn test_last_outcomes(#[with(vec!["hook_a", "hook_b", "hook_eoa"])] mut deps: TestDeps) {
+        let mut env = mock_env();
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("mailbox", &[]),
+            post_dispatch_msg(),
+        )
+        .unwrap();
+
+        // nothing is recorded until every sub-hook has reported back
+        reply_with(deps.as_mut(), 0, Ok(()));
+        assert!(get_last_outcomes(deps.as_ref())
+            .unwrap()
+            .outcomes
+            .is_empty());
+
+        reply_with(deps.as_mut(), 1, Err("hook_b failed"));
+
+        let height = env.block.height;
+        assert_eq!(
+            get_last_outcomes(deps.as_ref()).unwrap().outcomes,
+            vec![
+                LastOutcome {
+                    hook: "hook_a".to_string(),
+                    success: true,
+                    height,
+                },
+                LastOutcome {
+                    hook: "hook_b".to_string(),
+                    success: false,
+                    height,
+                },
+                LastOutcome {
+                    hook: "hook_eoa".to_string(),
+                    success: false,
+                    height,
+                },
+            ]
+        );
+
+        // a later dispatch overwrites the previous outcome
+        env.block.height += 1;
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("mailbox", &[]),
+            post_dispatch_msg(),
+        )
+        .unwrap();
+        reply(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id: 0,
+                result: SubMsgResult::Err("hook_a failed".to_string()),
+            },
+        )
+        .unwrap();
+        reply(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id: 1,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                }),
+            },
+        )
+        .unwrap();
+
+        let outcomes = get_last_outcomes(deps.as_ref()).unwrap().outcomes;
+        assert_eq!(
+            outcomes
+                .iter()
+                .map(|v| (v.hook.as_str(), v.success, v.height))
+                .collect::<Vec<_>>(),
+            vec![
+                ("hook_a", false, env.block.height),
+                ("hook_b", true, env.block.height),
+                ("hook_eoa", false, env.block.height),
+            ]
+        );
+
+        // removed hooks are dropped from the map
+        execute(
+            deps.as_mut(),
+            env,
+            mock_info("owner", &[]),
+            ExecuteMsg::SetHooks {
+                hooks: vec!["hook_a".to_string()],
+            },
+        )
+        .unwrap();
+
+        let outcomes = get_last_outcomes(deps.as_ref()).unwrap().outcomes;
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].hook, "hook_a");
+    }
+
+    #[rstest]
+    #[case(false, MetadataFormat::Passthrough)]
+    #[case(true, MetadataFormat::Aggregate)]
+    fn test_metadata_format(#[case] aggregate_metadata: bool, #[case] expected: MetadataFormat) {
+        let deps = deps(vec!["hook_a"], false, true, false, aggregate_metadata);
+
+        assert_eq!(get_metadata_format(deps.as_ref()).unwrap().format, expected);
+    }
+
+    #[rstest]
+    fn test_post_dispatch_quote_failure(#[with(vec!["hook_a", "hook_broken"])] mut deps: TestDeps) {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("mailbox", &[]),
+            post_dispatch_msg(),
+        )
+        .unwrap();
+
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].id, 0);
+
+        let res = reply_with(deps.as_mut(), 0, Ok(()));
+        let event = res
+            .events
+            .iter()
+            .find(|v| v.ty == "hpl_hook_aggregate::post_dispatch_result")
+            .unwrap();
+
+        assert_eq!(find_attr(event, "succeeded"), "hook_a");
+        assert_eq!(find_attr(event, "failed"), "hook_broken");
+    }
+
+    #[rstest]
+    fn test_post_dispatch_skip_invalid_hook(
+        #[with(vec!["hook_a", "hook_eoa"])] mut deps: TestDeps,
+    ) {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("mailbox", &[]),
+            post_dispatch_msg(),
+        )
+        .unwrap();
+
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].id, 0);
+
+        let skipped = res
+            .events
+            .iter()
+            .filter(|v| v.ty == "hpl_hook_aggregate::skipped_invalid_hook")
+            .collect::<Vec<_>>();
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(find_attr(skipped[0], "hook"), "hook_eoa");
+
+        let res = reply_with(deps.as_mut(), 0, Ok(()));
+        let event = res
+            .events
+            .iter()
+            .find(|v| v.ty == "hpl_hook_aggregate::post_dispatch_result")
+            .unwrap();
+
+        assert_eq!(find_attr(event, "succeeded"), "hook_a");
+        assert_eq!(find_attr(event, "failed"), "hook_eoa");
+    }
+
+    #[rstest]
+    fn test_post_dispatch_strict(#[with(vec!["hook_a", "hook_b"], false)] mut deps: TestDeps) {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("mailbox", &[]),
+            post_dispatch_msg(),
+        )
+        .unwrap();
+
+        assert_eq!(res.messages.len(), 2);
+        assert!(res.messages.iter().all(|v| v.reply_on == ReplyOn::Never));
+        assert!(PENDING_DISPATCH
+            .may_load(deps.as_ref().storage)
+            .unwrap()
+            .is_none());
+    }
+
+    #[rstest]
+    #[case(addr("owner"))]
+    #[should_panic(expected = "unauthorized")]
+    #[case(addr("someone"))]
+    fn test_set_continue_on_error(mut deps: TestDeps, #[case] sender: Addr) {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender.as_str(), &[]),
+            ExecuteMsg::SetContinueOnError {
+                continue_on_error: false,
+            },
+        )
+        .map_err(|e| e.to_string())
+        .unwrap();
+
+        assert!(
+            !get_continue_on_error(deps.as_ref())
+                .unwrap()
+                .continue_on_error
+        );
+    }
+
+    #[rstest]
+    #[case(&[])]
+    #[should_panic(expected = "unexpected funds")]
+    #[case(&[coin(100, "untrn")])]
+    fn test_post_dispatch_unexpected_funds(
+        #[with(vec!["hook_a", "hook_b"], false, false)] mut deps: TestDeps,
+        #[case] funds: &[Coin],
+    ) {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("mailbox", funds),
+            post_dispatch_msg(),
+        )
+        .map_err(|e| e.to_string())
+        .unwrap();
+
+        assert_eq!(res.messages.len(), 2);
+    }
+
+    #[rstest]
+    fn test_post_dispatch_forward_funds(
+        #[with(vec!["hook_fee_untrn", "hook_b"], false)] mut deps: TestDeps,
+    ) {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("mailbox", &[coin(100, "untrn")]),
+            post_dispatch_msg(),
+        )
+        .unwrap();
+
+        assert_eq!(res.messages.len(), 2);
+    }
+
+    #[rstest]
+    #[case(addr("owner"))]
+    #[should_panic(expected = "unauthorized")]
+    #[case(addr("someone"))]
+    fn test_set_forward_funds(mut deps: TestDeps, #[case] sender: Addr) {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender.as_str(), &[]),
+            ExecuteMsg::SetForwardFunds {
+                forward_funds: false,
+            },
+        )
+        .map_err(|e| e.to_string())
+        .unwrap();
+
+        assert!(!get_forward_funds(deps.as_ref()).unwrap().forward_funds);
+    }
+
+    #[rstest]
+    #[case(true)]
+    #[should_panic(expected = "hook list must not be empty")]
+    #[case(false)]
+    fn test_instantiate_empty_hooks(#[case] allow_empty: bool) {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("deployer", &[]),
+            InstantiateMsg {
+                owner: "owner".to_string(),
+                hooks: vec![],
+                continue_on_error: false,
+                forward_funds: true,
+                allow_empty,
+                aggregate_metadata: false,
+            },
+        )
+        .map_err(|e| e.to_string())
+        .unwrap();
+
+        // nothing to dispatch to
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("mailbox", &[]),
+            post_dispatch_msg(),
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+    }
+
+    #[rstest]
+    #[case(true)]
+    #[should_panic(expected = "hook list must not be empty")]
+    #[case(false)]
+    fn test_set_hooks_empty(#[case] allow_empty: bool) {
+        let mut deps = deps(vec!["hook_a"], false, true, allow_empty, false);
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::SetHooks { hooks: vec![] },
+        )
+        .map_err(|e| e.to_string())
+        .unwrap();
+
+        assert!(HOOKS.load(deps.as_ref().storage).unwrap().is_empty());
+    }
+
+    #[rstest]
+    #[case(100)]
+    #[should_panic(expected = "cooldown active: 1 seconds remaining")]
+    #[case(99)]
+    fn test_set_hooks_cooldown(mut deps: TestDeps, #[case] elapsed: u64) {
+        let mut env = mock_env();
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("owner", &[]),
+            ExecuteMsg::Ownable(OwnableMsg::SetCooldown { cooldown: 100 }),
+        )
+        .unwrap();
+
+        // rejected within the cooldown that starts once it is set
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("owner", &[]),
+            ExecuteMsg::SetContinueOnError {
+                continue_on_error: false,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::CooldownActive {
+                seconds_remaining: 100
+            }
+        );
+
+        env.block.time = env.block.time.plus_seconds(elapsed);
+        execute(
+            deps.as_mut(),
+            env,
+            mock_info("owner", &[]),
+            ExecuteMsg::SetHooks {
+                hooks: vec!["hook_c".to_string()],
+            },
+        )
+        .map_err(|e| e.to_string())
+        .unwrap();
+
+        assert_eq!(
+            HOOKS.load(deps.as_ref().storage).unwrap(),
+            vec![addr("hook_c")]
+        );
+    }
+
+    fn forwarded_metadata(res: &Response) -> Vec<(String, HexBinary)> {
+        res.messages
+            .iter()
+            .map(|v| match &v.msg {
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr, msg, ..
+                }) => {
+                    let ExecuteMsg::PostDispatch(msg) = from_json(msg).unwrap() else {
+                        unreachable!("unexpected msg")
+                    };
+                    (contract_addr.clone(), msg.metadata)
+                }
+                _ => unreachable!("unexpected msg"),
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case(false, HexBinary::default(), (hex("01"), HexBinary::default()))]
+    #[case(false, hex("aaaa"), (hex("01aaaa"), hex("aaaa")))]
+    #[case(true, HexBinary::default(), (hex("01"), HexBinary::default()))]
+    #[case(
+        true,
+        AggregateMetadata::new(vec![(addr("hook_a"), hex("aaaa")), (addr("hook_b"), hex("bbbb"))]).into(),
+        (hex("01aaaa"), hex("bbbb"))
+    )]
+    fn test_post_dispatch_hook_metadata(
+        #[case] aggregate_metadata: bool,
+        #[case] metadata: HexBinary,
+        #[case] expected: (HexBinary, HexBinary),
+    ) {
+        let mut deps = deps(
+            vec!["hook_a", "hook_b"],
+            false,
+            true,
+            false,
+            aggregate_metadata,
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::SetHookMetadata {
+                hook: "hook_a".to_string(),
+                metadata: hex("01"),
+            },
+        )
+        .unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("mailbox", &[]),
+            ExecuteMsg::PostDispatch(PostDispatchMsg {
+                metadata,
+                message: hex(TEST_MESSAGE),
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            forwarded_metadata(&res),
+            vec![
+                ("hook_a".to_string(), expected.0),
+                ("hook_b".to_string(), expected.1)
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_post_dispatch_raw_metadata(
+        #[with(vec!["hook_a", "hook_b"], false)] mut deps: TestDeps,
+    ) {
+        // raw igp metadata, not an aggregate encoding
+        let metadata = hex("0001000000000000000000000000000000000000000000000000000000000000c350");
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("mailbox", &[]),
+            ExecuteMsg::PostDispatch(PostDispatchMsg {
+                metadata: metadata.clone(),
+                message: hex(TEST_MESSAGE),
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            forwarded_metadata(&res),
+            vec![
+                ("hook_a".to_string(), metadata.clone()),
+                ("hook_b".to_string(), metadata)
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(addr("owner"), "hook_a")]
+    #[should_panic(expected = "unauthorized")]
+    #[case(addr("someone"), "hook_a")]
+    #[should_panic(expected = "hook not found: hook_c")]
+    #[case(addr("owner"), "hook_c")]
+    fn test_set_hook_metadata(mut deps: TestDeps, #[case] sender: Addr, #[case] hook: &str) {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender.as_str(), &[]),
+            ExecuteMsg::SetHookMetadata {
+                hook: hook.to_string(),
+                metadata: hex("01"),
+            },
+        )
+        .map_err(|e| e.to_string())
+        .unwrap();
+
+        let res = get_hook_metadata(deps.as_ref(), hook.to_string()).unwrap();
+        assert_eq!(res.metadata, hex("01"));
+
+        // removed along with the hook
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender.as_str(), &[]),
+            ExecuteMsg::SetHooks {
+                hooks: vec!["hook_b".to_string()],
+            },
+        )
+        .unwrap();
+
+        let res = get_hook_metadata(deps.as_ref(), hook.to_string()).unwrap();
+        assert_eq!(res.metadata, HexBinary::default());
+    }
+
+    #[rstest]
+    fn test_quote_denoms(
+        #[with(vec!["hook_fee_uosmo", "hook_a", "hook_fee_untrn"])] deps: TestDeps,
+    ) {
+        let res = get_quote_denoms(deps.as_ref(), None).unwrap();
+        assert_eq!(res.denoms, vec!["untrn", "uosmo"]);
+    }
+
+    #[rstest]
+    #[case(None, vec!["uosmo"])]
+    #[case(Some(hex(TEST_MESSAGE)), vec!["uigp", "uosmo"])]
+    fn test_quote_denoms_decoding_hook(
+        #[with(vec!["hook_igp", "hook_fee_uosmo"])] deps: TestDeps,
+        #[case] message: Option<HexBinary>,
+        #[case] expected: Vec<&str>,
+    ) {
+        // the placeholder message targets domain 0, which the igp has no route for
+        let res = get_quote_denoms(deps.as_ref(), message).unwrap();
+        assert_eq!(res.denoms, expected);
+    }
+}
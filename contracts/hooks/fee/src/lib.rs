@@ -1,14 +1,18 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure, ensure_eq, BankMsg, Coin, CosmosMsg, Deps, DepsMut, Empty, Env, Event, MessageInfo,
-    QueryResponse, Response, StdError,
+    coin, ensure, ensure_eq, BankMsg, Coin, CosmosMsg, Deps, DepsMut, Empty, Env, Event,
+    MessageInfo, Order, QueryResponse, Response, StdError, StdResult, Uint128,
 };
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 use hpl_interface::{
     hook::{
-        fee::{ExecuteMsg, FeeHookMsg, FeeHookQueryMsg, FeeResponse, InstantiateMsg, QueryMsg},
-        HookQueryMsg, MailboxResponse, QuoteDispatchResponse,
+        fee::{
+            CollectedResponse, ExecuteMsg, FeeHookMsg, FeeHookQueryMsg, FeeResponse,
+            InstantiateMsg, QueryMsg, TotalCollectedEverResponse,
+        },
+        HookQueryMsg, MailboxResponse, MetadataFormat, MetadataFormatResponse,
+        QuoteDispatchResponse,
     },
     to_binary,
 };
@@ -38,6 +42,12 @@ pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const COIN_FEE_KEY: &str = "coin_fee";
 pub const COIN_FEE: Item<Coin> = Item::new(COIN_FEE_KEY);
 
+pub const COLLECTED_PREFIX: &str = "collected";
+pub const COLLECTED: Map<&str, Uint128> = Map::new(COLLECTED_PREFIX);
+
+pub const TOTAL_COLLECTED_PREFIX: &str = "total_collected";
+pub const TOTAL_COLLECTED: Map<&str, Uint128> = Map::new(TOTAL_COLLECTED_PREFIX);
+
 fn new_event(name: &str) -> Event {
     Event::new(format!("hpl_hook_fee::{}", name))
 }
@@ -71,6 +81,24 @@ fn get_fee(deps: Deps) -> Result<FeeResponse, ContractError> {
     Ok(FeeResponse { fee })
 }
 
+fn list_coins(deps: Deps, map: Map<&str, Uint128>) -> StdResult<Vec<Coin>> {
+    map.range(deps.storage, None, None, Order::Ascending)
+        .map(|v| v.map(|(denom, amount)| coin(amount.u128(), denom)))
+        .collect()
+}
+
+fn get_collected(deps: Deps) -> Result<CollectedResponse, ContractError> {
+    Ok(CollectedResponse {
+        collected: list_coins(deps, COLLECTED)?,
+    })
+}
+
+fn get_total_collected_ever(deps: Deps) -> Result<TotalCollectedEverResponse, ContractError> {
+    Ok(TotalCollectedEverResponse {
+        total: list_coins(deps, TOTAL_COLLECTED)?,
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -106,6 +134,8 @@ pub fn execute(
                 }
                 .into();
 
+                COLLECTED.clear(deps.storage);
+
                 Ok(Response::new()
                     .add_message(claim_msg)
                     .add_event(new_event("claim")))
@@ -121,6 +151,12 @@ pub fn execute(
                 StdError::generic_err("insufficient funds")
             );
 
+            for map in [COLLECTED, TOTAL_COLLECTED] {
+                map.update(deps.storage, &fee.denom, |v| -> StdResult<_> {
+                    Ok(v.unwrap_or_default().checked_add(supplied)?)
+                })?;
+            }
+
             Ok(Response::new().add_event(
                 new_event("post_dispatch")
                     .add_attribute("paid_denom", fee.denom)
@@ -136,9 +172,14 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, Contr
         QueryMsg::Ownable(msg) => Ok(hpl_ownable::handle_query(deps, env, msg)?),
         QueryMsg::Hook(msg) => match msg {
             HookQueryMsg::Mailbox {} => to_binary(get_mailbox(deps)),
+            HookQueryMsg::MetadataFormat {} => to_binary(get_metadata_format()),
             HookQueryMsg::QuoteDispatch(_) => to_binary(quote_dispatch(deps)),
         },
-        QueryMsg::FeeHook(FeeHookQueryMsg::Fee {}) => to_binary(get_fee(deps)),
+        QueryMsg::FeeHook(msg) => match msg {
+            FeeHookQueryMsg::Fee {} => to_binary(get_fee(deps)),
+            FeeHookQueryMsg::Collected {} => to_binary(get_collected(deps)),
+            FeeHookQueryMsg::TotalCollectedEver {} => to_binary(get_total_collected_ever(deps)),
+        },
     }
 }
 
@@ -148,6 +189,10 @@ fn get_mailbox(_deps: Deps) -> Result<MailboxResponse, ContractError> {
     })
 }
 
+fn get_metadata_format() -> Result<MetadataFormatResponse, ContractError> {
+    Ok(MetadataFormat::None.into())
+}
+
 fn quote_dispatch(deps: Deps) -> Result<QuoteDispatchResponse, ContractError> {
     let fee = COIN_FEE.load(deps.storage)?;
     Ok(QuoteDispatchResponse { fees: vec![fee] })
@@ -283,4 +328,46 @@ mod test {
         );
         println!("{:?}", res);
     }
+
+    #[rstest]
+    fn test_collected(mut deps: TestDeps) {
+        for amount in [100, 150, 100] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("mailbox", &[coin(amount, "uusd")]),
+                ExecuteMsg::PostDispatch(PostDispatchMsg {
+                    metadata: HexBinary::default(),
+                    message: gen_bz(100),
+                }),
+            )
+            .unwrap();
+        }
+
+        let res: CollectedResponse = query(
+            deps.as_ref(),
+            QueryMsg::FeeHook(FeeHookQueryMsg::Collected {}),
+        );
+        assert_eq!(res.collected, vec![coin(350, "uusd")]);
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::FeeHook(FeeHookMsg::Claim { recipient: None }),
+        )
+        .unwrap();
+
+        let res: CollectedResponse = query(
+            deps.as_ref(),
+            QueryMsg::FeeHook(FeeHookQueryMsg::Collected {}),
+        );
+        assert_eq!(res.collected, vec![]);
+
+        let res: TotalCollectedEverResponse = query(
+            deps.as_ref(),
+            QueryMsg::FeeHook(FeeHookQueryMsg::TotalCollectedEver {}),
+        );
+        assert_eq!(res.total, vec![coin(350, "uusd")]);
+    }
 }
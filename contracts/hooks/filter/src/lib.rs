@@ -0,0 +1,422 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    ensure_eq, Addr, BankMsg, Deps, DepsMut, Empty, Env, Event, MessageInfo, Order, QueryResponse,
+    Response, StdError, StdResult, Storage,
+};
+use cw_storage_plus::{Item, Map};
+use hpl_interface::{
+    hook::{
+        filter::{
+            BlockedDomainsResponse, ExecuteMsg, FilterHookQueryMsg, InnerHookResponse,
+            InstantiateMsg, QueryMsg,
+        },
+        post_dispatch, HookQueryMsg, MailboxResponse, MetadataFormat, MetadataFormatResponse,
+        PostDispatchMsg, QuoteDispatchMsg, QuoteDispatchResponse,
+    },
+    to_binary,
+    types::Message,
+};
+use hpl_ownable::get_owner;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    MigrationError(#[from] hpl_utils::MigrationError),
+
+    #[error("unauthorized")]
+    Unauthorized {},
+}
+
+// version info for migration info
+pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub const HOOK_KEY: &str = "hook";
+pub const HOOK: Item<Addr> = Item::new(HOOK_KEY);
+
+pub const BLOCKED_DOMAINS_PREFIX: &str = "blocked_domains";
+pub const BLOCKED_DOMAINS: Map<u32, bool> = Map::new(BLOCKED_DOMAINS_PREFIX);
+
+fn new_event(name: &str) -> Event {
+    Event::new(format!("hpl_hook_filter::{}", name))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = deps.api.addr_validate(&msg.owner)?;
+    let hook = deps.api.addr_validate(&msg.hook)?;
+
+    hpl_ownable::initialize(deps.storage, &owner)?;
+
+    HOOK.save(deps.storage, &hook)?;
+    for domain in &msg.blocked_domains {
+        BLOCKED_DOMAINS.save(deps.storage, *domain, &true)?;
+    }
+
+    Ok(Response::new().add_event(
+        new_event("initialize")
+            .add_attribute("sender", info.sender)
+            .add_attribute("owner", owner)
+            .add_attribute("hook", hook)
+            .add_attribute(
+                "blocked_domains",
+                msg.blocked_domains
+                    .iter()
+                    .map(|v| v.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+    ))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Ownable(msg) => Ok(hpl_ownable::handle(deps, env, info, msg)?),
+        ExecuteMsg::PostDispatch(msg) => dispatch(deps, info, msg),
+        ExecuteMsg::SetHook { hook } => {
+            ensure_eq!(
+                get_owner(deps.storage)?,
+                info.sender,
+                ContractError::Unauthorized {}
+            );
+
+            let hook = deps.api.addr_validate(&hook)?;
+
+            HOOK.save(deps.storage, &hook)?;
+
+            Ok(Response::new().add_event(
+                new_event("set_hook")
+                    .add_attribute("sender", info.sender)
+                    .add_attribute("hook", hook),
+            ))
+        }
+        ExecuteMsg::SetBlocked { domain, blocked } => {
+            ensure_eq!(
+                get_owner(deps.storage)?,
+                info.sender,
+                ContractError::Unauthorized {}
+            );
+
+            if blocked {
+                BLOCKED_DOMAINS.save(deps.storage, domain, &true)?;
+            } else {
+                BLOCKED_DOMAINS.remove(deps.storage, domain);
+            }
+
+            Ok(Response::new().add_event(
+                new_event("set_blocked")
+                    .add_attribute("sender", info.sender)
+                    .add_attribute("domain", domain.to_string())
+                    .add_attribute("blocked", blocked.to_string()),
+            ))
+        }
+    }
+}
+
+fn is_blocked(storage: &dyn Storage, domain: u32) -> bool {
+    BLOCKED_DOMAINS.has(storage, domain)
+}
+
+fn dispatch(
+    deps: DepsMut,
+    info: MessageInfo,
+    req: PostDispatchMsg,
+) -> Result<Response, ContractError> {
+    let decoded_msg: Message = req.message.clone().into();
+
+    if is_blocked(deps.storage, decoded_msg.dest_domain) {
+        let mut resp = Response::new();
+
+        // nothing is forwarded, so hand the funds back
+        if !info.funds.is_empty() {
+            resp = resp.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: info.funds,
+            });
+        }
+
+        return Ok(resp.add_event(
+            new_event("dropped")
+                .add_attribute("domain", decoded_msg.dest_domain.to_string())
+                .add_attribute("message_id", decoded_msg.id().to_hex()),
+        ));
+    }
+
+    let hook = HOOK.load(deps.storage)?;
+
+    let msg = post_dispatch(&hook, req.metadata, req.message, Some(info.funds))?;
+
+    Ok(Response::new().add_message(msg).add_event(
+        new_event("post_dispatch")
+            .add_attribute("domain", decoded_msg.dest_domain.to_string())
+            .add_attribute("hook", hook)
+            .add_attribute("message_id", decoded_msg.id().to_hex()),
+    ))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, ContractError> {
+    match msg {
+        QueryMsg::Ownable(msg) => Ok(hpl_ownable::handle_query(deps, env, msg)?),
+        QueryMsg::Hook(msg) => match msg {
+            HookQueryMsg::Mailbox {} => to_binary(get_mailbox(deps)),
+            HookQueryMsg::MetadataFormat {} => to_binary(get_metadata_format()),
+            HookQueryMsg::QuoteDispatch(msg) => to_binary(quote_dispatch(deps, msg)),
+        },
+        QueryMsg::FilterHook(msg) => match msg {
+            FilterHookQueryMsg::InnerHook {} => to_binary(get_hook(deps)),
+            FilterHookQueryMsg::BlockedDomains {} => to_binary(get_blocked_domains(deps)),
+        },
+    }
+}
+
+fn get_mailbox(_deps: Deps) -> Result<MailboxResponse, ContractError> {
+    Ok(MailboxResponse {
+        mailbox: "unrestricted".to_string(),
+    })
+}
+
+fn get_metadata_format() -> Result<MetadataFormatResponse, ContractError> {
+    Ok(MetadataFormat::Passthrough.into())
+}
+
+fn get_hook(deps: Deps) -> Result<InnerHookResponse, ContractError> {
+    Ok(InnerHookResponse {
+        hook: HOOK.load(deps.storage)?.into(),
+    })
+}
+
+fn get_blocked_domains(deps: Deps) -> Result<BlockedDomainsResponse, ContractError> {
+    let domains = BLOCKED_DOMAINS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+
+    Ok(BlockedDomainsResponse { domains })
+}
+
+fn quote_dispatch(
+    deps: Deps,
+    req: QuoteDispatchMsg,
+) -> Result<QuoteDispatchResponse, ContractError> {
+    let decoded_msg: Message = req.message.clone().into();
+
+    if is_blocked(deps.storage, decoded_msg.dest_domain) {
+        return Ok(QuoteDispatchResponse { fees: vec![] });
+    }
+
+    let hook = HOOK.load(deps.storage)?;
+
+    Ok(hpl_interface::hook::quote_dispatch(
+        &deps.querier,
+        hook,
+        req.metadata,
+        req.message,
+    )?)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
+    hpl_utils::migrate(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::default())
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{
+        coin, from_json,
+        testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
+        to_json_binary, ContractResult, CosmosMsg, HexBinary, OwnedDeps, SystemResult, WasmMsg,
+        WasmQuery,
+    };
+    use hpl_interface::{
+        build_test_executor, build_test_querier,
+        hook::{ExpectedHookMsg, ExpectedHookQueryMsg},
+    };
+    use ibcx_test_utils::{addr, gen_bz};
+    use rstest::{fixture, rstest};
+
+    use super::*;
+
+    build_test_executor!(crate::execute);
+    build_test_querier!(crate::query);
+
+    type TestDeps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+    const OWNER: &str = "owner";
+    const DEPLOYER: &str = "deployer";
+    const MAILBOX: &str = "mailbox";
+    const INNER_HOOK: &str = "inner_hook";
+
+    const BLOCKED: u32 = 26657;
+    const ALLOWED: u32 = 26658;
+
+    #[fixture]
+    fn deps() -> TestDeps {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(DEPLOYER, &[]),
+            InstantiateMsg {
+                owner: OWNER.to_string(),
+                hook: INNER_HOOK.to_string(),
+                blocked_domains: vec![BLOCKED],
+            },
+        )
+        .unwrap();
+
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, msg } => {
+                assert_eq!(contract_addr, INNER_HOOK);
+                let ExpectedHookQueryMsg::Hook(HookQueryMsg::QuoteDispatch(_)) =
+                    from_json(msg).unwrap()
+                else {
+                    unreachable!("unexpected query")
+                };
+
+                let res = QuoteDispatchResponse {
+                    fees: vec![coin(100, "utest")],
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&res).unwrap()))
+            }
+            _ => unreachable!("unexpected query"),
+        });
+
+        deps
+    }
+
+    fn message(dest_domain: u32) -> HexBinary {
+        let mut msg: Message = gen_bz(100).into();
+        msg.dest_domain = dest_domain;
+        msg.into()
+    }
+
+    #[rstest]
+    fn test_init(deps: TestDeps) {
+        let res: InnerHookResponse = test_query(
+            deps.as_ref(),
+            QueryMsg::FilterHook(FilterHookQueryMsg::InnerHook {}),
+        );
+        assert_eq!(res.hook, INNER_HOOK);
+
+        let res: BlockedDomainsResponse = test_query(
+            deps.as_ref(),
+            QueryMsg::FilterHook(FilterHookQueryMsg::BlockedDomains {}),
+        );
+        assert_eq!(res.domains, vec![BLOCKED]);
+    }
+
+    #[rstest]
+    #[case(OWNER)]
+    #[should_panic(expected = "unauthorized")]
+    #[case(DEPLOYER)]
+    fn test_set_blocked(mut deps: TestDeps, #[case] sender: &str) {
+        for (domain, blocked) in [(ALLOWED, true), (BLOCKED, false)] {
+            test_execute(
+                deps.as_mut(),
+                &addr(sender),
+                ExecuteMsg::SetBlocked { domain, blocked },
+                vec![],
+            );
+        }
+
+        let res: BlockedDomainsResponse = test_query(
+            deps.as_ref(),
+            QueryMsg::FilterHook(FilterHookQueryMsg::BlockedDomains {}),
+        );
+        assert_eq!(res.domains, vec![ALLOWED]);
+    }
+
+    #[rstest]
+    fn test_post_dispatch_dropped(mut deps: TestDeps) {
+        let funds = vec![coin(100, "utest")];
+
+        let res = test_execute(
+            deps.as_mut(),
+            &addr(MAILBOX),
+            ExecuteMsg::PostDispatch(PostDispatchMsg {
+                metadata: HexBinary::default(),
+                message: message(BLOCKED),
+            }),
+            funds.clone(),
+        );
+
+        assert_eq!(res.events[0].ty, "hpl_hook_filter::dropped");
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: MAILBOX.to_string(),
+                amount: funds,
+            })
+        );
+
+        let res: QuoteDispatchResponse = test_query(
+            deps.as_ref(),
+            QueryMsg::Hook(HookQueryMsg::QuoteDispatch(QuoteDispatchMsg {
+                metadata: HexBinary::default(),
+                message: message(BLOCKED),
+            })),
+        );
+        assert!(res.fees.is_empty());
+    }
+
+    #[rstest]
+    fn test_post_dispatch_forwarded(mut deps: TestDeps) {
+        let funds = vec![coin(100, "utest")];
+        let req = PostDispatchMsg {
+            metadata: HexBinary::default(),
+            message: message(ALLOWED),
+        };
+
+        let res = test_execute(
+            deps.as_mut(),
+            &addr(MAILBOX),
+            ExecuteMsg::PostDispatch(req.clone()),
+            funds.clone(),
+        );
+
+        assert_eq!(res.events[0].ty, "hpl_hook_filter::post_dispatch");
+
+        let CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            msg,
+            funds: forwarded,
+        }) = &res.messages[0].msg
+        else {
+            unreachable!("unexpected msg")
+        };
+        assert_eq!(contract_addr, INNER_HOOK);
+        assert_eq!(forwarded, &funds);
+        assert_eq!(
+            from_json::<ExpectedHookMsg>(msg).unwrap(),
+            ExpectedHookMsg::PostDispatch(req.clone())
+        );
+
+        let res: QuoteDispatchResponse = test_query(
+            deps.as_ref(),
+            QueryMsg::Hook(HookQueryMsg::QuoteDispatch(QuoteDispatchMsg {
+                metadata: req.metadata,
+                message: req.message,
+            })),
+        );
+        assert_eq!(res.fees, funds);
+    }
+}
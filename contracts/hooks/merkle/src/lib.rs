@@ -1,18 +1,19 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure_eq, Addr, Deps, DepsMut, Empty, Env, Event, MessageInfo, QueryResponse, Response,
-    StdError,
+    ensure, ensure_eq, Addr, Deps, DepsMut, Empty, Env, Event, HexBinary, MessageInfo, Order,
+    QueryResponse, Response, StdError, StdResult,
 };
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 use hpl_interface::{
-    core::mailbox::{LatestDispatchedIdResponse, MailboxQueryMsg},
+    core::mailbox::{LatestDispatchedIdResponse, LocalDomainResponse, MailboxQueryMsg},
     hook::{
-        merkle::{self, ExecuteMsg, InstantiateMsg, MerkleHookQueryMsg, QueryMsg},
-        HookQueryMsg, MailboxResponse, PostDispatchMsg, QuoteDispatchResponse,
+        merkle::{self, ExecuteMsg, InstantiateMsg, MerkleHookQueryMsg, MigrateMsg, QueryMsg},
+        HookQueryMsg, MailboxResponse, MetadataFormat, MetadataFormatResponse, PostDispatchMsg,
+        QuoteDispatchResponse,
     },
     to_binary,
-    types::{MerkleTree, Message},
+    types::{bech32_to_h256, domain_hash, MerkleTree, Message},
 };
 
 #[derive(thiserror::Error, Debug, PartialEq)]
@@ -46,9 +47,15 @@ pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const MAILBOX_KEY: &str = "mailbox";
 pub const MAILBOX: Item<Addr> = Item::new(MAILBOX_KEY);
 
+pub const TRUSTED_HOOKS_PREFIX: &str = "trusted_hooks";
+pub const TRUSTED_HOOKS: Map<&Addr, Empty> = Map::new(TRUSTED_HOOKS_PREFIX);
+
 pub const MESSAGE_TREE_KEY: &str = "message_tree";
 pub const MESSAGE_TREE: Item<MerkleTree> = Item::new(MESSAGE_TREE_KEY);
 
+pub const LATEST_INSERTED_ID_KEY: &str = "latest_inserted_id";
+pub const LATEST_INSERTED_ID: Item<HexBinary> = Item::new(LATEST_INSERTED_ID_KEY);
+
 fn new_event(name: &str) -> Event {
     Event::new(format!("hpl_hook_merkle::{}", name))
 }
@@ -62,29 +69,44 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    let owner = deps.api.addr_validate(&msg.owner)?;
     let mailbox = deps.api.addr_validate(&msg.mailbox)?;
 
+    hpl_ownable::initialize(deps.storage, &owner)?;
+
     MAILBOX.save(deps.storage, &mailbox)?;
     MESSAGE_TREE.save(deps.storage, &MerkleTree::default())?;
 
+    for hook in msg.trusted_hooks.iter() {
+        TRUSTED_HOOKS.save(deps.storage, &deps.api.addr_validate(hook)?, &Empty {})?;
+    }
+
     Ok(Response::new().add_event(
         new_event("initialize")
             .add_attribute("sender", info.sender)
-            .add_attribute("mailbox", mailbox),
+            .add_attribute("owner", owner)
+            .add_attribute("mailbox", mailbox)
+            .add_attribute("trusted_hooks", msg.trusted_hooks.join(",")),
     ))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
+    env: Env,
+    info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
+        ExecuteMsg::Ownable(msg) => Ok(hpl_ownable::handle(deps, env, info, msg)?),
         ExecuteMsg::PostDispatch(PostDispatchMsg { message, .. }) => {
             let mailbox = MAILBOX.load(deps.storage)?;
 
+            ensure!(
+                info.sender == mailbox || TRUSTED_HOOKS.has(deps.storage, &info.sender),
+                ContractError::unauthorized("sender is not the mailbox or a trusted hook")
+            );
+
             let latest_dispatch_id = deps
                 .querier
                 .query_wasm_smart::<LatestDispatchedIdResponse>(
@@ -95,11 +117,18 @@ pub fn execute(
 
             let decoded_msg: Message = message.into();
 
+            // trusted hooks relay on behalf of the mailbox. still, only the message being
+            // dispatched gets in, and only once
             ensure_eq!(
                 latest_dispatch_id,
                 decoded_msg.id(),
                 ContractError::unauthorized("message is not dispatching")
             );
+            ensure!(
+                LATEST_INSERTED_ID.may_load(deps.storage)?.as_ref() != Some(&latest_dispatch_id),
+                ContractError::unauthorized("message is already inserted")
+            );
+            LATEST_INSERTED_ID.save(deps.storage, &latest_dispatch_id)?;
 
             let mut tree = MESSAGE_TREE.load(deps.storage)?;
             let index = tree.count;
@@ -117,16 +146,39 @@ pub fn execute(
                     new_event("inserted_into_tree").add_attribute("index", index.to_string()),
                 ))
         }
+        ExecuteMsg::SetTrustedHook { hook, trusted } => {
+            ensure_eq!(
+                hpl_ownable::get_owner(deps.storage)?,
+                info.sender,
+                ContractError::unauthorized("not an owner")
+            );
+
+            let hook_addr = deps.api.addr_validate(&hook)?;
+            if trusted {
+                TRUSTED_HOOKS.save(deps.storage, &hook_addr, &Empty {})?;
+            } else {
+                TRUSTED_HOOKS.remove(deps.storage, &hook_addr);
+            }
+
+            Ok(Response::new().add_event(
+                new_event("set_trusted_hook")
+                    .add_attribute("sender", info.sender)
+                    .add_attribute("hook", hook)
+                    .add_attribute("trusted", trusted.to_string()),
+            ))
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<QueryResponse, ContractError> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, ContractError> {
     use MerkleHookQueryMsg::*;
 
     match msg {
+        QueryMsg::Ownable(msg) => Ok(hpl_ownable::handle_query(deps, env, msg)?),
         QueryMsg::Hook(msg) => match msg {
             HookQueryMsg::Mailbox {} => to_binary(get_mailbox(deps)),
+            HookQueryMsg::MetadataFormat {} => to_binary(get_metadata_format()),
             HookQueryMsg::QuoteDispatch(_) => to_binary(quote_dispatch()),
         },
         QueryMsg::MerkleHook(msg) => match msg {
@@ -135,6 +187,8 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<QueryResponse, Cont
             Branch {} => to_binary(get_tree_branch(deps)),
             Tree {} => to_binary(get_tree(deps)),
             CheckPoint {} => to_binary(get_tree_checkpoint(deps)),
+            CheckpointDigestPreimage {} => to_binary(get_checkpoint_digest_preimage(deps, env)),
+            TrustedHooks {} => to_binary(get_trusted_hooks(deps)),
         },
     }
 }
@@ -145,6 +199,19 @@ fn get_mailbox(deps: Deps) -> Result<MailboxResponse, ContractError> {
     })
 }
 
+fn get_trusted_hooks(deps: Deps) -> Result<merkle::TrustedHooksResponse, ContractError> {
+    let hooks = TRUSTED_HOOKS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|v| v.map(String::from))
+        .collect::<StdResult<_>>()?;
+
+    Ok(merkle::TrustedHooksResponse { hooks })
+}
+
+fn get_metadata_format() -> Result<MetadataFormatResponse, ContractError> {
+    Ok(MetadataFormat::None.into())
+}
+
 fn quote_dispatch() -> Result<QuoteDispatchResponse, ContractError> {
     Ok(QuoteDispatchResponse { fees: vec![] })
 }
@@ -193,9 +260,46 @@ fn get_tree_checkpoint(deps: Deps) -> Result<merkle::CheckPointResponse, Contrac
     })
 }
 
+fn get_checkpoint_digest_preimage(
+    deps: Deps,
+    env: Env,
+) -> Result<merkle::CheckpointDigestPreimageResponse, ContractError> {
+    let mailbox = MAILBOX.load(deps.storage)?;
+
+    let local_domain = deps
+        .querier
+        .query_wasm_smart::<LocalDomainResponse>(&mailbox, &MailboxQueryMsg::LocalDomain {}.wrap())?
+        .local_domain;
+
+    let merkle_tree = bech32_to_h256(env.contract.address.as_str())?;
+    let checkpoint = get_tree_checkpoint(deps)?;
+
+    let mut bz = vec![];
+    bz.append(&mut domain_hash(local_domain, merkle_tree.to_vec().into()).to_vec());
+    bz.append(&mut checkpoint.root.to_vec());
+    bz.append(&mut checkpoint.count.to_be_bytes().to_vec());
+
+    Ok(merkle::CheckpointDigestPreimageResponse {
+        preimage: bz.into(),
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
     hpl_utils::migrate(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if let Some(owner) = msg.owner {
+        ensure!(
+            hpl_ownable::get_owner(deps.storage).is_err(),
+            StdError::generic_err("owner is already set")
+        );
+        hpl_ownable::initialize(deps.storage, &deps.api.addr_validate(&owner)?)?;
+    }
+
+    for hook in msg.trusted_hooks.iter() {
+        TRUSTED_HOOKS.save(deps.storage, &deps.api.addr_validate(hook)?, &Empty {})?;
+    }
+
     Ok(Response::default())
 }
 
@@ -210,7 +314,10 @@ mod test {
     };
 
     use hpl_interface::{
-        build_test_executor, build_test_querier, core::mailbox, hook::QuoteDispatchMsg,
+        build_test_executor, build_test_querier,
+        core::mailbox,
+        hook::QuoteDispatchMsg,
+        types::{keccak256_hash, multisig_hash},
     };
     use ibcx_test_utils::hex;
     use rstest::{fixture, rstest};
@@ -234,7 +341,9 @@ mod test {
             mock_env(),
             mock_info(sender.as_str(), &[]),
             InstantiateMsg {
+                owner: "owner".to_string(),
                 mailbox: mailbox.to_string(),
+                trusted_hooks: vec!["aggregate_hook".to_string()],
             },
         )
         .unwrap();
@@ -252,6 +361,63 @@ mod test {
             MerkleTree::default(),
             MESSAGE_TREE.load(deps.as_ref().storage).unwrap()
         );
+        assert_eq!(
+            "owner",
+            hpl_ownable::get_owner(deps.as_ref().storage)
+                .unwrap()
+                .as_str()
+        );
+    }
+
+    #[rstest]
+    #[case("owner")]
+    #[should_panic(expected = "unauthorized. reason: not an owner")]
+    #[case("someone")]
+    fn test_set_trusted_hook(mut deps: TestDeps, #[case] sender: &str) {
+        let set_trusted = |deps: &mut TestDeps, hook: &str, trusted: bool| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(sender, &[]),
+                ExecuteMsg::SetTrustedHook {
+                    hook: hook.to_string(),
+                    trusted,
+                },
+            )
+            .map_err(|e| e.to_string())
+            .unwrap();
+
+            let res: merkle::TrustedHooksResponse = test_query(
+                deps.as_ref(),
+                QueryMsg::MerkleHook(MerkleHookQueryMsg::TrustedHooks {}),
+            );
+            res.hooks
+        };
+
+        assert_eq!(
+            set_trusted(&mut deps, "routing_hook", true),
+            vec!["aggregate_hook", "routing_hook"]
+        );
+        assert_eq!(
+            set_trusted(&mut deps, "aggregate_hook", false),
+            vec!["routing_hook"]
+        );
+
+        // a distrusted hook can't insert anymore
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("aggregate_hook", &[]),
+            ExecuteMsg::PostDispatch(PostDispatchMsg {
+                metadata: HexBinary::default(),
+                message: hex(TEST_MESSAGE),
+            }),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::unauthorized("sender is not the mailbox or a trusted hook")
+        );
     }
 
     const TEST_MESSAGE: &str = "dc7b240deb74cca40636435ade8514b7ac35176e085f810e92dbc8bdb54a3d554ef32b9f724df19861d7e9b89a8ed11a4ecb35512f58b18b6607689cb9ba36dcf0f4af3cc1c7128c6cf0b47ea1f1aa07a4fe64502edd9a2b2e2dddf770776040efa24f19";
@@ -259,8 +425,11 @@ mod test {
 
     #[rstest]
     #[case("mailbox", None)]
-    #[should_panic(expected = "unauthorized")]
+    #[case("aggregate_hook", None)]
+    #[should_panic(expected = "unauthorized. reason: message is not dispatching")]
     #[case("mailbox", Some(hex(TEST_MESSAGE_FAIL)))]
+    #[should_panic(expected = "unauthorized. reason: sender is not the mailbox or a trusted hook")]
+    #[case("someone", None)]
     fn test_post_dispatch(
         mut deps: TestDeps,
         #[case] sender: &str,
@@ -287,14 +456,16 @@ mod test {
             }
         });
 
+        let msg = ExecuteMsg::PostDispatch(PostDispatchMsg {
+            metadata: HexBinary::default(),
+            message: message.unwrap_or(hex(TEST_MESSAGE)),
+        });
+
         let res = execute(
             deps.as_mut(),
             mock_env(),
             mock_info(sender, &[]),
-            ExecuteMsg::PostDispatch(PostDispatchMsg {
-                metadata: HexBinary::default(),
-                message: message.unwrap_or(hex(TEST_MESSAGE)),
-            }),
+            msg.clone(),
         )
         .map_err(|e| e.to_string())
         .unwrap();
@@ -314,6 +485,14 @@ mod test {
         let tree = MESSAGE_TREE.load(deps.as_ref().storage).unwrap();
         assert_ne!(tree, MerkleTree::default());
         assert_eq!(tree.count, 1);
+
+        // replaying the dispatched message doesn't insert it again
+        let err = execute(deps.as_mut(), mock_env(), mock_info("mailbox", &[]), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::unauthorized("message is already inserted")
+        );
+        assert_eq!(MESSAGE_TREE.load(deps.as_ref().storage).unwrap().count, 1);
     }
 
     #[rstest]
@@ -322,6 +501,13 @@ mod test {
             test_query(deps.as_ref(), QueryMsg::Hook(HookQueryMsg::Mailbox {}));
         assert_eq!("mailbox", res.mailbox.as_str());
 
+        let res: MetadataFormatResponse = test_query(
+            deps.as_ref(),
+            QueryMsg::Hook(HookQueryMsg::MetadataFormat {}),
+        );
+        assert_eq!(res.format, MetadataFormat::None);
+        assert!(res.lengths.is_empty());
+
         let res: QuoteDispatchResponse = test_query(
             deps.as_ref(),
             QueryMsg::Hook(HookQueryMsg::QuoteDispatch(QuoteDispatchMsg::default())),
@@ -358,4 +544,56 @@ mod test {
         );
         assert_eq!(res.root, MerkleTree::default().root().unwrap());
     }
+
+    #[rstest]
+    fn test_checkpoint_digest_preimage(mut deps: TestDeps) {
+        let local_domain = 26657;
+        let message_id = hex("a6d8af738f99da8a0a8a3611e6c777bc9ebf42b1f685a5ff6b1ff1f2b7b70f45");
+
+        let mut tree = MerkleTree::default();
+        tree.insert(message_id.clone()).unwrap();
+        MESSAGE_TREE.save(deps.as_mut().storage, &tree).unwrap();
+
+        deps.querier.update_wasm(move |query| {
+            use cosmwasm_std::{to_json_binary, ContractResult, SystemResult};
+
+            let msg = match query {
+                WasmQuery::Smart { msg, .. } => from_json(msg).unwrap(),
+                _ => unreachable!("noo"),
+            };
+
+            match msg {
+                mailbox::QueryMsg::Mailbox(MailboxQueryMsg::LocalDomain {}) => {
+                    let res = LocalDomainResponse { local_domain };
+                    SystemResult::Ok(ContractResult::Ok(to_json_binary(&res).unwrap()))
+                }
+                _ => unreachable!("unwrap noo"),
+            }
+        });
+
+        let mut env = mock_env();
+        env.contract.address =
+            Addr::unchecked("osmo1466nf3zuxpya8q9emxukd7vftaf6h4psr0a07srl5zw74zh84yjqkk0zfx");
+
+        let res: merkle::CheckpointDigestPreimageResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::MerkleHook(MerkleHookQueryMsg::CheckpointDigestPreimage {}),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let merkle_tree = bech32_to_h256(env.contract.address.as_str()).unwrap();
+        let expected = multisig_hash(
+            domain_hash(local_domain, merkle_tree.to_vec().into()).to_vec(),
+            tree.root().unwrap().to_vec(),
+            0,
+            message_id.to_vec(),
+        );
+
+        let digest = keccak256_hash(&[res.preimage.as_slice(), message_id.as_slice()].concat());
+        assert_eq!(digest, expected);
+    }
 }
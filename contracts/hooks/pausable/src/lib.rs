@@ -6,7 +6,8 @@ use cosmwasm_std::{
 use hpl_interface::{
     hook::{
         pausable::{ExecuteMsg, InstantiateMsg, QueryMsg},
-        HookQueryMsg, MailboxResponse, QuoteDispatchResponse,
+        HookQueryMsg, MailboxResponse, MetadataFormat, MetadataFormatResponse,
+        QuoteDispatchResponse,
     },
     to_binary,
 };
@@ -87,6 +88,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, Contr
         QueryMsg::Ownable(msg) => Ok(hpl_ownable::handle_query(deps, env, msg)?),
         QueryMsg::Hook(msg) => match msg {
             HookQueryMsg::Mailbox {} => to_binary(get_mailbox(deps)),
+            HookQueryMsg::MetadataFormat {} => to_binary(get_metadata_format()),
             HookQueryMsg::QuoteDispatch(_) => to_binary(quote_dispatch()),
         },
     }
@@ -98,6 +100,10 @@ fn get_mailbox(_deps: Deps) -> Result<MailboxResponse, ContractError> {
     })
 }
 
+fn get_metadata_format() -> Result<MetadataFormatResponse, ContractError> {
+    Ok(MetadataFormat::None.into())
+}
+
 fn quote_dispatch() -> Result<QuoteDispatchResponse, ContractError> {
     Ok(QuoteDispatchResponse { fees: vec![] })
 }
@@ -166,7 +172,7 @@ mod test {
     #[case("owner", true)]
     fn test_post_dispatch(mut deps: TestDeps, #[case] sender: &str, #[case] paused: bool) {
         if paused {
-            hpl_pausable::pause(deps.as_mut().storage, &addr("owner")).unwrap();
+            hpl_pausable::pause(deps.as_mut().storage, &mock_env(), &addr("owner"), None).unwrap();
         }
 
         execute(
@@ -13,7 +13,8 @@ use hpl_interface::{
             ClearCustomHookMsg, CustomHookResponse, CustomHooksResponse, CustomRoutingHookQueryMsg,
             ExecuteMsg, InstantiateMsg, QueryMsg, RegisterCustomHookMsg,
         },
-        HookQueryMsg, MailboxResponse, PostDispatchMsg, QuoteDispatchMsg, QuoteDispatchResponse,
+        HookQueryMsg, MailboxResponse, MetadataFormat, MetadataFormatResponse, PostDispatchMsg,
+        QuoteDispatchMsg, QuoteDispatchResponse,
     },
     range_option, to_binary,
     types::Message,
@@ -32,6 +33,9 @@ pub enum ContractError {
     #[error("{0}")]
     MigrationError(#[from] hpl_utils::MigrationError),
 
+    #[error("{0}")]
+    RouterError(#[from] hpl_router::RouterError),
+
     #[error("unauthorized")]
     Unauthorized {},
 
@@ -100,6 +104,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, Contr
         QueryMsg::Router(msg) => Ok(hpl_router::handle_query(deps, env, msg)?),
         QueryMsg::Hook(msg) => match msg {
             HookQueryMsg::Mailbox {} => to_binary(get_mailbox(deps)),
+            HookQueryMsg::MetadataFormat {} => to_binary(get_metadata_format()),
             HookQueryMsg::QuoteDispatch(msg) => to_binary(quote_dispatch(deps, msg)),
         },
         QueryMsg::CustomRoutingHook(msg) => match msg {
@@ -123,6 +128,10 @@ fn get_mailbox(_deps: Deps) -> Result<MailboxResponse, ContractError> {
     })
 }
 
+fn get_metadata_format() -> Result<MetadataFormatResponse, ContractError> {
+    Ok(MetadataFormat::Passthrough.into())
+}
+
 fn get_custom_hook(
     deps: Deps,
     dest_domain: u32,
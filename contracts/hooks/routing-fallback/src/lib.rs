@@ -10,7 +10,8 @@ use hpl_interface::{
     hook::{
         self,
         routing_fallback::{ExecuteMsg, InstantiateMsg, QueryMsg},
-        HookQueryMsg, MailboxResponse, PostDispatchMsg, QuoteDispatchMsg, QuoteDispatchResponse,
+        HookQueryMsg, MailboxResponse, MetadataFormat, MetadataFormatResponse, PostDispatchMsg,
+        QuoteDispatchMsg, QuoteDispatchResponse,
     },
     to_binary,
     types::Message,
@@ -28,6 +29,9 @@ pub enum ContractError {
     #[error("{0}")]
     MigrationError(#[from] hpl_utils::MigrationError),
 
+    #[error("{0}")]
+    RouterError(#[from] hpl_router::RouterError),
+
     #[error("unauthorized")]
     Unauthorized {},
 }
@@ -101,6 +105,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, Contr
         QueryMsg::Router(msg) => Ok(hpl_router::handle_query(deps, env, msg)?),
         QueryMsg::Hook(msg) => match msg {
             HookQueryMsg::Mailbox {} => to_binary(get_mailbox(deps)),
+            HookQueryMsg::MetadataFormat {} => to_binary(get_metadata_format()),
             HookQueryMsg::QuoteDispatch(msg) => to_binary(quote_dispatch(deps, msg)),
         },
     }
@@ -112,6 +117,10 @@ fn get_mailbox(_deps: Deps) -> Result<MailboxResponse, ContractError> {
     })
 }
 
+fn get_metadata_format() -> Result<MetadataFormatResponse, ContractError> {
+    Ok(MetadataFormat::Passthrough.into())
+}
+
 fn route(storage: &dyn Storage, message: &HexBinary) -> Result<(Message, Addr), ContractError> {
     let decoded_msg: Message = message.clone().into();
     let dest_domain = decoded_msg.dest_domain;
@@ -1,18 +1,21 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    wasm_execute, Addr, Deps, DepsMut, Empty, Env, Event, HexBinary, MessageInfo, QueryResponse,
-    Response, StdError, Storage,
+    ensure_eq, wasm_execute, Addr, Deps, DepsMut, Empty, Env, Event, HexBinary, MessageInfo,
+    QueryResponse, Response, StdError, Storage,
 };
+use cw_storage_plus::Item;
 
 use hpl_interface::{
     hook::{
         self,
         routing::{ExecuteMsg, InstantiateMsg, QueryMsg},
-        HookQueryMsg, MailboxResponse, PostDispatchMsg, QuoteDispatchMsg, QuoteDispatchResponse,
+        HookQueryMsg, MailboxResponse, MetadataFormat, MetadataFormatResponse, PostDispatchMsg,
+        QuoteDispatchMsg, QuoteDispatchResponse,
     },
     to_binary,
     types::Message,
+    EventVerbosity,
 };
 
 #[derive(thiserror::Error, Debug, PartialEq)]
@@ -26,6 +29,9 @@ pub enum ContractError {
     #[error("{0}")]
     MigrationError(#[from] hpl_utils::MigrationError),
 
+    #[error("{0}")]
+    RouterError(#[from] hpl_router::RouterError),
+
     #[error("unauthorized")]
     Unauthorized {},
 
@@ -37,6 +43,10 @@ pub enum ContractError {
 pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// storage definition for how many attributes the post_dispatch event carries
+pub const EVENT_VERBOSITY_KEY: &str = "event_verbosity";
+pub const EVENT_VERBOSITY: Item<EventVerbosity> = Item::new(EVENT_VERBOSITY_KEY);
+
 fn new_event(name: &str) -> Event {
     Event::new(format!("hpl_hook_routing::{}", name))
 }
@@ -72,6 +82,21 @@ pub fn execute(
         ExecuteMsg::Ownable(msg) => Ok(hpl_ownable::handle(deps, env, info, msg)?),
         ExecuteMsg::Router(msg) => Ok(hpl_router::handle(deps, env, info, msg)?),
         ExecuteMsg::PostDispatch(msg) => post_dispatch(deps, info, msg),
+        ExecuteMsg::SetEventVerbosity { verbosity } => {
+            ensure_eq!(
+                hpl_ownable::get_owner(deps.storage)?,
+                info.sender,
+                ContractError::Unauthorized {}
+            );
+
+            EVENT_VERBOSITY.save(deps.storage, &verbosity)?;
+
+            Ok(Response::new().add_event(
+                new_event("set_event_verbosity")
+                    .add_attribute("sender", info.sender)
+                    .add_attribute("verbosity", verbosity.to_string()),
+            ))
+        }
     }
 }
 
@@ -82,6 +107,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, Contr
         QueryMsg::Router(msg) => Ok(hpl_router::handle_query(deps, env, msg)?),
         QueryMsg::Hook(msg) => match msg {
             HookQueryMsg::Mailbox {} => to_binary(get_mailbox(deps)),
+            HookQueryMsg::MetadataFormat {} => to_binary(get_metadata_format()),
             HookQueryMsg::QuoteDispatch(msg) => to_binary(quote_dispatch(deps, msg)),
         },
     }
@@ -93,6 +119,10 @@ fn get_mailbox(_deps: Deps) -> Result<MailboxResponse, ContractError> {
     })
 }
 
+fn get_metadata_format() -> Result<MetadataFormatResponse, ContractError> {
+    Ok(MetadataFormat::Passthrough.into())
+}
+
 fn quote_dispatch(
     deps: Deps,
     req: QuoteDispatchMsg,
@@ -130,12 +160,24 @@ fn post_dispatch(
 
     let hook_msg = wasm_execute(&routed_hook, &req.wrap(), vec![])?;
 
-    Ok(Response::new().add_message(hook_msg).add_event(
-        new_event("post_dispatch")
+    let verbosity = EVENT_VERBOSITY.may_load(deps.storage)?.unwrap_or_default();
+
+    let mut event = new_event("post_dispatch");
+    if verbosity >= EventVerbosity::Standard {
+        event = event
             .add_attribute("domain", decoded_msg.dest_domain.to_string())
-            .add_attribute("route", routed_hook)
-            .add_attribute("message_id", decoded_msg.id().to_hex()),
-    ))
+            .add_attribute("route", routed_hook);
+    }
+    event = event.add_attribute("message_id", decoded_msg.id().to_hex());
+    if verbosity >= EventVerbosity::Verbose {
+        event = event
+            .add_attribute("origin", decoded_msg.origin_domain.to_string())
+            .add_attribute("sender", decoded_msg.sender.to_hex())
+            .add_attribute("recipient", decoded_msg.recipient.to_hex())
+            .add_attribute("nonce", decoded_msg.nonce.to_string());
+    }
+
+    Ok(Response::new().add_message(hook_msg).add_event(event))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -320,4 +362,74 @@ mod test {
             expected_fee
         );
     }
+
+    #[rstest]
+    #[case(EventVerbosity::Minimal, vec!["message_id"])]
+    #[case(EventVerbosity::Standard, vec!["domain", "route", "message_id"])]
+    #[case(
+        EventVerbosity::Verbose,
+        vec!["domain", "route", "message_id", "origin", "sender", "recipient", "nonce"]
+    )]
+    fn test_post_dispatch_event_verbosity(
+        deps_routes: (TestDeps, Routes),
+        #[case] verbosity: EventVerbosity,
+        #[case] keys: Vec<&str>,
+    ) {
+        let (mut deps, _) = deps_routes;
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(OWNER, &[]),
+            ExecuteMsg::SetEventVerbosity { verbosity },
+        )
+        .unwrap();
+
+        let mut rand_msg: Message = gen_bz(100).into();
+        rand_msg.dest_domain = ROUTE1.0;
+
+        let res = post_dispatch(
+            deps.as_mut(),
+            mock_info("someone", &[]),
+            PostDispatchMsg {
+                metadata: HexBinary::default(),
+                message: rand_msg.clone().into(),
+            },
+        )
+        .unwrap();
+
+        let event = res
+            .events
+            .iter()
+            .find(|v| v.ty == new_event("post_dispatch").ty)
+            .unwrap();
+
+        assert_eq!(
+            event
+                .attributes
+                .iter()
+                .map(|v| v.key.as_str())
+                .collect::<Vec<_>>(),
+            keys
+        );
+        assert!(event
+            .attributes
+            .iter()
+            .any(|v| v.key == "message_id" && v.value == rand_msg.id().to_hex()));
+    }
+
+    #[rstest]
+    #[should_panic(expected = "unauthorized")]
+    fn test_set_event_verbosity_unauthorized(mut deps: TestDeps) {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("someone", &[]),
+            ExecuteMsg::SetEventVerbosity {
+                verbosity: EventVerbosity::Minimal,
+            },
+        )
+        .map_err(|e| e.to_string())
+        .unwrap();
+    }
 }
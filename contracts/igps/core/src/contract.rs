@@ -80,6 +80,11 @@ pub fn execute(
         ExecuteMsg::SetBeneficiary { beneficiary } => {
             execute::set_beneficiary(deps, info, beneficiary)
         }
+        ExecuteMsg::SetMinRefund { min_refund } => execute::set_min_refund(deps, info, min_refund),
+        ExecuteMsg::SetMinGasPrice {
+            domain,
+            min_gas_price,
+        } => execute::set_min_gas_price(deps, info, domain, min_gas_price),
         ExecuteMsg::Claim {} => execute::claim(deps, env, info),
 
         ExecuteMsg::PayForGas {
@@ -109,6 +114,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, Contr
         QueryMsg::Hook(msg) => match msg {
             HookQueryMsg::QuoteDispatch(msg) => to_binary(quote_dispatch(deps, msg)),
             HookQueryMsg::Mailbox {} => to_binary(get_mailbox(deps)),
+            HookQueryMsg::MetadataFormat {} => to_binary(get_metadata_format()),
         },
         QueryMsg::Oracle(msg) => match msg {
             IgpGasOracleQueryMsg::GetExchangeRateAndGasPrice { dest_domain } => {
@@ -125,11 +131,19 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, Contr
             } => to_binary(list_gas_for_domains(deps, offset, limit, order)),
 
             IgpQueryMsg::Beneficiary {} => to_binary(get_beneficiary(deps)),
+            IgpQueryMsg::MinRefund {} => to_binary(get_min_refund(deps)),
+            IgpQueryMsg::MinGasPrice { domain } => to_binary(get_min_gas_price(deps, domain)),
 
             IgpQueryMsg::QuoteGasPayment {
                 dest_domain,
                 gas_amount,
             } => to_binary(quote_gas_payment(deps, dest_domain, gas_amount)),
+
+            IgpQueryMsg::QuoteRefund {
+                dest_domain,
+                gas_limit,
+                paid,
+            } => to_binary(quote_refund(deps, dest_domain, gas_limit, paid)),
         },
     }
 }
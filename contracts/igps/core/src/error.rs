@@ -14,6 +14,9 @@ pub enum ContractError {
     #[error("{0}")]
     MigrationError(#[from] hpl_utils::MigrationError),
 
+    #[error("{0}")]
+    RouterError(#[from] hpl_router::RouterError),
+
     #[error("unauthorized")]
     Unauthorized {},
 
@@ -28,6 +31,9 @@ pub enum ContractError {
 
     #[error("gas oracle not found for {0}")]
     GasOracleNotFound(u32),
+
+    #[error("math overflow")]
+    MathOverflow {},
 }
 
 impl ContractError {
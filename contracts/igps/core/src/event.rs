@@ -38,6 +38,22 @@ pub fn emit_set_beneficiary(owner: Addr, beneficiary: String) -> Event {
         .add_attribute("beneficiary", beneficiary)
 }
 
+pub fn emit_set_min_refund(owner: Addr, min_refund: Uint128) -> Event {
+    Event::new("igp-core-set-min-refund")
+        .add_attribute("owner", owner)
+        .add_attribute("min-refund", min_refund)
+}
+
+pub fn emit_set_min_gas_price(owner: Addr, domain: u32, min_gas_price: Option<Uint128>) -> Event {
+    Event::new("igp-core-set-min-gas-price")
+        .add_attribute("owner", owner)
+        .add_attribute("domain", domain.to_string())
+        .add_attribute(
+            "min-gas-price",
+            min_gas_price.map(|v| v.to_string()).unwrap_or_default(),
+        )
+}
+
 pub fn emit_claim(beneficiary: Addr, balance: Coin) -> Event {
     Event::new("igp-core-claim")
         .add_attribute("beneficiary", beneficiary)
@@ -57,12 +73,14 @@ pub fn emit_post_dispatch(metadata: HexBinary, message: HexBinary) -> Event {
         .add_attribute("message", message.to_string())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn emit_pay_for_gas(
     sender: Addr,
     dest_domain: u32,
     message_id: HexBinary,
     gas_amount: Uint256,
     gas_refunded: Uint128,
+    gas_retained: Uint128,
     gas_required: Uint256,
     payment: Uint256,
 ) -> Event {
@@ -72,6 +90,7 @@ pub fn emit_pay_for_gas(
         .add_attribute("message_id", message_id.to_hex())
         .add_attribute("gas_amount", gas_amount)
         .add_attribute("gas_refunded", gas_refunded)
+        .add_attribute("gas_retained", gas_retained)
         .add_attribute("gas_required", gas_required)
         .add_attribute("payment", payment)
 }
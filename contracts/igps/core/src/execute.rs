@@ -1,10 +1,12 @@
 use crate::event::{
     emit_claim, emit_pay_for_gas, emit_post_dispatch, emit_set_beneficiary, emit_set_default_gas,
-    emit_set_gas_for_domain, emit_unset_gas_for_domain,
+    emit_set_gas_for_domain, emit_set_min_gas_price, emit_set_min_refund,
+    emit_unset_gas_for_domain,
 };
 use crate::query::quote_gas_price;
 use crate::{
-    get_default_gas, ContractError, BENEFICIARY, DEFAULT_GAS_USAGE, GAS_FOR_DOMAIN, GAS_TOKEN, HRP,
+    get_default_gas, get_min_refund, ContractError, BENEFICIARY, DEFAULT_GAS_USAGE, GAS_FOR_DOMAIN,
+    GAS_TOKEN, HRP, MIN_GAS_PRICE, MIN_REFUND,
 };
 
 use cosmwasm_std::{
@@ -87,6 +89,42 @@ pub fn set_beneficiary(
     Ok(Response::new().add_event(emit_set_beneficiary(info.sender, beneficiary)))
 }
 
+pub fn set_min_refund(
+    deps: DepsMut,
+    info: MessageInfo,
+    min_refund: Uint128,
+) -> Result<Response, ContractError> {
+    ensure_eq!(
+        info.sender,
+        get_owner(deps.storage)?,
+        ContractError::Unauthorized {}
+    );
+
+    MIN_REFUND.save(deps.storage, &min_refund)?;
+
+    Ok(Response::new().add_event(emit_set_min_refund(info.sender, min_refund)))
+}
+
+pub fn set_min_gas_price(
+    deps: DepsMut,
+    info: MessageInfo,
+    domain: u32,
+    min_gas_price: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    ensure_eq!(
+        info.sender,
+        get_owner(deps.storage)?,
+        ContractError::Unauthorized {}
+    );
+
+    match min_gas_price {
+        Some(v) => MIN_GAS_PRICE.save(deps.storage, domain, &v)?,
+        None => MIN_GAS_PRICE.remove(deps.storage, domain),
+    }
+
+    Ok(Response::new().add_event(emit_set_min_gas_price(info.sender, domain, min_gas_price)))
+}
+
 pub fn claim(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
     let beneficiary = BENEFICIARY.load(deps.storage)?;
     ensure_eq!(info.sender, beneficiary, ContractError::Unauthorized {});
@@ -161,12 +199,19 @@ pub fn pay_for_gas(
 
     let payment_gap = Uint128::from_str(&(received - gas_needed).to_string())?;
 
+    // dust is not worth the refund. it stays in the contract to be claimed by the beneficiary
+    let (refund, retained) = if payment_gap < get_min_refund(deps.storage)? {
+        (Uint128::zero(), payment_gap)
+    } else {
+        (payment_gap, Uint128::zero())
+    };
+
     let mut resp = Response::new();
 
-    if !payment_gap.is_zero() {
+    if !refund.is_zero() {
         let refund_msg = BankMsg::Send {
             to_address: refund_address,
-            amount: coins(payment_gap.u128(), &gas_token),
+            amount: coins(refund.u128(), &gas_token),
         };
         resp = resp.add_message(refund_msg);
     }
@@ -176,7 +221,8 @@ pub fn pay_for_gas(
         dest_domain,
         message_id,
         gas_amount,
-        payment_gap,
+        refund,
+        retained,
         gas_needed,
         received,
     )))
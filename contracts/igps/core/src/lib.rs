@@ -7,7 +7,7 @@ pub mod query;
 #[cfg(test)]
 pub mod tests;
 
-use cosmwasm_std::{Addr, StdResult, Storage};
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
 use cw_storage_plus::{Item, Map};
 pub use error::ContractError;
 
@@ -16,6 +16,9 @@ pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 // constants
+
+/// Oracle exchange rates are fixed point numbers scaled by 1e10. A rate of 1e10 means one unit
+/// of the remote gas token is worth one unit of the local gas token.
 pub const TOKEN_EXCHANGE_RATE_SCALE: u128 = 10_000_000_000;
 
 pub const HRP_KEY: &str = "hrp";
@@ -33,9 +36,19 @@ pub const GAS_FOR_DOMAIN: Map<u32, u128> = Map::new(GAS_FOR_DOMAIN_PREFIX);
 pub const BENEFICIARY_KEY: &str = "beneficiary";
 pub const BENEFICIARY: Item<Addr> = Item::new(BENEFICIARY_KEY);
 
+pub const MIN_REFUND_KEY: &str = "min_refund";
+pub const MIN_REFUND: Item<Uint128> = Item::new(MIN_REFUND_KEY);
+
+pub const MIN_GAS_PRICE_PREFIX: &str = "min_gas_price";
+pub const MIN_GAS_PRICE: Map<u32, Uint128> = Map::new(MIN_GAS_PRICE_PREFIX);
+
 pub fn get_default_gas(storage: &dyn Storage, domain: u32) -> StdResult<u128> {
     let custom_gas = GAS_FOR_DOMAIN.may_load(storage, domain)?;
     let default_gas = DEFAULT_GAS_USAGE.load(storage)?;
 
     Ok(custom_gas.unwrap_or(default_gas))
 }
+
+pub fn get_min_refund(storage: &dyn Storage) -> StdResult<Uint128> {
+    Ok(MIN_REFUND.may_load(storage)?.unwrap_or_default())
+}
@@ -1,10 +1,19 @@
 use crate::error::ContractError;
-use crate::{BENEFICIARY, DEFAULT_GAS_USAGE, GAS_FOR_DOMAIN, GAS_TOKEN, TOKEN_EXCHANGE_RATE_SCALE};
+use crate::{
+    BENEFICIARY, DEFAULT_GAS_USAGE, GAS_FOR_DOMAIN, GAS_TOKEN, MIN_GAS_PRICE,
+    TOKEN_EXCHANGE_RATE_SCALE,
+};
 
-use cosmwasm_std::{coins, Addr, Deps, QuerierWrapper, StdResult, Storage, Uint256};
-use hpl_interface::hook::{MailboxResponse, QuoteDispatchMsg, QuoteDispatchResponse};
+use cosmwasm_std::{
+    coins, ensure, Addr, Coin, Deps, MessageInfo, QuerierWrapper, StdResult, Storage, Uint256,
+};
+use hpl_interface::hook::{
+    MailboxResponse, MetadataFormat, MetadataFormatResponse, QuoteDispatchMsg,
+    QuoteDispatchResponse,
+};
 use hpl_interface::igp::core::{
-    BeneficiaryResponse, DefaultGasResponse, GasForDomainResponse, QuoteGasPaymentResponse,
+    BeneficiaryResponse, DefaultGasResponse, GasForDomainResponse, MinGasPriceResponse,
+    MinRefundResponse, QuoteGasPaymentResponse, QuoteRefundResponse,
 };
 use hpl_interface::igp::oracle::{self, GetExchangeRateAndGasPriceResponse, IgpGasOracleQueryMsg};
 use hpl_interface::types::{IGPMetadata, Message};
@@ -16,6 +25,14 @@ pub fn get_mailbox(_deps: Deps) -> Result<MailboxResponse, ContractError> {
     })
 }
 
+pub fn get_metadata_format() -> Result<MetadataFormatResponse, ContractError> {
+    Ok(MetadataFormatResponse {
+        format: MetadataFormat::Igp,
+        // empty, gas limit, gas limit + 20 or 32 byte refund address
+        lengths: vec![0, 32, 52, 64],
+    })
+}
+
 pub fn get_default_gas(deps: Deps) -> Result<DefaultGasResponse, ContractError> {
     let default_gas = DEFAULT_GAS_USAGE.load(deps.storage)?;
 
@@ -58,6 +75,18 @@ pub fn get_beneficiary(deps: Deps) -> Result<BeneficiaryResponse, ContractError>
     })
 }
 
+pub fn get_min_refund(deps: Deps) -> Result<MinRefundResponse, ContractError> {
+    Ok(MinRefundResponse {
+        min_refund: crate::get_min_refund(deps.storage)?,
+    })
+}
+
+pub fn get_min_gas_price(deps: Deps, domain: u32) -> Result<MinGasPriceResponse, ContractError> {
+    Ok(MinGasPriceResponse {
+        min_gas_price: MIN_GAS_PRICE.may_load(deps.storage, domain)?,
+    })
+}
+
 pub fn quote_gas_price(
     storage: &dyn Storage,
     querier: &QuerierWrapper,
@@ -74,9 +103,19 @@ pub fn quote_gas_price(
         &oracle::QueryMsg::Oracle(IgpGasOracleQueryMsg::GetExchangeRateAndGasPrice { dest_domain }),
     )?;
 
-    let dest_gas_cost = fees * Uint256::from(gas_price_resp.gas_price);
-    let gas_needed = (dest_gas_cost * Uint256::from(gas_price_resp.exchange_rate))
-        / Uint256::from(TOKEN_EXCHANGE_RATE_SCALE);
+    // stale oracle data must not underprice the message
+    let gas_price = match MIN_GAS_PRICE.may_load(storage, dest_domain)? {
+        Some(floor) => gas_price_resp.gas_price.max(floor),
+        None => gas_price_resp.gas_price,
+    };
+
+    // scale down last so that the result is only floored once
+    let gas_needed = fees
+        .checked_mul(gas_price.into())
+        .and_then(|v| v.checked_mul(gas_price_resp.exchange_rate.into()))
+        .map_err(|_| ContractError::MathOverflow {})?
+        .checked_div(TOKEN_EXCHANGE_RATE_SCALE.into())
+        .map_err(|_| ContractError::MathOverflow {})?;
 
     Ok(gas_needed)
 }
@@ -91,6 +130,39 @@ pub fn quote_gas_payment(
     Ok(QuoteGasPaymentResponse { gas_needed })
 }
 
+pub fn quote_refund(
+    deps: Deps,
+    dest_domain: u32,
+    gas_limit: Uint256,
+    paid: Vec<Coin>,
+) -> Result<QuoteRefundResponse, ContractError> {
+    let gas_token = GAS_TOKEN.load(deps.storage)?;
+    // validated the same way as pay_for_gas, so the quote fails whenever the payment would
+    let info = MessageInfo {
+        sender: Addr::unchecked(""),
+        funds: paid,
+    };
+    let received = Uint256::from(cw_utils::must_pay(&info, &gas_token)?);
+    let gas_needed = quote_gas_price(deps.storage, &deps.querier, dest_domain, gas_limit)?;
+    ensure!(
+        received >= gas_needed,
+        ContractError::InsufficientFunds {
+            received,
+            gas_needed,
+        }
+    );
+
+    let refund = (received - gas_needed).to_string().parse::<u128>()?;
+
+    Ok(QuoteRefundResponse {
+        refund: if refund > 0 && refund >= crate::get_min_refund(deps.storage)?.u128() {
+            coins(refund, gas_token)
+        } else {
+            vec![]
+        },
+    })
+}
+
 pub fn quote_dispatch(
     deps: Deps,
     req: QuoteDispatchMsg,
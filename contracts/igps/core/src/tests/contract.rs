@@ -5,6 +5,7 @@ use cosmwasm_std::{
     StdResult, SubMsg, SystemResult, Uint128, Uint256, WasmQuery,
 };
 use hpl_interface::{
+    hook::MetadataFormat,
     igp::{
         core::{
             DefaultGasResponse, ExecuteMsg, GasForDomainResponse, GasOracleConfig, IgpQueryMsg,
@@ -19,7 +20,10 @@ use hpl_router::get_routes;
 use ibcx_test_utils::{addr, gen_bz};
 use rstest::{fixture, rstest};
 
-use crate::{get_default_gas, BENEFICIARY, DEFAULT_GAS_USAGE, GAS_TOKEN, HRP};
+use crate::{
+    get_default_gas, get_min_refund, query::quote_gas_price, ContractError, BENEFICIARY,
+    DEFAULT_GAS_USAGE, GAS_TOKEN, HRP, MIN_GAS_PRICE,
+};
 
 use super::IGP;
 
@@ -52,6 +56,24 @@ fn test_mock_querier(v: &WasmQuery) -> QuerierResult {
             }
             _ => unreachable!("unsupported query"),
         },
+        // unscaled, to hit the precision of the exchange rate scale
+        "oracle_raw" => match msg {
+            oracle::QueryMsg::Oracle(
+                oracle::IgpGasOracleQueryMsg::GetExchangeRateAndGasPrice { .. },
+            ) => {
+                let gas_price = split.pop().unwrap().parse::<u128>().unwrap();
+                let exchange_rate = split.pop().unwrap().parse::<u128>().unwrap();
+
+                let res = to_json_binary(&oracle::GetExchangeRateAndGasPriceResponse {
+                    gas_price: Uint128::new(gas_price),
+                    exchange_rate: Uint128::new(exchange_rate),
+                })
+                .unwrap();
+
+                SystemResult::Ok(ContractResult::Ok(res))
+            }
+            _ => unreachable!("unsupported query"),
+        },
         _ => unreachable!("unsupported query"),
     }
 }
@@ -149,6 +171,14 @@ fn test_get_beneficiary(igp: IGP) {
     assert_eq!(addr("beneficiary"), actual_beneficiary);
 }
 
+#[rstest]
+fn test_get_metadata_format(igp: IGP) {
+    let res = igp.get_metadata_format().unwrap();
+
+    assert_eq!(res.format, MetadataFormat::Igp);
+    assert_eq!(res.lengths, vec![0, 32, 52, 64]);
+}
+
 #[rstest]
 #[case(1, 300_000)]
 #[should_panic(expected = "gas oracle not found for 2")]
@@ -169,6 +199,68 @@ fn test_get_quote_gas_payment(
     assert_eq!(resp.gas_needed, Uint256::from_u128(9 * 10u128.pow(15)))
 }
 
+#[rstest]
+#[case(1, Uint256::from_u128(300_000), Uint256::from_u128(9 * DEC_15))]
+#[case(2, Uint256::from_u128(3_333_333_333), Uint256::zero())] // 9_999_999_999 / 1e10
+#[case(2, Uint256::from_u128(3_333_333_334), Uint256::one())] // 10_000_000_002 / 1e10
+#[should_panic(expected = "math overflow")]
+#[case(1, Uint256::MAX, Uint256::zero())]
+fn test_quote_gas_price_math(
+    #[with(vec![(1, "oracle/2/150".into()), (2, "oracle_raw/3/1".into())])] igp_routes: (
+        IGP,
+        Vec<(u32, String)>,
+    ),
+    #[case] dest_domain: u32,
+    #[case] gas_amount: Uint256,
+    #[case] expected: Uint256,
+) {
+    let (mut igp, _) = igp_routes;
+
+    igp.deps.querier.update_wasm(test_mock_querier);
+
+    let deps = igp.deps_ref();
+    let gas_needed = quote_gas_price(deps.storage, &deps.querier, dest_domain, gas_amount)
+        .map_err(|e| e.to_string())
+        .unwrap();
+    assert_eq!(gas_needed, expected);
+}
+
+#[rstest]
+#[case(100, 9 * DEC_15)] // oracle price above the floor
+#[case(300, 18 * DEC_15)] // clamped up to the floor
+fn test_quote_min_gas_price(
+    #[with(vec![(1, "oracle/2/150".into())])] igp_routes: (IGP, Vec<(u32, String)>),
+    #[case] min_gas_price: u128,
+    #[case] expected: u128,
+) {
+    let (mut igp, _) = igp_routes;
+
+    igp.deps.querier.update_wasm(test_mock_querier);
+
+    igp.set_min_gas_price(&addr("owner"), 1, Some(min_gas_price * DEC_9))
+        .unwrap();
+
+    let resp = igp.get_quote_gas_payment(1, 300_000).unwrap();
+    assert_eq!(resp.gas_needed, Uint256::from_u128(expected));
+}
+
+#[rstest]
+fn test_set_min_gas_price(mut igp: IGP) {
+    let err = igp
+        .set_min_gas_price(&addr("someone"), 1, Some(1))
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    igp.set_min_gas_price(&addr("owner"), 1, Some(1)).unwrap();
+    assert_eq!(
+        MIN_GAS_PRICE.load(igp.deps.as_ref().storage, 1).unwrap(),
+        Uint128::one()
+    );
+
+    igp.set_min_gas_price(&addr("owner"), 1, None).unwrap();
+    assert!(!MIN_GAS_PRICE.has(igp.deps.as_ref().storage, 1));
+}
+
 #[rstest]
 #[case(1)]
 #[should_panic(expected = "gas oracle not found for 2")]
@@ -242,6 +334,105 @@ fn test_pay_for_gas(
     }
 }
 
+#[rstest]
+#[case(10 * DEC_15, 0)] // below threshold, retained
+#[case(11 * DEC_15, 2 * DEC_15)] // at threshold, refunded
+#[case(12 * DEC_15, 3 * DEC_15)] // above threshold, refunded
+fn test_pay_for_gas_min_refund(
+    #[with(vec![(1, "oracle/2/150".into())])] igp_routes: (IGP, Vec<(u32, String)>),
+    #[case] gas_paid: u128,
+    #[case] refunded: u128,
+) {
+    let (mut igp, _) = igp_routes;
+
+    igp.deps.querier.update_wasm(test_mock_querier);
+
+    let owner = get_owner(igp.deps.as_ref().storage).unwrap();
+    igp.set_min_refund(&owner, 2 * DEC_15).unwrap();
+
+    let user_refund = addr("user-refund");
+
+    let res = igp
+        .pay_for_gas(
+            &addr("user-payer"),
+            &[coin(gas_paid, "utest")],
+            &gen_bz(8),
+            1,
+            300_000,
+            &user_refund,
+        )
+        .unwrap();
+
+    if refunded > 0 {
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: user_refund.to_string(),
+                amount: vec![coin(refunded, "utest")]
+            })]
+        );
+    } else {
+        assert_eq!(res.messages, vec![]);
+    }
+
+    let retained = gas_paid - 9 * DEC_15 - refunded;
+    let event = res.events.last().unwrap();
+    assert!(event
+        .attributes
+        .iter()
+        .any(|v| v.key == "gas_retained" && v.value == retained.to_string()));
+
+    let expected = if refunded > 0 {
+        vec![coin(refunded, "utest")]
+    } else {
+        vec![]
+    };
+    let quoted = igp
+        .get_quote_refund(1, 300_000, vec![coin(gas_paid, "utest")])
+        .unwrap();
+    assert_eq!(quoted.refund, expected);
+}
+
+#[rstest]
+fn test_set_min_refund(mut igp: IGP) {
+    let owner = get_owner(igp.deps.as_ref().storage).unwrap();
+
+    let err = igp.set_min_refund(&addr("someone"), 1).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    igp.set_min_refund(&owner, 1).unwrap();
+    assert_eq!(
+        get_min_refund(igp.deps.as_ref().storage).unwrap(),
+        Uint128::one()
+    );
+}
+
+#[rstest]
+#[case(vec![coin(10 * DEC_15, "utest")], vec![coin(DEC_15, "utest")])] // over
+#[case(vec![coin(9 * DEC_15, "utest")], vec![])] // exact
+#[should_panic(expected = "insufficient funds")]
+#[case(vec![coin(8 * DEC_15, "utest")], vec![])] // under
+#[should_panic(expected = "Must send reserve token 'utest'")]
+#[case(vec![coin(10 * DEC_15, "test1")], vec![])] // other denom
+#[should_panic(expected = "Sent more than one denomination")]
+#[case(vec![coin(10 * DEC_15, "utest"), coin(1, "test1")], vec![])] // extra denom
+fn test_quote_refund(
+    #[with(vec![(1, "oracle/2/150".into())])] igp_routes: (IGP, Vec<(u32, String)>),
+    #[case] paid: Vec<Coin>,
+    #[case] expected: Vec<Coin>,
+) {
+    let (mut igp, _) = igp_routes;
+
+    igp.deps.querier.update_wasm(test_mock_querier);
+
+    let res = igp
+        .get_quote_refund(1, 300_000, paid)
+        .map_err(|e| e.to_string())
+        .unwrap();
+
+    assert_eq!(res.refund, expected);
+}
+
 #[rstest]
 #[case(addr("mailbox"), true, Some(300_000))]
 #[case(addr("mailbox"), true, None)]
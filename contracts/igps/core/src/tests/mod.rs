@@ -1,14 +1,14 @@
 use cosmwasm_std::{
     from_json,
     testing::{mock_info, MockApi, MockQuerier, MockStorage},
-    Addr, Coin, Deps, DepsMut, Empty, Env, HexBinary, MessageInfo, OwnedDeps, Response,
+    Addr, Coin, Deps, DepsMut, Empty, Env, HexBinary, MessageInfo, OwnedDeps, Response, Uint128,
 };
 use hpl_interface::{
-    hook::PostDispatchMsg,
+    hook::{HookQueryMsg, MetadataFormatResponse, PostDispatchMsg},
     igp::{
         core::{
             ExecuteMsg, GasOracleConfig, IgpQueryMsg, InstantiateMsg, QueryMsg,
-            QuoteGasPaymentResponse,
+            QuoteGasPaymentResponse, QuoteRefundResponse,
         },
         oracle::{GetExchangeRateAndGasPriceResponse, IgpGasOracleQueryMsg},
     },
@@ -69,11 +69,11 @@ impl IGP {
             .map_err(|e| e.into())
     }
 
-    pub fn deps_mut(&mut self) -> DepsMut {
+    pub fn deps_mut(&mut self) -> DepsMut<'_> {
         self.deps.as_mut()
     }
 
-    pub fn deps_ref(&self) -> Deps {
+    pub fn deps_ref(&self) -> Deps<'_> {
         self.deps.as_ref()
     }
 
@@ -109,6 +109,34 @@ impl IGP {
         )
     }
 
+    pub fn set_min_refund(
+        &mut self,
+        sender: &Addr,
+        min_refund: u128,
+    ) -> Result<Response, ContractError> {
+        self.execute(
+            mock_info(sender.as_str(), &[]),
+            ExecuteMsg::SetMinRefund {
+                min_refund: Uint128::new(min_refund),
+            },
+        )
+    }
+
+    pub fn set_min_gas_price(
+        &mut self,
+        sender: &Addr,
+        domain: u32,
+        min_gas_price: Option<u128>,
+    ) -> Result<Response, ContractError> {
+        self.execute(
+            mock_info(sender.as_str(), &[]),
+            ExecuteMsg::SetMinGasPrice {
+                domain,
+                min_gas_price: min_gas_price.map(Uint128::new),
+            },
+        )
+    }
+
     pub fn claim(&mut self, sender: &Addr) -> Result<Response, ContractError> {
         self.execute(mock_info(sender.as_str(), &[]), ExecuteMsg::Claim {})
     }
@@ -160,6 +188,26 @@ impl IGP {
         )
     }
 
+    pub fn get_quote_refund(
+        &self,
+        dest_domain: u32,
+        gas_limit: u128,
+        paid: Vec<Coin>,
+    ) -> Result<QuoteRefundResponse, ContractError> {
+        self.query(
+            IgpQueryMsg::QuoteRefund {
+                dest_domain,
+                gas_limit: gas_limit.into(),
+                paid,
+            }
+            .wrap(),
+        )
+    }
+
+    pub fn get_metadata_format(&self) -> Result<MetadataFormatResponse, ContractError> {
+        self.query(QueryMsg::Hook(HookQueryMsg::MetadataFormat {}))
+    }
+
     pub fn get_exchange_rate_and_gas_price(
         &self,
         dest_domain: u32,
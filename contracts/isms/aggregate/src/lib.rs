@@ -11,7 +11,10 @@ use cw2::set_contract_version;
 use cw_storage_plus::Item;
 use hpl_interface::{
     ism::{
-        aggregate::{AggregateIsmQueryMsg, ExecuteMsg, InstantiateMsg, IsmsResponse, QueryMsg},
+        aggregate::{
+            AggregateIsmQueryMsg, ConfigResponse, ExecuteMsg, InstantiateMsg, IsmsResponse,
+            ProgressResponse, QueryMsg,
+        },
         IsmQueryMsg, IsmType, ModuleTypeResponse, VerifyInfoResponse, VerifyResponse,
     },
     to_binary,
@@ -129,10 +132,26 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, Contr
                     .collect(),
                 threshold: THRESHOLD.load(deps.storage)?,
             })?),
+            AggregateIsmQueryMsg::Config {} => to_binary(get_config(deps)),
+            AggregateIsmQueryMsg::Progress { metadata, message } => {
+                to_binary(progress(deps, metadata, message))
+            }
         },
     }
 }
 
+fn get_config(deps: Deps) -> Result<ConfigResponse, ContractError> {
+    Ok(ConfigResponse {
+        owner: get_owner(deps.storage)?.into(),
+        isms: ISMS
+            .load(deps.storage)?
+            .into_iter()
+            .map(|v| v.into())
+            .collect(),
+        threshold: THRESHOLD.load(deps.storage)?,
+    })
+}
+
 fn verify(
     deps: Deps,
     metadata: HexBinary,
@@ -159,6 +178,30 @@ fn verify(
     })
 }
 
+fn progress(
+    deps: Deps,
+    metadata: HexBinary,
+    message: HexBinary,
+) -> Result<ProgressResponse, ContractError> {
+    let isms = ISMS.load(deps.storage)?;
+    let threshold = THRESHOLD.load(deps.storage)?;
+
+    let mut passed = 0u8;
+
+    for (ism, meta) in AggregateMetadata::from_hex(metadata, isms) {
+        // a sub-ism rejecting its metadata with an error just hasn't passed yet
+        if let Ok(true) = hpl_interface::ism::verify(&deps.querier, ism, meta, message.clone()) {
+            passed += 1;
+        }
+    }
+
+    Ok(ProgressResponse {
+        passed,
+        threshold,
+        needed: threshold.saturating_sub(passed),
+    })
+}
+
 fn verify_info(deps: Deps, _message: HexBinary) -> Result<VerifyInfoResponse, ContractError> {
     Ok(VerifyInfoResponse {
         threshold: THRESHOLD.load(deps.storage)?,
@@ -175,3 +218,136 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, Contra
     hpl_utils::migrate(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     Ok(Response::default())
 }
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{
+        from_json,
+        testing::{mock_dependencies, mock_env, mock_info},
+        ContractResult, SystemResult, WasmQuery,
+    };
+    use hpl_interface::ism::ExpectedIsmQueryMsg;
+
+    use super::*;
+
+    #[test]
+    fn test_config() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("deployer", &[]),
+            InstantiateMsg {
+                owner: "owner".to_string(),
+                isms: vec!["ism_a".to_string()],
+                threshold: 1,
+            },
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::SetIsms {
+                isms: vec![
+                    "ism_b".to_string(),
+                    "ism_c".to_string(),
+                    "ism_d".to_string(),
+                ],
+                threshold: 2,
+            },
+        )
+        .unwrap();
+
+        let res: ConfigResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::AggregateIsm(AggregateIsmQueryMsg::Config {}),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            res,
+            ConfigResponse {
+                owner: "owner".to_string(),
+                isms: vec![
+                    "ism_b".to_string(),
+                    "ism_c".to_string(),
+                    "ism_d".to_string(),
+                ],
+                threshold: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_progress() {
+        let mut deps = mock_dependencies();
+
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, msg } => {
+                let ExpectedIsmQueryMsg::Ism(IsmQueryMsg::Verify { .. }) = from_json(msg).unwrap()
+                else {
+                    unreachable!("unexpected query")
+                };
+
+                if contract_addr == "ism_error" {
+                    return SystemResult::Ok(ContractResult::Err("invalid metadata".into()));
+                }
+
+                let res = VerifyResponse {
+                    verified: contract_addr == "ism_pass",
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&res).unwrap()))
+            }
+            _ => unreachable!("unexpected query"),
+        });
+
+        let isms = ["ism_pass", "ism_fail", "ism_error"];
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("deployer", &[]),
+            InstantiateMsg {
+                owner: "owner".to_string(),
+                isms: isms.iter().map(|v| v.to_string()).collect(),
+                threshold: 2,
+            },
+        )
+        .unwrap();
+
+        let metadata = AggregateMetadata::new(
+            isms.iter()
+                .map(|v| (Addr::unchecked(*v), HexBinary::from(b"meta".to_vec())))
+                .collect(),
+        );
+
+        let res: ProgressResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::AggregateIsm(AggregateIsmQueryMsg::Progress {
+                    metadata: metadata.into(),
+                    message: HexBinary::default(),
+                }),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            res,
+            ProgressResponse {
+                passed: 1,
+                threshold: 2,
+                needed: 1,
+            }
+        );
+    }
+}
@@ -1,15 +1,17 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure, ensure_eq, Deps, DepsMut, Empty, Env, Event, MessageInfo, QueryResponse, Response,
+    ensure, ensure_eq, Deps, DepsMut, Empty, Env, Event, HexBinary, MessageInfo, QueryResponse,
+    Response,
 };
 use cw2::set_contract_version;
 use hpl_interface::{
     ism::{
         multisig::{
             EnrolledValidatorsResponse, ExecuteMsg, InstantiateMsg, MultisigIsmQueryMsg, QueryMsg,
+            ScheduledValidatorsResponse,
         },
-        IsmQueryMsg,
+        IsmQueryMsg, IsmType,
     },
     to_binary,
 };
@@ -17,7 +19,10 @@ use hpl_ownable::get_owner;
 
 use crate::{
     error::ContractError,
-    state::{THRESHOLD, VALIDATORS},
+    state::{
+        load_active_set, promote_scheduled, ScheduledValidators, MODULE_TYPE, SCHEDULED_VALIDATORS,
+        THRESHOLD, VALIDATORS,
+    },
     CONTRACT_NAME, CONTRACT_VERSION,
 };
 
@@ -32,8 +37,18 @@ pub fn instantiate(
 
     let owner = deps.api.addr_validate(&msg.owner)?;
 
+    ensure!(
+        matches!(
+            msg.module_type,
+            IsmType::MessageIdMultisig | IsmType::MerkleRootMultisig
+        ),
+        ContractError::invalid_args("module type should be a multisig type")
+    );
+
     hpl_ownable::initialize(deps.storage, &owner)?;
 
+    MODULE_TYPE.save(deps.storage, &msg.module_type)?;
+
     Ok(Response::new().add_attribute("method", "instantiate"))
 }
 
@@ -59,17 +74,10 @@ pub fn execute(
                 get_owner(deps.storage)?,
                 ContractError::Unauthorized {}
             );
-            ensure!(
-                validators.iter().all(|v| v.len() == 20),
-                ContractError::invalid_addr("length should be 20")
-            );
-            ensure!(
-                validators.len() >= threshold as usize && threshold > 0,
-                ContractError::invalid_args(&format!(
-                    "threshold not in range. 0 <  <= {}",
-                    validators.len(),
-                ))
-            );
+            validate_set(&validators, threshold)?;
+
+            // an effective schedule would otherwise shadow the new set
+            promote_scheduled(deps.storage, env.block.time, domain)?;
 
             VALIDATORS.save(deps.storage, domain, &validators)?;
             THRESHOLD.save(deps.storage, domain, &threshold)?;
@@ -91,6 +99,7 @@ pub fn execute(
 
             VALIDATORS.remove(deps.storage, domain);
             THRESHOLD.remove(deps.storage, domain);
+            SCHEDULED_VALIDATORS.remove(deps.storage, domain);
 
             Ok(Response::new().add_event(
                 Event::new("ism_multisig_unset_domain")
@@ -98,9 +107,84 @@ pub fn execute(
                     .add_attribute("domain", domain.to_string()),
             ))
         }
+        ScheduleValidators {
+            domain,
+            threshold,
+            validators,
+            effective_at,
+        } => {
+            ensure_eq!(
+                info.sender,
+                get_owner(deps.storage)?,
+                ContractError::Unauthorized {}
+            );
+            validate_set(&validators, threshold)?;
+            ensure!(
+                effective_at > env.block.time.seconds(),
+                ContractError::invalid_args("effective_at should be in the future")
+            );
+
+            promote_scheduled(deps.storage, env.block.time, domain)?;
+
+            SCHEDULED_VALIDATORS.save(
+                deps.storage,
+                domain,
+                &ScheduledValidators {
+                    validators: validators.clone(),
+                    threshold,
+                    effective_at,
+                },
+            )?;
+
+            Ok(Response::new().add_event(
+                Event::new("ism_multisig_schedule_validators")
+                    .add_attribute("sender", info.sender)
+                    .add_attribute("domain", domain.to_string())
+                    .add_attribute("validators", validators.len().to_string())
+                    .add_attribute("threshold", threshold.to_string())
+                    .add_attribute("effective_at", effective_at.to_string()),
+            ))
+        }
+        CancelScheduledValidators { domain } => {
+            ensure_eq!(
+                info.sender,
+                get_owner(deps.storage)?,
+                ContractError::Unauthorized {}
+            );
+
+            promote_scheduled(deps.storage, env.block.time, domain)?;
+
+            ensure!(
+                SCHEDULED_VALIDATORS.has(deps.storage, domain),
+                ContractError::invalid_args("no pending validator set")
+            );
+            SCHEDULED_VALIDATORS.remove(deps.storage, domain);
+
+            Ok(Response::new().add_event(
+                Event::new("ism_multisig_cancel_scheduled_validators")
+                    .add_attribute("sender", info.sender)
+                    .add_attribute("domain", domain.to_string()),
+            ))
+        }
     }
 }
 
+fn validate_set(validators: &[HexBinary], threshold: u8) -> Result<(), ContractError> {
+    ensure!(
+        validators.iter().all(|v| v.len() == 20),
+        ContractError::invalid_addr("length should be 20")
+    );
+    ensure!(
+        validators.len() >= threshold as usize && threshold > 0,
+        ContractError::invalid_args(&format!(
+            "threshold not in range. 0 <  <= {}",
+            validators.len(),
+        ))
+    );
+
+    Ok(())
+}
+
 /// Handling contract query
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, ContractError> {
@@ -110,25 +194,34 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, Contr
     match msg {
         QueryMsg::Ownable(msg) => Ok(hpl_ownable::handle_query(deps, env, msg)?),
         QueryMsg::Ism(msg) => match msg {
-            ModuleType {} => to_binary(query::get_module_type()),
+            ModuleType {} => to_binary(query::get_module_type(deps)),
             Verify {
                 metadata: raw_metadata,
                 message: raw_message,
-            } => to_binary(query::verify_message(deps, raw_metadata, raw_message)),
+            } => to_binary(query::verify_message(deps, env, raw_metadata, raw_message)),
             VerifyInfo {
                 message: raw_message,
-            } => to_binary(query::get_verify_info(deps, raw_message)),
+            } => to_binary(query::get_verify_info(deps, env, raw_message)),
         },
         QueryMsg::MultisigIsm(msg) => match msg {
             MultisigIsmQueryMsg::EnrolledValidators { domain } => to_binary({
-                let validators = VALIDATORS.load(deps.storage, domain)?;
-                let threshold = THRESHOLD.load(deps.storage, domain)?;
+                let (validators, threshold) =
+                    load_active_set(deps.storage, env.block.time, domain)?;
 
                 Ok::<_, ContractError>(EnrolledValidatorsResponse {
                     validators,
                     threshold,
                 })
             }),
+            MultisigIsmQueryMsg::ScheduledValidators { domain } => to_binary({
+                let scheduled = SCHEDULED_VALIDATORS.load(deps.storage, domain)?;
+
+                Ok::<_, ContractError>(ScheduledValidatorsResponse {
+                    validators: scheduled.validators,
+                    threshold: scheduled.threshold,
+                    effective_at: scheduled.effective_at,
+                })
+            }),
         },
     }
 }
@@ -141,16 +234,53 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, Contra
 
 #[cfg(test)]
 mod test {
-    use cosmwasm_std::{testing::mock_dependencies, HexBinary};
-    use hpl_interface::{build_test_executor, build_test_querier, ism::multisig::ExecuteMsg};
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info},
+        HexBinary,
+    };
+    use hpl_interface::{
+        build_test_executor, build_test_querier,
+        ism::{
+            multisig::{
+                EnrolledValidatorsResponse, ExecuteMsg, InstantiateMsg, MultisigIsmQueryMsg,
+                QueryMsg, ScheduledValidatorsResponse,
+            },
+            IsmQueryMsg, IsmType, ModuleTypeResponse,
+        },
+    };
     use ibcx_test_utils::{addr, hex};
     use rstest::rstest;
 
-    use crate::state::VALIDATORS;
+    use crate::state::{SCHEDULED_VALIDATORS, THRESHOLD, VALIDATORS};
 
     build_test_executor!(crate::contract::execute);
     build_test_querier!(crate::contract::query);
 
+    #[rstest]
+    #[case(IsmType::MessageIdMultisig)]
+    #[case(IsmType::MerkleRootMultisig)]
+    #[should_panic(expected = "module type should be a multisig type")]
+    #[case(IsmType::Routing)]
+    fn test_instantiate(#[case] module_type: IsmType) {
+        let mut deps = mock_dependencies();
+
+        super::instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("deployer", &[]),
+            InstantiateMsg {
+                owner: addr("owner").to_string(),
+                module_type: module_type.clone(),
+            },
+        )
+        .map_err(|e| e.to_string())
+        .unwrap();
+
+        let res: ModuleTypeResponse =
+            test_query(deps.as_ref(), QueryMsg::Ism(IsmQueryMsg::ModuleType {}));
+        assert_eq!(res.typ, module_type);
+    }
+
     #[rstest]
     #[case("owner", vec![hex(&"deadbeef".repeat(5))])]
     #[should_panic(expected = "unauthorized")]
@@ -206,4 +336,132 @@ mod test {
 
         assert!(!VALIDATORS.has(deps.as_ref().storage, 1));
     }
+
+    #[rstest]
+    #[case("owner", 100)]
+    #[should_panic(expected = "unauthorized")]
+    #[case("someone", 100)]
+    #[should_panic(expected = "effective_at should be in the future")]
+    #[case("owner", 0)]
+    fn test_schedule_validators(#[case] sender: &str, #[case] delay: u64) {
+        let mut deps = mock_dependencies();
+
+        hpl_ownable::initialize(deps.as_mut().storage, &addr("owner")).unwrap();
+
+        let old_set = vec![hex(&"deadbeef".repeat(5))];
+        let new_set = vec![hex(&"cafebabe".repeat(5)), hex(&"beefcafe".repeat(5))];
+        let effective_at = mock_env().block.time.seconds() + delay;
+
+        test_execute(
+            deps.as_mut(),
+            &addr("owner"),
+            ExecuteMsg::SetValidators {
+                domain: 1,
+                threshold: 1,
+                validators: old_set.clone(),
+            },
+            vec![],
+        );
+
+        test_execute(
+            deps.as_mut(),
+            &addr(sender),
+            ExecuteMsg::ScheduleValidators {
+                domain: 1,
+                threshold: 2,
+                validators: new_set.clone(),
+                effective_at,
+            },
+            vec![],
+        );
+
+        let res: ScheduledValidatorsResponse = test_query(
+            deps.as_ref(),
+            QueryMsg::MultisigIsm(MultisigIsmQueryMsg::ScheduledValidators { domain: 1 }),
+        );
+        assert_eq!(
+            res,
+            ScheduledValidatorsResponse {
+                validators: new_set,
+                threshold: 2,
+                effective_at,
+            }
+        );
+
+        // still pending, so the enrolled set is unchanged
+        let res: EnrolledValidatorsResponse = test_query(
+            deps.as_ref(),
+            QueryMsg::MultisigIsm(MultisigIsmQueryMsg::EnrolledValidators { domain: 1 }),
+        );
+        assert_eq!(res.validators, old_set);
+        assert_eq!(res.threshold, 1);
+    }
+
+    #[rstest]
+    #[case("owner")]
+    #[should_panic(expected = "unauthorized")]
+    #[case("someone")]
+    fn test_cancel_scheduled_validators(#[case] sender: &str) {
+        let mut deps = mock_dependencies();
+
+        hpl_ownable::initialize(deps.as_mut().storage, &addr("owner")).unwrap();
+
+        test_execute(
+            deps.as_mut(),
+            &addr("owner"),
+            ExecuteMsg::ScheduleValidators {
+                domain: 1,
+                threshold: 1,
+                validators: vec![hex(&"deadbeef".repeat(5))],
+                effective_at: mock_env().block.time.seconds() + 100,
+            },
+            vec![],
+        );
+
+        test_execute(
+            deps.as_mut(),
+            &addr(sender),
+            ExecuteMsg::CancelScheduledValidators { domain: 1 },
+            vec![],
+        );
+
+        assert!(!SCHEDULED_VALIDATORS.has(deps.as_ref().storage, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "no pending validator set")]
+    fn test_cancel_effective_schedule() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        hpl_ownable::initialize(deps.as_mut().storage, &addr("owner")).unwrap();
+
+        let new_set = vec![hex(&"cafebabe".repeat(5))];
+
+        test_execute(
+            deps.as_mut(),
+            &addr("owner"),
+            ExecuteMsg::ScheduleValidators {
+                domain: 1,
+                threshold: 1,
+                validators: new_set.clone(),
+                effective_at: env.block.time.seconds() + 100,
+            },
+            vec![],
+        );
+
+        // once effective, the schedule is promoted and can no longer be cancelled
+        env.block.time = env.block.time.plus_seconds(100);
+        let res = super::execute(
+            deps.as_mut(),
+            env,
+            cosmwasm_std::testing::mock_info("owner", &[]),
+            ExecuteMsg::CancelScheduledValidators { domain: 1 },
+        );
+
+        assert_eq!(VALIDATORS.load(deps.as_ref().storage, 1).unwrap(), new_set);
+        assert_eq!(THRESHOLD.load(deps.as_ref().storage, 1).unwrap(), 1);
+
+        res.map_err(|e| e.to_string()).unwrap();
+    }
 }
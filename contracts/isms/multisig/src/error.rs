@@ -27,6 +27,9 @@ pub enum ContractError {
     #[error("invalid address. reason: {0}")]
     InvalidAddress(String),
 
+    #[error("invalid signature. reason: {0}")]
+    InvalidSignature(String),
+
     #[error("invalid arguments. reason: {reason:?}")]
     InvalidArguments { reason: String },
 
@@ -42,6 +45,10 @@ impl ContractError {
         ContractError::InvalidAddress(reason.into())
     }
 
+    pub fn invalid_signature(reason: &str) -> Self {
+        ContractError::InvalidSignature(reason.into())
+    }
+
     pub fn invalid_args(reason: &str) -> Self {
         ContractError::InvalidArguments {
             reason: reason.into(),
@@ -1,50 +1,120 @@
-use cosmwasm_std::{Deps, HexBinary};
+use cosmwasm_std::{Deps, Env, HexBinary};
 use hpl_interface::{
     ism::{IsmType, ModuleTypeResponse, VerifyInfoResponse, VerifyResponse},
-    types::{eth_addr, eth_hash, Message, MessageIdMultisigIsmMetadata},
+    types::{
+        domain_hash, eth_addr, eth_hash, multisig_hash, MerkleRootMultisigIsmMetadata, Message,
+        MessageIdMultisigIsmMetadata,
+    },
 };
 
 use crate::{
-    domain_hash, multisig_hash,
-    state::{THRESHOLD, VALIDATORS},
+    signature::{Signature, SignatureFormat},
+    state::{load_active_set, MODULE_TYPE},
     ContractError,
 };
 
-pub fn get_module_type() -> Result<ModuleTypeResponse, ContractError> {
+// origin_merkle_tree (32) + merkle_root (32) + merkle_index (4)
+const SIGNATURES_OFFSET: usize = 68;
+
+// origin_merkle_tree (32) + message_index (4) + message_id (32) + proof (32 * 32) + checkpoint_index (4)
+const MERKLE_ROOT_SIGNATURES_OFFSET: usize = 1096;
+
+// contracts instantiated before the module type was stored only verified message ids
+pub fn get_module_type(deps: Deps) -> Result<ModuleTypeResponse, ContractError> {
     Ok(ModuleTypeResponse {
-        typ: IsmType::MessageIdMultisig,
+        typ: MODULE_TYPE
+            .may_load(deps.storage)?
+            .unwrap_or(IsmType::MessageIdMultisig),
     })
 }
 
 pub fn verify_message(
     deps: Deps,
+    env: Env,
     raw_metadata: HexBinary,
     raw_message: HexBinary,
 ) -> Result<VerifyResponse, ContractError> {
+    if get_module_type(deps)?.typ == IsmType::MerkleRootMultisig {
+        return verify_merkle_root_message(deps, env, raw_metadata, raw_message);
+    }
+
+    if raw_metadata.len() < SIGNATURES_OFFSET {
+        return Err(ContractError::invalid_args("metadata too short"));
+    }
+
+    let signatures = SignatureFormat::default().parse(&raw_metadata[SIGNATURES_OFFSET..])?;
+
     let metadata: MessageIdMultisigIsmMetadata = raw_metadata.into();
     let message: Message = raw_message.into();
 
     let merkle_index = metadata.merkle_index();
 
     let multisig_hash = multisig_hash(
-        domain_hash(message.origin_domain, metadata.origin_merkle_tree)?.to_vec(),
+        domain_hash(message.origin_domain, metadata.origin_merkle_tree).to_vec(),
         metadata.merkle_root.to_vec(),
         merkle_index,
         message.id().to_vec(),
-    )?;
+    );
+
+    let verified = verify_signatures(deps, env, message.origin_domain, multisig_hash, signatures)?;
+
+    Ok(VerifyResponse { verified })
+}
 
+pub fn verify_merkle_root_message(
+    deps: Deps,
+    env: Env,
+    raw_metadata: HexBinary,
+    raw_message: HexBinary,
+) -> Result<VerifyResponse, ContractError> {
+    if raw_metadata.len() < MERKLE_ROOT_SIGNATURES_OFFSET {
+        return Err(ContractError::invalid_args("metadata too short"));
+    }
+
+    let signatures =
+        SignatureFormat::default().parse(&raw_metadata[MERKLE_ROOT_SIGNATURES_OFFSET..])?;
+
+    let metadata: MerkleRootMultisigIsmMetadata = raw_metadata.into();
+    let message: Message = raw_message.into();
+
+    if metadata.message_index > metadata.checkpoint_index {
+        return Err(ContractError::invalid_args(
+            "message index exceeds checkpoint index",
+        ));
+    }
+
+    // a wrong leaf index yields a different root, which the validators never signed
+    let merkle_root = metadata.branch_root(message.id())?;
+
+    let multisig_hash = multisig_hash(
+        domain_hash(message.origin_domain, metadata.origin_mailbox).to_vec(),
+        merkle_root.to_vec(),
+        metadata.checkpoint_index,
+        metadata.message_id.to_vec(),
+    );
+
+    let verified = verify_signatures(deps, env, message.origin_domain, multisig_hash, signatures)?;
+
+    Ok(VerifyResponse { verified })
+}
+
+fn verify_signatures(
+    deps: Deps,
+    env: Env,
+    origin_domain: u32,
+    multisig_hash: HexBinary,
+    signatures: Vec<Signature>,
+) -> Result<bool, ContractError> {
     let hashed_message = eth_hash(multisig_hash)?;
 
     // pizza :)
-    let validators = VALIDATORS.load(deps.storage, message.origin_domain)?;
-    let mut threshold = THRESHOLD.load(deps.storage, message.origin_domain)?;
+    let (validators, mut threshold) = load_active_set(deps.storage, env.block.time, origin_domain)?;
 
-    for signature in metadata.signatures {
-        let signature = signature.as_slice();
+    for signature in signatures {
         let pubkey = deps.api.secp256k1_recover_pubkey(
             &hashed_message,
-            &signature[..64],
-            signature[64] - 27,
+            &signature.rs,
+            signature.recovery_id,
         )?;
 
         if validators.contains(&eth_addr(pubkey.into())?) {
@@ -55,19 +125,18 @@ pub fn verify_message(
         }
     }
 
-    Ok(VerifyResponse {
-        verified: threshold == 0,
-    })
+    Ok(threshold == 0)
 }
 
 pub fn get_verify_info(
     deps: Deps,
+    env: Env,
     raw_message: HexBinary,
 ) -> Result<VerifyInfoResponse, ContractError> {
     let message: Message = raw_message.into();
 
-    let threshold = THRESHOLD.load(deps.storage, message.origin_domain)?;
-    let validators = VALIDATORS.load(deps.storage, message.origin_domain)?;
+    let (validators, threshold) =
+        load_active_set(deps.storage, env.block.time, message.origin_domain)?;
 
     Ok(VerifyInfoResponse {
         threshold,
@@ -77,28 +146,40 @@ pub fn get_verify_info(
 
 #[cfg(test)]
 mod test {
-    use crate::state::{THRESHOLD, VALIDATORS};
-    use cosmwasm_std::{testing::mock_dependencies, HexBinary};
+    use crate::state::{
+        ScheduledValidators, MODULE_TYPE, SCHEDULED_VALIDATORS, THRESHOLD, VALIDATORS,
+    };
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env},
+        HexBinary,
+    };
     use hpl_interface::{
         ism::{IsmType, ModuleTypeResponse, VerifyResponse},
-        types::{eth_addr, Message},
+        types::{
+            domain_hash, eth_addr, eth_hash, multisig_hash, MerkleRootMultisigIsmMetadata,
+            MerkleTree, Message, MessageIdMultisigIsmMetadata,
+        },
     };
-    use ibcx_test_utils::hex;
+    use ibcx_test_utils::{gen_bz, hex};
     use k256::{ecdsa::SigningKey, elliptic_curve::rand_core::OsRng};
     use rstest::rstest;
 
     use super::{get_module_type, get_verify_info, verify_message};
 
-    #[test]
-    fn test_get_module_type() {
-        let result = get_module_type().unwrap();
+    #[rstest]
+    #[case(None, IsmType::MessageIdMultisig)]
+    #[case(Some(IsmType::MessageIdMultisig), IsmType::MessageIdMultisig)]
+    #[case(Some(IsmType::MerkleRootMultisig), IsmType::MerkleRootMultisig)]
+    fn test_get_module_type(#[case] stored: Option<IsmType>, #[case] expected: IsmType) {
+        let mut deps = mock_dependencies();
 
-        assert_eq!(
-            result,
-            ModuleTypeResponse {
-                typ: IsmType::MessageIdMultisig
-            }
-        );
+        if let Some(typ) = stored {
+            MODULE_TYPE.save(deps.as_mut().storage, &typ).unwrap();
+        }
+
+        let result = get_module_type(deps.as_ref()).unwrap();
+
+        assert_eq!(result, ModuleTypeResponse { typ: expected });
     }
 
     #[rstest]
@@ -135,10 +216,116 @@ mod test {
             .save(deps.as_mut().storage, message.origin_domain, &1u8)
             .unwrap();
 
-        let res = verify_message(deps.as_ref(), raw_metadata, raw_message).unwrap();
+        let res = verify_message(deps.as_ref(), mock_env(), raw_metadata, raw_message).unwrap();
         assert_eq!(res, VerifyResponse { verified: true });
     }
 
+    #[test]
+    #[should_panic(
+        expected = "invalid signature. reason: expected a multiple of 65 bytes, got 129"
+    )]
+    fn test_verify_truncated_signatures() {
+        let raw_message = hex("03000000240001388100000000000000000000000004980c17e2ce26578c82f81207e706e4505fae3b0000a8690000000000000000000000000b1c1b54f45e02552331d3106e71f5e0b573d5d448656c6c6f21");
+        let raw_metadata = hex("0000000000000000000000009af85731edd41e2e50f81ef8a0a69d2fb836edf9a84430f822e0e9b5942faace72bd5b97f0b59a58a9b8281231d9e5c393b5859c00000024539feceace17782697e29e74151006dc7b47227cf48aba02926336cb5f7fa38b3d05e8293045f7b5811eda3ae8aa070116bb5fbf57c79e143a69e909df90cefa1b6e6ead7180e0415c36642ee4bc5454bc4f5ca250ca77a1a83562035544e0e898734d6541a20404e05fd53eb1c75b0bd21851c3bd8122cfa3550d7b6fb94d7cee1b");
+
+        let deps = mock_dependencies();
+
+        let truncated = raw_metadata[..raw_metadata.len() - 1].to_vec().into();
+
+        verify_message(deps.as_ref(), mock_env(), truncated, raw_message)
+            .map_err(|e| e.to_string())
+            .unwrap();
+    }
+
+    #[rstest]
+    #[case(IsmType::MerkleRootMultisig, 5, true)]
+    #[case(IsmType::MerkleRootMultisig, 4, false)]
+    #[should_panic(expected = "message index exceeds checkpoint index")]
+    #[case(IsmType::MerkleRootMultisig, 6, false)]
+    // not parsed as merkle root metadata unless the ism is configured for it
+    #[should_panic(expected = "expected a multiple of 65 bytes, got 1093")]
+    #[case(IsmType::MessageIdMultisig, 5, false)]
+    fn test_verify_merkle_root_index(
+        #[case] module_type: IsmType,
+        #[case] message_index: u32,
+        #[case] verified: bool,
+    ) {
+        const LEAF_INDEX: u32 = 5;
+
+        let mut deps = mock_dependencies();
+
+        MODULE_TYPE
+            .save(deps.as_mut().storage, &module_type)
+            .unwrap();
+
+        let message = Message {
+            version: 3,
+            nonce: LEAF_INDEX,
+            origin_domain: 26658,
+            sender: gen_bz(32),
+            dest_domain: 26657,
+            recipient: gen_bz(32),
+            body: gen_bz(123),
+        };
+        let origin_mailbox = gen_bz(32);
+        let proof = gen_bz(32 * 32);
+
+        let signing_key = SigningKey::random(&mut OsRng);
+        let validator = eth_addr(
+            signing_key
+                .verifying_key()
+                .to_encoded_point(false)
+                .as_bytes()
+                .into(),
+        )
+        .unwrap();
+
+        VALIDATORS
+            .save(
+                deps.as_mut().storage,
+                message.origin_domain,
+                &vec![validator],
+            )
+            .unwrap();
+        THRESHOLD
+            .save(deps.as_mut().storage, message.origin_domain, &1u8)
+            .unwrap();
+
+        // validators sign the root computed at the real leaf index
+        let branch = proof
+            .chunks_exact(32)
+            .map(HexBinary::from)
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+        let root = MerkleTree::branch_root(message.id(), branch, LEAF_INDEX as u128);
+        let digest = eth_hash(multisig_hash(
+            domain_hash(message.origin_domain, origin_mailbox.clone()).to_vec(),
+            root.to_vec(),
+            LEAF_INDEX,
+            message.id().to_vec(),
+        ))
+        .unwrap();
+
+        let (signature, recovery_id) = signing_key.sign_prehash_recoverable(&digest).unwrap();
+        let mut signature = signature.to_bytes().to_vec();
+        signature.push(recovery_id.to_byte() + 27);
+
+        let metadata = MerkleRootMultisigIsmMetadata {
+            origin_mailbox,
+            message_index,
+            message_id: message.id(),
+            proof,
+            checkpoint_index: LEAF_INDEX,
+            signatures: signature.into(),
+        };
+
+        let res = verify_message(deps.as_ref(), mock_env(), metadata.into(), message.into())
+            .map_err(|e| e.to_string())
+            .unwrap();
+        assert_eq!(res, VerifyResponse { verified });
+    }
+
     #[test]
     fn test_get_verify_info() {
         let raw_message = hex("0000000000000068220000000000000000000000000d1255b09d94659bb0888e0aa9fca60245ce402a0000682155208cd518cffaac1b5d8df216a9bd050c9a03f0d4f3ba88e5268ac4cd12ee2d68656c6c6f");
@@ -155,9 +342,113 @@ mod test {
             .unwrap();
         THRESHOLD.save(deps.as_mut().storage, 26658, &1u8).unwrap();
 
-        let info = get_verify_info(deps.as_ref(), raw_message).unwrap();
+        let info = get_verify_info(deps.as_ref(), mock_env(), raw_message).unwrap();
 
         assert_eq!(info.validators, vec![addr]);
         assert_eq!(info.threshold, 1);
     }
+
+    #[rstest]
+    #[case(0, true, false)]
+    #[case(99, true, false)]
+    #[case(100, false, true)]
+    #[case(200, false, true)]
+    fn test_verify_scheduled_rotation(
+        #[case] elapsed: u64,
+        #[case] old_verified: bool,
+        #[case] new_verified: bool,
+    ) {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let message = Message {
+            version: 3,
+            nonce: 1,
+            origin_domain: 26658,
+            sender: gen_bz(32),
+            dest_domain: 26657,
+            recipient: gen_bz(32),
+            body: gen_bz(123),
+        };
+        let origin_merkle_tree = gen_bz(32);
+        let merkle_root = gen_bz(32);
+
+        let old_key = SigningKey::random(&mut OsRng);
+        let new_key = SigningKey::random(&mut OsRng);
+        let to_addr = |key: &SigningKey| {
+            eth_addr(
+                key.verifying_key()
+                    .to_encoded_point(false)
+                    .as_bytes()
+                    .into(),
+            )
+            .unwrap()
+        };
+
+        VALIDATORS
+            .save(
+                deps.as_mut().storage,
+                message.origin_domain,
+                &vec![to_addr(&old_key)],
+            )
+            .unwrap();
+        THRESHOLD
+            .save(deps.as_mut().storage, message.origin_domain, &1u8)
+            .unwrap();
+        SCHEDULED_VALIDATORS
+            .save(
+                deps.as_mut().storage,
+                message.origin_domain,
+                &ScheduledValidators {
+                    validators: vec![to_addr(&new_key)],
+                    threshold: 1,
+                    effective_at: env.block.time.seconds() + 100,
+                },
+            )
+            .unwrap();
+
+        let digest = eth_hash(multisig_hash(
+            domain_hash(message.origin_domain, origin_merkle_tree.clone()).to_vec(),
+            merkle_root.to_vec(),
+            0,
+            message.id().to_vec(),
+        ))
+        .unwrap();
+        let sign = |key: &SigningKey| {
+            let (signature, recovery_id) = key.sign_prehash_recoverable(&digest).unwrap();
+            let mut signature = signature.to_bytes().to_vec();
+            signature.push(recovery_id.to_byte() + 27);
+
+            MessageIdMultisigIsmMetadata {
+                origin_merkle_tree: origin_merkle_tree.clone(),
+                merkle_root: merkle_root.clone(),
+                merkle_index: 0u32.to_be_bytes().to_vec().into(),
+                signatures: vec![signature.into()],
+            }
+        };
+
+        env.block.time = env.block.time.plus_seconds(elapsed);
+
+        let res = verify_message(
+            deps.as_ref(),
+            env.clone(),
+            sign(&old_key).into(),
+            message.clone().into(),
+        )
+        .unwrap();
+        assert_eq!(res.verified, old_verified);
+
+        let res = verify_message(
+            deps.as_ref(),
+            env.clone(),
+            sign(&new_key).into(),
+            message.clone().into(),
+        )
+        .unwrap();
+        assert_eq!(res.verified, new_verified);
+
+        let info = get_verify_info(deps.as_ref(), env, message.into()).unwrap();
+        let active_key = if new_verified { &new_key } else { &old_key };
+        assert_eq!(info.validators, vec![to_addr(active_key)]);
+    }
 }
@@ -0,0 +1,93 @@
+use crate::ContractError;
+
+pub const RSV_SIGNATURE_LENGTH: usize = 65;
+
+/// Layout of the validator signatures concatenated in the ism metadata
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SignatureFormat {
+    /// 64 bytes of (r, s) followed by a one byte `v` of 27 or 28
+    #[default]
+    Rsv,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Signature {
+    pub rs: Vec<u8>,
+    pub recovery_id: u8,
+}
+
+impl SignatureFormat {
+    pub fn signature_len(&self) -> usize {
+        match self {
+            SignatureFormat::Rsv => RSV_SIGNATURE_LENGTH,
+        }
+    }
+
+    pub fn parse(&self, bz: &[u8]) -> Result<Vec<Signature>, ContractError> {
+        let len = self.signature_len();
+
+        if !bz.len().is_multiple_of(len) {
+            return Err(ContractError::invalid_signature(&format!(
+                "expected a multiple of {len} bytes, got {}",
+                bz.len()
+            )));
+        }
+
+        bz.chunks_exact(len)
+            .map(|v| match self {
+                SignatureFormat::Rsv => {
+                    let recovery_id = match v[64] {
+                        27 | 28 => v[64] - 27,
+                        v => {
+                            return Err(ContractError::invalid_signature(&format!(
+                                "invalid recovery byte {v}"
+                            )))
+                        }
+                    };
+
+                    Ok(Signature {
+                        rs: v[..64].to_vec(),
+                        recovery_id,
+                    })
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ibcx_test_utils::gen_bz;
+    use rstest::rstest;
+
+    use super::*;
+
+    fn rsv(v: u8) -> Vec<u8> {
+        [gen_bz(64).as_slice(), &[v]].concat()
+    }
+
+    #[test]
+    fn test_parse_rsv() {
+        let bz = [rsv(27), rsv(28)].concat();
+
+        let signatures = SignatureFormat::Rsv.parse(&bz).unwrap();
+
+        assert_eq!(signatures.len(), 2);
+        assert_eq!(signatures[0].rs, bz[..64].to_vec());
+        assert_eq!(signatures[0].recovery_id, 0);
+        assert_eq!(signatures[1].rs, bz[65..129].to_vec());
+        assert_eq!(signatures[1].recovery_id, 1);
+    }
+
+    #[rstest]
+    #[case::truncated([rsv(27), rsv(28)].concat()[..129].to_vec(), "expected a multiple of 65 bytes, got 129")]
+    #[case::recovery_byte(rsv(1), "invalid recovery byte 1")]
+    fn test_parse_rsv_malformed(#[case] bz: Vec<u8>, #[case] reason: &str) {
+        let err = SignatureFormat::Rsv.parse(&bz).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!("invalid signature. reason: {reason}")
+        );
+    }
+}
@@ -1,8 +1,63 @@
-use cosmwasm_std::HexBinary;
-use cw_storage_plus::Map;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{HexBinary, StdResult, Storage, Timestamp};
+use cw_storage_plus::{Item, Map};
+use hpl_interface::ism::IsmType;
+
+#[cw_serde]
+pub struct ScheduledValidators {
+    pub validators: Vec<HexBinary>,
+    pub threshold: u8,
+    pub effective_at: u64,
+}
+
+impl ScheduledValidators {
+    pub fn is_effective(&self, now: Timestamp) -> bool {
+        now.seconds() >= self.effective_at
+    }
+}
+
+pub const MODULE_TYPE_KEY: &str = "module_type";
+pub const MODULE_TYPE: Item<IsmType> = Item::new(MODULE_TYPE_KEY);
 
 pub const VALIDATORS_PREFIX: &str = "validators";
 pub const VALIDATORS: Map<u32, Vec<HexBinary>> = Map::new(VALIDATORS_PREFIX);
 
 pub const THRESHOLD_PREFIX: &str = "threshold";
 pub const THRESHOLD: Map<u32, u8> = Map::new(THRESHOLD_PREFIX);
+
+pub const SCHEDULED_VALIDATORS_PREFIX: &str = "scheduled_validators";
+pub const SCHEDULED_VALIDATORS: Map<u32, ScheduledValidators> =
+    Map::new(SCHEDULED_VALIDATORS_PREFIX);
+
+/// Returns the validator set and threshold in effect for the domain at `now`.
+/// A scheduled set takes over as soon as its effective time is reached.
+pub fn load_active_set(
+    storage: &dyn Storage,
+    now: Timestamp,
+    domain: u32,
+) -> StdResult<(Vec<HexBinary>, u8)> {
+    if let Some(scheduled) = SCHEDULED_VALIDATORS.may_load(storage, domain)? {
+        if scheduled.is_effective(now) {
+            return Ok((scheduled.validators, scheduled.threshold));
+        }
+    }
+
+    Ok((
+        VALIDATORS.load(storage, domain)?,
+        THRESHOLD.load(storage, domain)?,
+    ))
+}
+
+/// Queries can't write, so an effective schedule is only moved into the
+/// active set on the next execution that touches the domain.
+pub fn promote_scheduled(storage: &mut dyn Storage, now: Timestamp, domain: u32) -> StdResult<()> {
+    if let Some(scheduled) = SCHEDULED_VALIDATORS.may_load(storage, domain)? {
+        if scheduled.is_effective(now) {
+            VALIDATORS.save(storage, domain, &scheduled.validators)?;
+            THRESHOLD.save(storage, domain, &scheduled.threshold)?;
+            SCHEDULED_VALIDATORS.remove(storage, domain);
+        }
+    }
+
+    Ok(())
+}
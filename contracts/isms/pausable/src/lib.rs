@@ -154,7 +154,7 @@ mod test {
     #[case(true)]
     fn test_query(mut deps: TestDeps, #[case] paused: bool) {
         if paused {
-            hpl_pausable::pause(deps.as_mut().storage, &addr("owner")).unwrap();
+            hpl_pausable::pause(deps.as_mut().storage, &mock_env(), &addr("owner"), None).unwrap();
         }
 
         let raw_message = hex("0000000000000068220000000000000000000000000d1255b09d94659bb0888e0aa9fca60245ce402a0000682155208cd518cffaac1b5d8df216a9bd050c9a03f0d4f3ba88e5268ac4cd12ee2d68656c6c6f");
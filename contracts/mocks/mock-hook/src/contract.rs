@@ -8,7 +8,8 @@ use cosmwasm_std::{
 use cw2::set_contract_version;
 use cw_storage_plus::Item;
 use hpl_interface::hook::{
-    ExpectedHookQueryMsg, HookQueryMsg, PostDispatchMsg, QuoteDispatchResponse,
+    ExpectedHookQueryMsg, HookQueryMsg, MetadataFormat, MetadataFormatResponse, PostDispatchMsg,
+    QuoteDispatchResponse,
 };
 
 use crate::{CONTRACT_NAME, CONTRACT_VERSION};
@@ -109,6 +110,9 @@ pub fn query(deps: Deps, _env: Env, msg: ExpectedHookQueryMsg) -> StdResult<Quer
             HookQueryMsg::Mailbox {} => {
                 unimplemented!("mailbox query not implemented on mock hook")
             }
+            HookQueryMsg::MetadataFormat {} => Ok(to_json_binary(&MetadataFormatResponse::from(
+                MetadataFormat::None,
+            ))?),
         },
     }
 }
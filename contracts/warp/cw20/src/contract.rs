@@ -1,11 +1,12 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure_eq, to_json_binary, wasm_execute, CosmosMsg, Deps, DepsMut, Empty, Env, HexBinary,
-    MessageInfo, QueryResponse, Reply, Response, StdError, SubMsg, Uint128, Uint256, WasmMsg,
+    ensure, ensure_eq, from_json, to_json_binary, wasm_execute, Addr, BankMsg, CosmosMsg, Deps,
+    DepsMut, Empty, Env, HexBinary, MessageInfo, QueryResponse, Reply, Response, StdError, Storage,
+    SubMsg, Uint128, Uint256, WasmMsg,
 };
 
-use cw20::Cw20ExecuteMsg;
+use cw20::{Cw20ExecuteMsg, Cw20QueryMsg};
 use hpl_connection::{get_hook, get_ism};
 use hpl_interface::{
     core::mailbox,
@@ -17,12 +18,21 @@ use hpl_interface::{
         cw20::{ExecuteMsg, InstantiateMsg, QueryMsg},
         TokenMode, TokenModeMsg, TokenModeResponse, TokenTypeResponse,
     },
+    EventVerbosity,
 };
 use hpl_router::get_route;
+use hpl_warp::{
+    admin, conv, split_gas,
+    state::{
+        ensure_mode, record_inbound, record_outbound, EMBED_NONCE, EVENT_VERBOSITY, MODE,
+        OUTSTANDING, OUTSTANDING_CAP, REMOTE_DECIMALS,
+    },
+    to_local_amount, to_remote_amount,
+};
 
 use crate::{
-    conv, error::ContractError, new_event, CONTRACT_NAME, CONTRACT_VERSION, HRP, MAILBOX, MODE,
-    REPLY_ID_CREATE_DENOM, TOKEN,
+    error::ContractError, new_event, ALLOWED_SENDERS, CONTRACT_NAME, CONTRACT_VERSION, HRP,
+    MAILBOX, MAX_DECIMALS, REPLY_ID_CREATE_DENOM, REPLY_ID_DISPATCH, TOKEN,
 };
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -34,6 +44,8 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    msg.validate()?;
+
     let mode: TokenMode = msg.token.clone().into();
     let owner = deps.api.addr_validate(&msg.owner)?;
     let mailbox = deps.api.addr_validate(&msg.mailbox)?;
@@ -46,6 +58,15 @@ pub fn instantiate(
 
     let (msgs, denom) = match msg.token {
         TokenModeMsg::Bridged(token) => {
+            if let Some(allowed) = msg.allowed_code_ids {
+                ensure!(
+                    allowed.contains(&token.code_id),
+                    ContractError::CodeIdNotAllowed {
+                        code_id: token.code_id
+                    }
+                );
+            }
+
             let mut token_init_msg = token.init_msg;
             token_init_msg.mint = Some(cw20::MinterResponse {
                 minter: env.contract.address.to_string(),
@@ -111,22 +132,51 @@ pub fn execute(
             hook,
             metadata,
         ),
+        SetRemoteDecimals {
+            dest_domain,
+            decimals,
+        } => set_remote_decimals(deps, info, dest_domain, decimals),
+        SetAllowedSenders { domain, senders } => set_allowed_senders(deps, info, domain, senders),
+        SetMinRefund { min_refund } => {
+            Ok(admin::set_min_refund(deps, info, new_event, min_refund)?)
+        }
+        SetEmbedNonce { embed_nonce } => {
+            Ok(admin::set_embed_nonce(deps, info, new_event, embed_nonce)?)
+        }
+        SetOutstandingCap { cap } => Ok(admin::set_outstanding_cap(deps, info, new_event, cap)?),
+        SetEventVerbosity { verbosity } => Ok(admin::set_event_verbosity(
+            deps, info, new_event, verbosity,
+        )?),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let reply_data = msg
+        .result
+        .into_result()
+        .map_err(StdError::generic_err)?
+        .data
+        .ok_or(StdError::generic_err("no reply data"))?;
+
     match msg.id {
         REPLY_ID_CREATE_DENOM => {
-            let reply_data = msg
-                .result
-                .into_result()
-                .map_err(StdError::generic_err)?
-                .data
-                .ok_or(StdError::generic_err("no reply data"))?;
             let init_resp = cw_utils::parse_instantiate_response_data(&reply_data)?;
             let init_addr = deps.api.addr_validate(&init_resp.contract_address)?;
 
+            // catch misconfigured init_msg here rather than at the first mint
+            let minter: Option<cw20::MinterResponse> = deps
+                .querier
+                .query_wasm_smart(&init_addr, &Cw20QueryMsg::Minter {})?;
+            let minter = minter.map(|v| v.minter);
+            ensure!(
+                minter.as_deref() == Some(env.contract.address.as_str()),
+                ContractError::InvalidMinter {
+                    expected: env.contract.address.into(),
+                    got: minter,
+                }
+            );
+
             TOKEN.save(deps.storage, &init_addr)?;
 
             let resp = Response::new()
@@ -135,6 +185,22 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
             Ok(resp)
         }
 
+        REPLY_ID_DISPATCH => {
+            let exec_resp = cw_utils::parse_execute_response_data(&reply_data)?;
+            let dispatch: mailbox::DispatchResponse = from_json(
+                exec_resp
+                    .data
+                    .ok_or(StdError::generic_err("no dispatch data"))?,
+            )?;
+
+            let resp = Response::new().add_event(
+                new_event("transfer-remote-dispatched")
+                    .add_attribute("message_id", dispatch.message_id.to_hex()),
+            );
+
+            Ok(resp)
+        }
+
         _ => Err(ContractError::InvalidReplyId),
     }
 }
@@ -151,34 +217,58 @@ fn mailbox_handle(
         ContractError::Unauthorized
     );
     // validate origin chain router
-    ensure_eq!(
-        msg.sender,
-        get_route::<HexBinary>(deps.storage, msg.origin)?
-            .route
-            .expect("route not found"),
+    ensure!(
+        is_allowed_sender(deps.as_ref(), msg.origin, &msg.sender)?,
         ContractError::Unauthorized
     );
 
     let token_msg: warp::Message = msg.body.into();
-    let recipient = bech32_encode(&HRP.load(deps.storage)?, &token_msg.recipient)?;
+    let recipient =
+        bech32_encode(&HRP.load(deps.storage)?, &token_msg.recipient).map_err(|_| {
+            ContractError::InvalidRecipient {
+                recipient: token_msg.recipient.to_hex(),
+            }
+        })?;
 
     let token = TOKEN.load(deps.storage)?;
     let mode = MODE.load(deps.storage)?;
 
-    let msg = match mode {
+    let amount = to_local_amount(deps.as_ref(), token.as_str(), msg.origin, token_msg.amount)?;
+
+    record_inbound(deps.storage, amount)?;
+
+    let token_out = match mode {
         // make token mint msg if token mode is bridged
-        TokenMode::Bridged => conv::to_mint_msg(&token, &recipient, token_msg.amount)?,
+        TokenMode::Bridged => mint_msg(deps.storage, &token, &recipient, amount)?,
         // make token transfer msg if token mode is collateral
         // we can consider to use MsgSend for further utility
-        TokenMode::Collateral => conv::to_send_msg(&token, &recipient, token_msg.amount)?,
+        TokenMode::Collateral => release_msg(deps.storage, &token, &recipient, amount)?,
     };
 
-    Ok(Response::new().add_message(msg).add_event(
-        new_event("handle")
+    let verbosity = EVENT_VERBOSITY.may_load(deps.storage)?.unwrap_or_default();
+
+    let mut event = new_event("handle");
+    // recorded by the mailbox right before it hands the message over.
+    // mailboxes without the LatestProcessId query leave it out
+    if let Ok(message_id) = mailbox::latest_process_id(&deps.querier, &info.sender) {
+        event = event.add_attribute("message_id", message_id.to_hex());
+    }
+    if verbosity >= EventVerbosity::Standard {
+        event = event
             .add_attribute("recipient", recipient)
             .add_attribute("token", token)
-            .add_attribute("amount", token_msg.amount),
-    ))
+            .add_attribute("amount", amount);
+        if let Some(nonce) = token_msg.nonce() {
+            event = event.add_attribute("nonce", nonce.to_string());
+        }
+    }
+    if verbosity >= EventVerbosity::Verbose {
+        event = event
+            .add_attribute("origin", msg.origin.to_string())
+            .add_attribute("origin_sender", msg.sender.to_hex());
+    }
+
+    Ok(Response::new().add_message(token_out).add_event(event))
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -205,6 +295,8 @@ fn transfer_remote(
         let _ = deps.api.addr_validate(custom_hook)?;
     }
 
+    record_outbound(deps.storage, Uint256::from_uint128(transfer_amount))?;
+
     let mut msgs: Vec<CosmosMsg> = vec![];
 
     // push token transfer msg
@@ -223,37 +315,194 @@ fn transfer_remote(
 
     if mode == TokenMode::Bridged {
         // push token burn msg if token is bridged
-        msgs.push(conv::to_burn_msg(&token, transfer_amount)?.into());
+        msgs.push(burn_msg(deps.storage, &token, transfer_amount)?.into());
     }
 
-    // push mailbox dispatch msg
-    msgs.push(mailbox::dispatch(
-        mailbox,
+    let remote_amount = to_remote_amount(
+        deps.as_ref(),
+        token.as_str(),
         dest_domain,
-        dest_router,
-        warp::Message {
-            recipient: recipient.clone(),
-            amount: Uint256::from_uint128(transfer_amount),
-            metadata: HexBinary::default(),
-        }
-        .into(),
-        hook.clone().or(get_hook(deps.storage)?.map(|v| v.into())),
-        metadata.clone(),
-        info.funds,
-    )?);
+        Uint256::from_uint128(transfer_amount),
+    )?;
+
+    let mut dispatch_payload = warp::Message {
+        recipient: recipient.clone(),
+        amount: remote_amount,
+        metadata: HexBinary::default(),
+    };
+    if EMBED_NONCE.may_load(deps.storage)?.unwrap_or_default() {
+        dispatch_payload = dispatch_payload.with_nonce(mailbox::nonce(&deps.querier, &mailbox)?);
+    }
+
+    let dispatch_msg = mailbox::DispatchMsg {
+        dest_domain,
+        recipient_addr: dest_router.clone(),
+        msg_body: dispatch_payload.into(),
+        hook: hook.clone().or(get_hook(deps.storage)?.map(|v| v.into())),
+        metadata: metadata.clone(),
+    };
 
-    Ok(Response::new().add_messages(msgs).add_event(
-        new_event("transfer-remote")
+    let (funds, refund) = split_gas(deps.as_ref(), &env, &mailbox, &dispatch_msg, info.funds)?;
+
+    // push mailbox dispatch msg. the reply picks up the id the mailbox assigns to it
+    let dispatch = SubMsg::reply_on_success(
+        mailbox::dispatch(
+            mailbox,
+            dispatch_msg.dest_domain,
+            dispatch_msg.recipient_addr,
+            dispatch_msg.msg_body,
+            dispatch_msg.hook,
+            dispatch_msg.metadata,
+            funds,
+        )?,
+        REPLY_ID_DISPATCH,
+    );
+    let mut resp = Response::new().add_messages(msgs).add_submessage(dispatch);
+
+    // refund gas overpayment
+    if !refund.is_empty() {
+        resp = resp.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: refund,
+        });
+    }
+
+    let verbosity = EVENT_VERBOSITY.may_load(deps.storage)?.unwrap_or_default();
+
+    // at Minimal only the id is emitted, by the transfer-remote-dispatched event of the reply
+    if verbosity >= EventVerbosity::Standard {
+        let mut event = new_event("transfer-remote")
             .add_attribute("sender", info.sender)
+            .add_attribute("amount", transfer_amount)
             .add_attribute("dest_domain", dest_domain.to_string())
             .add_attribute("recipient", recipient.to_hex())
             .add_attribute("token", token)
-            .add_attribute("amount", transfer_amount)
+            .add_attribute("remote_amount", remote_amount)
             .add_attribute("hook", hook.unwrap_or_default())
-            .add_attribute("metadata", metadata.unwrap_or_default().to_string()),
+            .add_attribute("metadata", metadata.unwrap_or_default().to_string());
+        if verbosity >= EventVerbosity::Verbose {
+            event = event.add_attribute("dest_router", dest_router.to_hex());
+        }
+        resp = resp.add_event(event);
+    }
+
+    Ok(resp)
+}
+
+fn set_allowed_senders(
+    deps: DepsMut,
+    info: MessageInfo,
+    domain: u32,
+    senders: Vec<HexBinary>,
+) -> Result<Response, ContractError> {
+    ensure_eq!(
+        hpl_ownable::get_owner(deps.storage)?,
+        info.sender,
+        ContractError::Unauthorized
+    );
+
+    if senders.is_empty() {
+        ALLOWED_SENDERS.remove(deps.storage, domain);
+    } else {
+        ALLOWED_SENDERS.save(deps.storage, domain, &senders)?;
+    }
+
+    Ok(Response::new().add_event(
+        new_event("set-allowed-senders")
+            .add_attribute("sender", info.sender)
+            .add_attribute("domain", domain.to_string())
+            .add_attribute(
+                "senders",
+                senders
+                    .iter()
+                    .map(|v| v.to_hex())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+    ))
+}
+
+fn is_allowed_sender(deps: Deps, origin: u32, sender: &HexBinary) -> Result<bool, ContractError> {
+    match ALLOWED_SENDERS.may_load(deps.storage, origin)? {
+        Some(senders) => Ok(senders.contains(sender)),
+        // defaults to the enrolled route. unknown origins are rejected
+        None => {
+            let route = get_route::<HexBinary>(deps.storage, origin)?.route;
+
+            Ok(route.as_ref() == Some(sender))
+        }
+    }
+}
+
+fn set_remote_decimals(
+    deps: DepsMut,
+    info: MessageInfo,
+    dest_domain: u32,
+    decimals: u8,
+) -> Result<Response, ContractError> {
+    ensure_eq!(
+        hpl_ownable::get_owner(deps.storage)?,
+        info.sender,
+        ContractError::Unauthorized
+    );
+    ensure!(
+        decimals <= MAX_DECIMALS,
+        ContractError::InvalidDecimals {
+            decimals,
+            max: MAX_DECIMALS
+        }
+    );
+
+    REMOTE_DECIMALS.save(deps.storage, dest_domain, &decimals)?;
+
+    Ok(Response::new().add_event(
+        new_event("set-remote-decimals")
+            .add_attribute("sender", info.sender)
+            .add_attribute("dest_domain", dest_domain.to_string())
+            .add_attribute("decimals", decimals.to_string()),
     ))
 }
 
+fn mint_msg(
+    storage: &dyn Storage,
+    token: &Addr,
+    recipient: &Addr,
+    amount: Uint256,
+) -> Result<WasmMsg, ContractError> {
+    ensure_mode(storage, TokenMode::Bridged)?;
+
+    Ok(conv::cw20::to_mint_msg(
+        token,
+        recipient,
+        conv::to_uint128(amount)?,
+    )?)
+}
+
+fn burn_msg(
+    storage: &dyn Storage,
+    token: &Addr,
+    amount: Uint128,
+) -> Result<WasmMsg, ContractError> {
+    ensure_mode(storage, TokenMode::Bridged)?;
+
+    Ok(conv::cw20::to_burn_msg(token, amount)?)
+}
+
+fn release_msg(
+    storage: &dyn Storage,
+    token: &Addr,
+    recipient: &Addr,
+    amount: Uint256,
+) -> Result<WasmMsg, ContractError> {
+    ensure_mode(storage, TokenMode::Collateral)?;
+
+    Ok(conv::cw20::to_send_msg(
+        token,
+        recipient,
+        conv::to_uint128(amount)?,
+    )?)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, ContractError> {
     use warp::TokenWarpDefaultQueryMsg::*;
@@ -265,6 +514,9 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, Contr
         QueryMsg::TokenDefault(msg) => match msg {
             TokenType {} => to_binary(get_token_type(deps)),
             TokenMode {} => to_binary(get_token_mode(deps)),
+            PreviewRecipient { recipient } => to_binary(preview_recipient(deps, recipient)),
+            CollateralDenom {} => to_binary(get_collateral_denom(deps)),
+            Outstanding {} => to_binary(get_outstanding(deps)),
         },
         QueryMsg::IsmSpecifier(IsmSpecifierQueryMsg::InterchainSecurityModule()) => {
             Ok(to_json_binary(&InterchainSecurityModuleResponse {
@@ -288,6 +540,32 @@ fn get_token_mode(deps: Deps) -> Result<TokenModeResponse, ContractError> {
     Ok(TokenModeResponse { mode })
 }
 
+fn get_collateral_denom(deps: Deps) -> Result<warp::CollateralDenomResponse, ContractError> {
+    ensure_mode(deps.storage, TokenMode::Collateral)?;
+
+    Ok(warp::CollateralDenomResponse {
+        denom: TOKEN.load(deps.storage)?.to_string(),
+    })
+}
+
+fn get_outstanding(deps: Deps) -> Result<warp::OutstandingResponse, ContractError> {
+    Ok(warp::OutstandingResponse {
+        outstanding: OUTSTANDING.may_load(deps.storage)?.unwrap_or_default(),
+        cap: OUTSTANDING_CAP.may_load(deps.storage)?.flatten(),
+    })
+}
+
+fn preview_recipient(
+    deps: Deps,
+    recipient: HexBinary,
+) -> Result<warp::PreviewRecipientResponse, ContractError> {
+    let recipient = bech32_encode(&HRP.load(deps.storage)?, &recipient)?;
+
+    Ok(warp::PreviewRecipientResponse {
+        recipient: recipient.into(),
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
     hpl_utils::migrate(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
@@ -297,16 +575,21 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, Contra
 #[cfg(test)]
 mod test {
     use cosmwasm_std::{
+        coin, from_json,
         testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
-        Empty, OwnedDeps, Uint128,
+        Binary, Coin, ContractResult, Empty, OwnedDeps, QuerierResult, SubMsgResponse,
+        SubMsgResult, SystemResult, Uint128, WasmQuery,
     };
+    use cw20::TokenInfoResponse;
     use hpl_interface::{
         build_test_executor, build_test_querier,
         core::HandleMsg,
+        hook::QuoteDispatchResponse,
         router::DomainRouteSet,
         warp::cw20::{Cw20ModeBridged, Cw20ModeCollateral},
     };
     use hpl_router::set_routes;
+    use hpl_warp::WarpError;
     use ibcx_test_utils::{addr, gen_bz};
     use rstest::{fixture, rstest};
 
@@ -369,6 +652,7 @@ mod test {
                 hrp: hrp.to_string(),
                 owner: OWNER.to_string(),
                 mailbox: MAILBOX.to_string(),
+                allowed_code_ids: None,
             },
         )
         .unwrap();
@@ -394,9 +678,43 @@ mod test {
             .unwrap();
         }
 
+        mock_quote(&mut deps, vec![]);
+
         (deps, res)
     }
 
+    const MESSAGE_ID: [u8; 32] = [0xab; 32];
+
+    fn mailbox_query(msg: &Binary, fees: &[Coin]) -> QuerierResult {
+        let res = match from_json(msg).unwrap() {
+            mailbox::QueryMsg::Hook(mailbox::MailboxHookQueryMsg::QuoteDispatch { .. }) => {
+                to_json_binary(&QuoteDispatchResponse {
+                    fees: fees.to_vec(),
+                })
+            }
+            mailbox::QueryMsg::Mailbox(mailbox::MailboxQueryMsg::Nonce {}) => {
+                to_json_binary(&mailbox::NonceResponse { nonce: 7 })
+            }
+            mailbox::QueryMsg::Mailbox(mailbox::MailboxQueryMsg::LatestProcessId {}) => {
+                to_json_binary(&mailbox::LatestProcessedIdResponse {
+                    message_id: MESSAGE_ID.into(),
+                })
+            }
+            _ => unreachable!("unexpected query"),
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    }
+
+    fn mock_quote(deps: &mut TestDeps, fees: Vec<Coin>) {
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, msg } => {
+                assert_eq!(contract_addr, MAILBOX);
+                mailbox_query(msg, &fees)
+            }
+            _ => unreachable!("unexpected query"),
+        });
+    }
+
     #[rstest]
     #[case(token_mode_bridged())]
     #[case(token_mode_collateral())]
@@ -443,7 +761,7 @@ mod test {
 
                 assert!(!super::TOKEN.exists(storage));
 
-                let reply = res.messages.get(0).unwrap();
+                let reply = res.messages.first().unwrap();
                 assert_eq!(reply.id, REPLY_ID_CREATE_DENOM);
                 assert_eq!(
                     reply.msg,
@@ -463,12 +781,121 @@ mod test {
         }
     }
 
+    #[rstest]
+    #[case(vec![CW20_BRIDGED_CODE_ID])]
+    #[should_panic(expected = "code id not allowed: 1")]
+    #[case(vec![CW20_BRIDGED_CODE_ID + 1])]
+    fn test_init_allowed_code_ids(#[case] allowed: Vec<u64>) {
+        let mut deps = mock_dependencies();
+
+        let res = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(DEPLOYER, &[]),
+            InstantiateMsg {
+                token: token_mode_bridged(),
+                hrp: "osmo".to_string(),
+                owner: OWNER.to_string(),
+                mailbox: MAILBOX.to_string(),
+                allowed_code_ids: Some(allowed),
+            },
+        )
+        .map_err(|e| e.to_string())
+        .unwrap();
+
+        assert_eq!(res.messages[0].id, REPLY_ID_CREATE_DENOM);
+    }
+
+    #[rstest]
+    #[case(mock_env().contract.address.to_string())]
+    #[should_panic(expected = "invalid minter")]
+    #[case("someone")]
+    fn test_reply_init(#[case] minter: String) {
+        let (mut deps, _) = deps(vec![], "osmo", None, token_mode_bridged());
+
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, msg } => {
+                assert_eq!(contract_addr, TOKEN);
+                match from_json(msg).unwrap() {
+                    Cw20QueryMsg::Minter {} => {
+                        let res = Some(cw20::MinterResponse {
+                            minter: minter.clone(),
+                            cap: None,
+                        });
+                        SystemResult::Ok(ContractResult::Ok(to_json_binary(&res).unwrap()))
+                    }
+                    _ => unreachable!("unexpected query"),
+                }
+            }
+            _ => unreachable!("unexpected query"),
+        });
+
+        // protobuf encoded MsgInstantiateContractResponse { address: TOKEN }
+        let mut data = vec![0x0a, TOKEN.len() as u8];
+        data.extend_from_slice(TOKEN.as_bytes());
+
+        let res = reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: REPLY_ID_CREATE_DENOM,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: Some(data.into()),
+                }),
+            },
+        )
+        .map_err(|e| e.to_string())
+        .unwrap();
+
+        assert_eq!(res.events[0].ty, "hpl_warp_cw20::reply-init");
+        assert_eq!(
+            super::TOKEN.load(deps.as_ref().storage).unwrap(),
+            addr(TOKEN)
+        );
+    }
+
+    #[rstest]
+    fn test_reply_dispatch() {
+        let (mut deps, _) = deps(vec![], "osmo", Some(TOKEN), token_mode_collateral());
+
+        let message_id = gen_bz(32);
+        let data = to_json_binary(&mailbox::DispatchResponse {
+            message_id: message_id.clone(),
+        })
+        .unwrap();
+
+        // protobuf encoded MsgExecuteContractResponse { data }
+        let mut reply_data = vec![0x0a, data.len() as u8];
+        reply_data.extend_from_slice(&data);
+
+        let res = reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: REPLY_ID_DISPATCH,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: Some(reply_data.into()),
+                }),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            res.events[0].ty,
+            "hpl_warp_cw20::transfer-remote-dispatched"
+        );
+        assert_eq!(res.events[0].attributes[0].key, "message_id");
+        assert_eq!(res.events[0].attributes[0].value, message_id.to_hex());
+    }
+
     #[rstest]
     #[case(MAILBOX, 1, gen_bz(32), token_mode_bridged())]
     #[case(MAILBOX, 1, gen_bz(32), token_mode_collateral())]
     #[should_panic(expected = "unauthorized")]
     #[case(TOKEN, 1, gen_bz(32), token_mode_collateral())]
-    #[should_panic(expected = "route not found")]
+    #[should_panic(expected = "unauthorized")]
     #[case(MAILBOX, 2, gen_bz(32), token_mode_collateral())]
     fn test_mailbox_handle(
         #[values("osmo", "neutron")] hrp: &str,
@@ -502,17 +929,21 @@ mod test {
             ExecuteMsg::Handle(handle_msg),
             vec![],
         );
-        let msg = &res.messages.get(0).unwrap().msg;
+        assert_eq!(
+            res.events[0].attributes[0].value,
+            HexBinary::from(MESSAGE_ID).to_hex()
+        );
+        let msg = &res.messages.first().unwrap().msg;
 
         match token_mode {
             TokenModeMsg::Bridged(_) => {
                 assert_eq!(
                     to_json_binary(msg).unwrap(),
                     to_json_binary(&CosmosMsg::<Empty>::Wasm(
-                        conv::to_mint_msg(
+                        conv::cw20::to_mint_msg(
                             TOKEN,
                             bech32_encode(hrp, warp_msg.recipient.as_slice()).unwrap(),
-                            warp_msg.amount
+                            conv::to_uint128(warp_msg.amount).unwrap()
                         )
                         .unwrap()
                     ))
@@ -523,10 +954,10 @@ mod test {
                 assert_eq!(
                     to_json_binary(msg).unwrap(),
                     to_json_binary(&CosmosMsg::<Empty>::Wasm(
-                        conv::to_send_msg(
+                        conv::cw20::to_send_msg(
                             TOKEN,
                             bech32_encode(hrp, warp_msg.recipient.as_slice()).unwrap(),
-                            warp_msg.amount
+                            conv::to_uint128(warp_msg.amount).unwrap()
                         )
                         .unwrap()
                     ))
@@ -536,6 +967,43 @@ mod test {
         }
     }
 
+    #[rstest]
+    fn test_mailbox_handle_invalid_recipient(token_mode_collateral: Cw20TokenMode) {
+        let route = gen_bz(32);
+        let (mut deps, _) = deps(
+            vec![(1, route.clone())],
+            "",
+            Some(TOKEN),
+            token_mode_collateral,
+        );
+
+        let recipient = gen_bz(32);
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MAILBOX, &[]),
+            ExecuteMsg::Handle(HandleMsg {
+                origin: 1,
+                sender: route,
+                body: warp::Message {
+                    recipient: recipient.clone(),
+                    amount: Uint256::from_u128(100),
+                    metadata: HexBinary::default(),
+                }
+                .into(),
+            }),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err,
+            ContractError::InvalidRecipient {
+                recipient: recipient.to_hex()
+            }
+        );
+    }
+
     #[rstest]
     #[case(1, gen_bz(32), token_mode_bridged(), Some(CUSTOM_HOOK), None)]
     #[case(1, gen_bz(32), token_mode_collateral(), None, Some(gen_bz(100)))]
@@ -607,7 +1075,7 @@ mod test {
                     to_json_binary(&msgs).unwrap(),
                     to_json_binary(&vec![
                         transfer_from_msg.into(),
-                        CosmosMsg::from(conv::to_burn_msg(TOKEN, Uint128::new(100)).unwrap()),
+                        CosmosMsg::from(conv::cw20::to_burn_msg(TOKEN, Uint128::new(100)).unwrap()),
                         dispatch_msg,
                     ])
                     .unwrap(),
@@ -621,4 +1089,687 @@ mod test {
             }
         }
     }
+
+    #[rstest]
+    fn test_embed_nonce(token_mode_collateral: Cw20TokenMode) {
+        let route = gen_bz(32);
+        let (mut deps, _) = deps(
+            vec![(1, route.clone())],
+            "osmo",
+            Some(TOKEN),
+            token_mode_collateral,
+        );
+
+        test_execute(
+            deps.as_mut(),
+            &addr(OWNER),
+            ExecuteMsg::SetEmbedNonce { embed_nonce: true },
+            vec![],
+        );
+
+        mock_quote(&mut deps, vec![]);
+
+        let res = test_execute(
+            deps.as_mut(),
+            &addr("sender"),
+            ExecuteMsg::TransferRemote {
+                dest_domain: 1,
+                recipient: gen_bz(32),
+                amount: Uint128::new(100),
+                hook: None,
+                metadata: None,
+            },
+            vec![],
+        );
+
+        let CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute { msg, .. }) =
+            res.messages.last().unwrap().msg.clone()
+        else {
+            unreachable!("unexpected msg")
+        };
+        let mailbox::ExecuteMsg::Dispatch(dispatch) = from_json(msg).unwrap() else {
+            unreachable!("unexpected msg")
+        };
+        let warp_msg: warp::Message = dispatch.msg_body.clone().into();
+        assert_eq!(warp_msg.nonce(), Some(7));
+
+        // the receiving route surfaces it on handle
+        let res = test_execute(
+            deps.as_mut(),
+            &addr(MAILBOX),
+            ExecuteMsg::Handle(HandleMsg {
+                origin: 1,
+                sender: route,
+                body: dispatch.msg_body,
+            }),
+            vec![],
+        );
+        assert!(res.events[0]
+            .attributes
+            .iter()
+            .any(|v| v.key == "nonce" && v.value == "7"));
+    }
+
+    #[rstest]
+    #[case(token_mode_bridged(), TokenMode::Collateral, TokenMode::Bridged)]
+    #[case(token_mode_collateral(), TokenMode::Bridged, TokenMode::Collateral)]
+    fn test_wrong_mode(
+        #[case] token_mode: Cw20TokenMode,
+        #[case] expected: TokenMode,
+        #[case] got: TokenMode,
+    ) {
+        let (deps, _) = deps(vec![], "osmo", Some(TOKEN), token_mode);
+
+        let storage = deps.as_ref().storage;
+        let (token, recipient) = (addr(TOKEN), addr("recipient"));
+        let err = ContractError::WarpError(WarpError::WrongMode {
+            expected,
+            got: got.clone(),
+        });
+
+        match got {
+            TokenMode::Bridged => {
+                assert_eq!(
+                    release_msg(storage, &token, &recipient, Uint256::one()).unwrap_err(),
+                    err
+                );
+            }
+            TokenMode::Collateral => {
+                assert_eq!(
+                    mint_msg(storage, &token, &recipient, Uint256::one()).unwrap_err(),
+                    err
+                );
+                assert_eq!(burn_msg(storage, &token, Uint128::one()).unwrap_err(), err);
+            }
+        }
+    }
+
+    fn mock_token_decimals(deps: &mut TestDeps, decimals: u8) {
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == MAILBOX => {
+                mailbox_query(msg, &[])
+            }
+            WasmQuery::Smart { msg, .. } => match from_json(msg).unwrap() {
+                Cw20QueryMsg::TokenInfo {} => {
+                    let res = TokenInfoResponse {
+                        name: TOKEN.to_string(),
+                        symbol: TOKEN.to_string(),
+                        decimals,
+                        total_supply: Uint128::zero(),
+                    };
+                    SystemResult::Ok(ContractResult::Ok(to_json_binary(&res).unwrap()))
+                }
+                _ => unreachable!("unexpected query"),
+            },
+            _ => unreachable!("unexpected query"),
+        });
+    }
+
+    fn dispatched_amount(res: &Response) -> Uint256 {
+        let CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) = &res.messages.last().unwrap().msg
+        else {
+            unreachable!("unexpected msg")
+        };
+        let mailbox::ExecuteMsg::Dispatch(dispatch) = from_json(msg).unwrap() else {
+            unreachable!("unexpected msg")
+        };
+
+        warp::Message::from(dispatch.msg_body).amount
+    }
+
+    #[rstest]
+    #[case(OWNER, 18)]
+    #[should_panic(expected = "unauthorized")]
+    #[case(DEPLOYER, 18)]
+    #[should_panic(expected = "invalid decimals: 39. max: 38")]
+    #[case(OWNER, 39)]
+    fn test_set_remote_decimals(#[case] sender: &str, #[case] decimals: u8) {
+        let (mut deps, _) = deps(vec![], "osmo", Some(TOKEN), token_mode_collateral());
+
+        let res = test_execute(
+            deps.as_mut(),
+            &addr(sender),
+            ExecuteMsg::SetRemoteDecimals {
+                dest_domain: 1,
+                decimals,
+            },
+            vec![],
+        );
+
+        assert_eq!(res.events[0].ty, "hpl_warp_cw20::set-remote-decimals");
+        assert_eq!(
+            super::REMOTE_DECIMALS
+                .load(deps.as_ref().storage, 1)
+                .unwrap(),
+            decimals
+        );
+    }
+
+    #[rstest]
+    fn test_remote_decimals_scaling() {
+        let route = gen_bz(32);
+        let (mut deps, _) = deps(
+            vec![(1, route.clone())],
+            "osmo",
+            Some(TOKEN),
+            token_mode_collateral(),
+        );
+        mock_token_decimals(&mut deps, 6);
+
+        let transfer_remote = ExecuteMsg::TransferRemote {
+            dest_domain: 1,
+            recipient: gen_bz(32),
+            amount: Uint128::new(100),
+            hook: None,
+            metadata: None,
+        };
+
+        // no scaling by default
+        let res = test_execute(
+            deps.as_mut(),
+            &addr("sender"),
+            transfer_remote.clone(),
+            vec![],
+        );
+        assert_eq!(dispatched_amount(&res), Uint256::from_u128(100));
+
+        for (decimals, expected) in [(18, 100 * 10u128.pow(12)), (9, 100 * 10u128.pow(3))] {
+            test_execute(
+                deps.as_mut(),
+                &addr(OWNER),
+                ExecuteMsg::SetRemoteDecimals {
+                    dest_domain: 1,
+                    decimals,
+                },
+                vec![],
+            );
+
+            let res = test_execute(
+                deps.as_mut(),
+                &addr("sender"),
+                transfer_remote.clone(),
+                vec![],
+            );
+            assert_eq!(dispatched_amount(&res), Uint256::from_u128(expected));
+        }
+
+        // inbound amounts are scaled back with the latest remote decimals
+        let recipient = gen_bz(32);
+        let res = test_execute(
+            deps.as_mut(),
+            &addr(MAILBOX),
+            ExecuteMsg::Handle(HandleMsg {
+                origin: 1,
+                sender: route,
+                body: warp::Message {
+                    recipient: recipient.clone(),
+                    amount: Uint256::from_u128(5 * 10u128.pow(9)),
+                    metadata: HexBinary::default(),
+                }
+                .into(),
+            }),
+            vec![],
+        );
+
+        assert_eq!(
+            to_json_binary(&res.messages[0].msg).unwrap(),
+            to_json_binary(&CosmosMsg::<Empty>::Wasm(
+                conv::cw20::to_send_msg(
+                    TOKEN,
+                    bech32_encode("osmo", recipient.as_slice()).unwrap(),
+                    Uint128::new(5 * 10u128.pow(6))
+                )
+                .unwrap()
+            ))
+            .unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case(1_000, 1)]
+    #[should_panic(expected = "amount 1001 can't be bridged exactly with 3 remote decimals")]
+    #[case(1_001, 0)]
+    #[should_panic(expected = "amount 999 can't be bridged exactly with 3 remote decimals")]
+    #[case(999, 0)]
+    fn test_remote_decimals_inexact(#[case] amount: u128, #[case] expected: u128) {
+        let (mut deps, _) = deps(
+            vec![(1, gen_bz(32))],
+            "osmo",
+            Some(TOKEN),
+            token_mode_collateral(),
+        );
+        mock_token_decimals(&mut deps, 6);
+
+        test_execute(
+            deps.as_mut(),
+            &addr(OWNER),
+            ExecuteMsg::SetRemoteDecimals {
+                dest_domain: 1,
+                decimals: 3,
+            },
+            vec![],
+        );
+
+        let res = test_execute(
+            deps.as_mut(),
+            &addr("sender"),
+            ExecuteMsg::TransferRemote {
+                dest_domain: 1,
+                recipient: gen_bz(32),
+                amount: Uint128::new(amount),
+                hook: None,
+                metadata: None,
+            },
+            vec![],
+        );
+        assert_eq!(dispatched_amount(&res), Uint256::from_u128(expected));
+    }
+
+    #[rstest]
+    fn test_transfer_remote_event_amounts() {
+        let (mut deps, _) = deps(
+            vec![(1, gen_bz(32))],
+            "osmo",
+            Some(TOKEN),
+            token_mode_collateral(),
+        );
+        mock_token_decimals(&mut deps, 6);
+
+        test_execute(
+            deps.as_mut(),
+            &addr(OWNER),
+            ExecuteMsg::SetRemoteDecimals {
+                dest_domain: 1,
+                decimals: 18,
+            },
+            vec![],
+        );
+
+        let res = test_execute(
+            deps.as_mut(),
+            &addr("sender"),
+            ExecuteMsg::TransferRemote {
+                dest_domain: 1,
+                recipient: gen_bz(32),
+                amount: Uint128::new(1_500_000),
+                hook: None,
+                metadata: None,
+            },
+            vec![],
+        );
+
+        let event = &res.events[0];
+        assert_eq!(event.ty, "hpl_warp_cw20::transfer-remote");
+
+        let attr = |key: &str| {
+            event
+                .attributes
+                .iter()
+                .find(|v| v.key == key)
+                .map(|v| v.value.clone())
+                .unwrap()
+        };
+        assert_eq!(attr("amount"), "1500000");
+        assert_eq!(attr("remote_amount"), "1500000000000000000");
+    }
+
+    #[rstest]
+    #[case(EventVerbosity::Minimal, vec!["message_id"], vec![])]
+    #[case(
+        EventVerbosity::Standard,
+        vec!["message_id", "recipient", "token", "amount"],
+        vec!["sender", "amount", "dest_domain", "recipient", "token", "remote_amount", "hook", "metadata"]
+    )]
+    #[case(
+        EventVerbosity::Verbose,
+        vec!["message_id", "recipient", "token", "amount", "origin", "origin_sender"],
+        vec!["sender", "amount", "dest_domain", "recipient", "token", "remote_amount", "hook", "metadata", "dest_router"]
+    )]
+    fn test_event_verbosity(
+        #[case] verbosity: EventVerbosity,
+        #[case] handle_keys: Vec<&str>,
+        #[case] transfer_keys: Vec<&str>,
+    ) {
+        let route = gen_bz(32);
+        let (mut deps, _) = deps(
+            vec![(1, route.clone())],
+            "osmo",
+            Some(TOKEN),
+            token_mode_collateral(),
+        );
+        let keys = |res: &Response| {
+            res.events
+                .iter()
+                .flat_map(|v| v.attributes.iter().map(|v| v.key.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        test_execute(
+            deps.as_mut(),
+            &addr(OWNER),
+            ExecuteMsg::SetEventVerbosity { verbosity },
+            vec![],
+        );
+
+        let res = test_execute(
+            deps.as_mut(),
+            &addr(MAILBOX),
+            ExecuteMsg::Handle(HandleMsg {
+                origin: 1,
+                sender: route,
+                body: warp::Message {
+                    recipient: gen_bz(32),
+                    amount: Uint256::from_u128(100),
+                    metadata: HexBinary::default(),
+                }
+                .into(),
+            }),
+            vec![],
+        );
+        assert_eq!(keys(&res), handle_keys);
+        assert_eq!(
+            res.events[0].attributes[0].value,
+            HexBinary::from(MESSAGE_ID).to_hex()
+        );
+
+        let res = test_execute(
+            deps.as_mut(),
+            &addr("sender"),
+            ExecuteMsg::TransferRemote {
+                dest_domain: 1,
+                recipient: gen_bz(32),
+                amount: Uint128::new(100),
+                hook: None,
+                metadata: None,
+            },
+            vec![],
+        );
+        assert_eq!(keys(&res), transfer_keys);
+    }
+
+    #[rstest]
+    fn test_handle_without_process_id() {
+        let route = gen_bz(32);
+        let (mut deps, _) = deps(
+            vec![(1, route.clone())],
+            "osmo",
+            Some(TOKEN),
+            token_mode_collateral(),
+        );
+
+        // a mailbox that predates the LatestProcessId query
+        deps.querier
+            .update_wasm(|_| SystemResult::Ok(ContractResult::Err("unknown variant".to_string())));
+
+        let res = test_execute(
+            deps.as_mut(),
+            &addr(MAILBOX),
+            ExecuteMsg::Handle(HandleMsg {
+                origin: 1,
+                sender: route,
+                body: warp::Message {
+                    recipient: gen_bz(32),
+                    amount: Uint256::from_u128(100),
+                    metadata: HexBinary::default(),
+                }
+                .into(),
+            }),
+            vec![],
+        );
+        assert!(!res.events[0]
+            .attributes
+            .iter()
+            .any(|v| v.key == "message_id"));
+    }
+
+    #[rstest]
+    #[case(0, vec![coin(30, "uatom")], vec![coin(70, "uatom")])]
+    #[case(71, vec![coin(100, "uatom")], vec![])]
+    fn test_transfer_remote_min_refund(
+        #[case] min_refund: u128,
+        #[case] forwarded: Vec<Coin>,
+        #[case] refunded: Vec<Coin>,
+    ) {
+        let (mut deps, _) = deps(
+            vec![(1, gen_bz(32))],
+            "osmo",
+            Some(TOKEN),
+            token_mode_collateral(),
+        );
+
+        test_execute(
+            deps.as_mut(),
+            &addr(OWNER),
+            ExecuteMsg::SetMinRefund {
+                min_refund: Uint128::new(min_refund),
+            },
+            vec![],
+        );
+
+        mock_quote(&mut deps, vec![coin(30, "uatom")]);
+
+        let res = test_execute(
+            deps.as_mut(),
+            &addr("sender"),
+            ExecuteMsg::TransferRemote {
+                dest_domain: 1,
+                recipient: gen_bz(32),
+                amount: Uint128::new(100),
+                hook: None,
+                metadata: None,
+            },
+            vec![coin(100, "uatom")],
+        );
+        let mut msgs: Vec<_> = res.messages.into_iter().map(|v| v.msg).collect();
+
+        if !refunded.is_empty() {
+            assert_eq!(
+                msgs.pop().unwrap(),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "sender".to_string(),
+                    amount: refunded,
+                })
+            );
+        }
+
+        let CosmosMsg::Wasm(WasmMsg::Execute { funds, .. }) = msgs.pop().unwrap() else {
+            unreachable!("unexpected msg")
+        };
+        assert_eq!(funds, forwarded);
+    }
+
+    #[rstest]
+    fn test_collateral_denom() {
+        let (collateral, _) = deps(vec![], "osmo", Some(TOKEN), token_mode_collateral());
+
+        let res: warp::CollateralDenomResponse = test_query(
+            collateral.as_ref(),
+            QueryMsg::TokenDefault(warp::TokenWarpDefaultQueryMsg::CollateralDenom {}),
+        );
+        assert_eq!(res.denom, TOKEN);
+
+        let (bridged, _) = deps(vec![], "osmo", Some(TOKEN), token_mode_bridged());
+
+        let err = query(
+            bridged.as_ref(),
+            mock_env(),
+            QueryMsg::TokenDefault(warp::TokenWarpDefaultQueryMsg::CollateralDenom {}),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::WarpError(WarpError::WrongMode {
+                expected: TokenMode::Collateral,
+                got: TokenMode::Bridged,
+            })
+        );
+    }
+
+    #[rstest]
+    #[case("osmo", "osmo1d6a3j0kkpc8eac0j8h6ypyevfz8hd3qnuqjrc5")]
+    #[case("neutron", "neutron1d6a3j0kkpc8eac0j8h6ypyevfz8hd3qnsyg35p")]
+    fn test_preview_recipient(#[case] hrp: &str, #[case] expected: &str) {
+        let (deps, _) = deps(vec![], hrp, Some(TOKEN), token_mode_collateral());
+
+        let res: warp::PreviewRecipientResponse = test_query(
+            deps.as_ref(),
+            QueryMsg::TokenDefault(warp::TokenWarpDefaultQueryMsg::PreviewRecipient {
+                recipient: HexBinary::from_hex(
+                    "0000000000000000000000006ebb193ed60e0f9ee1f23df440932c488f76c413",
+                )
+                .unwrap(),
+            }),
+        );
+        assert_eq!(res.recipient, expected);
+    }
+
+    #[rstest]
+    #[case(vec![], 0, true)]
+    #[case(vec![], 1, false)]
+    #[case(vec![1, 2], 1, true)]
+    #[case(vec![1, 2], 2, true)]
+    #[case(vec![1, 2], 0, false)]
+    #[case(vec![1, 2], 3, false)]
+    fn test_allowed_senders(
+        #[case] allowed: Vec<usize>,
+        #[case] sender: usize,
+        #[case] accepted: bool,
+    ) {
+        let senders = (0..4).map(|_| gen_bz(32)).collect::<Vec<_>>();
+        let (mut deps, _) = deps(
+            vec![(1, senders[0].clone())],
+            "osmo",
+            Some(TOKEN),
+            token_mode_collateral(),
+        );
+
+        if !allowed.is_empty() {
+            test_execute(
+                deps.as_mut(),
+                &addr(OWNER),
+                ExecuteMsg::SetAllowedSenders {
+                    domain: 1,
+                    senders: allowed.iter().map(|i| senders[*i].clone()).collect(),
+                },
+                vec![],
+            );
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MAILBOX, &[]),
+            ExecuteMsg::Handle(HandleMsg {
+                origin: 1,
+                sender: senders[sender].clone(),
+                body: warp::Message {
+                    recipient: gen_bz(32),
+                    amount: Uint256::from_u128(100),
+                    metadata: HexBinary::default(),
+                }
+                .into(),
+            }),
+        );
+
+        match accepted {
+            true => assert!(res.is_ok()),
+            false => assert_eq!(res.unwrap_err(), ContractError::Unauthorized),
+        }
+    }
+
+    #[rstest]
+    #[should_panic(expected = "unauthorized")]
+    fn test_set_allowed_senders_unauthorized() {
+        let (mut deps, _) = deps(vec![], "osmo", Some(TOKEN), token_mode_collateral());
+
+        test_execute(
+            deps.as_mut(),
+            &addr(DEPLOYER),
+            ExecuteMsg::SetAllowedSenders {
+                domain: 1,
+                senders: vec![gen_bz(32)],
+            },
+            vec![],
+        );
+    }
+
+    #[rstest]
+    #[case(token_mode_bridged())]
+    #[case(token_mode_collateral())]
+    fn test_outstanding_cap(#[case] token_mode: Cw20TokenMode) {
+        let route = gen_bz(32);
+        let (mut deps, _) = deps(vec![(1, route.clone())], "osmo", Some(TOKEN), token_mode);
+
+        test_execute(
+            deps.as_mut(),
+            &addr(OWNER),
+            ExecuteMsg::SetOutstandingCap {
+                cap: Some(Uint256::from_u128(100)),
+            },
+            vec![],
+        );
+
+        let transfer = |deps: &mut TestDeps, amount: u128| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("sender", &[]),
+                ExecuteMsg::TransferRemote {
+                    dest_domain: 1,
+                    recipient: gen_bz(32),
+                    amount: Uint128::new(amount),
+                    hook: None,
+                    metadata: None,
+                },
+            )
+        };
+        let outstanding = |deps: &TestDeps| -> warp::OutstandingResponse {
+            test_query(
+                deps.as_ref(),
+                QueryMsg::TokenDefault(warp::TokenWarpDefaultQueryMsg::Outstanding {}),
+            )
+        };
+
+        // bridge up to the cap
+        transfer(&mut deps, 60).unwrap();
+        transfer(&mut deps, 40).unwrap();
+        assert_eq!(
+            outstanding(&deps),
+            warp::OutstandingResponse {
+                outstanding: Uint256::from_u128(100),
+                cap: Some(Uint256::from_u128(100)),
+            }
+        );
+
+        // over the cap
+        assert_eq!(
+            transfer(&mut deps, 1).unwrap_err(),
+            ContractError::WarpError(WarpError::OutstandingCapExceeded {
+                cap: Uint256::from_u128(100),
+                outstanding: Uint256::from_u128(100),
+                amount: Uint256::from_u128(1),
+            })
+        );
+
+        // bridging back in frees capacity
+        test_execute(
+            deps.as_mut(),
+            &addr(MAILBOX),
+            ExecuteMsg::Handle(HandleMsg {
+                origin: 1,
+                sender: route,
+                body: warp::Message {
+                    recipient: gen_bz(32),
+                    amount: Uint256::from_u128(30),
+                    metadata: HexBinary::default(),
+                }
+                .into(),
+            }),
+            vec![],
+        );
+        assert_eq!(outstanding(&deps).outstanding, Uint256::from_u128(70));
+
+        transfer(&mut deps, 30).unwrap();
+        assert_eq!(outstanding(&deps).outstanding, Uint256::from_u128(100));
+    }
 }
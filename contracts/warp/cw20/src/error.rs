@@ -12,6 +12,12 @@ pub enum ContractError {
     #[error("{0}")]
     MigrationError(#[from] hpl_utils::MigrationError),
 
+    #[error("{0}")]
+    RouterError(#[from] hpl_router::RouterError),
+
+    #[error("{0}")]
+    WarpError(#[from] hpl_warp::WarpError),
+
     #[error("unauthorized")]
     Unauthorized,
 
@@ -29,4 +35,19 @@ pub enum ContractError {
 
     #[error("no router for domain {domain:?}")]
     NoRouter { domain: u32 },
+
+    #[error("invalid decimals: {decimals}. max: {max}")]
+    InvalidDecimals { decimals: u8, max: u8 },
+
+    #[error("code id not allowed: {code_id}")]
+    CodeIdNotAllowed { code_id: u64 },
+
+    #[error("invalid minter. expected: {expected}, got: {got:?}")]
+    InvalidMinter {
+        expected: String,
+        got: Option<String>,
+    },
+
+    #[error("invalid recipient: {recipient}")]
+    InvalidRecipient { recipient: String },
 }
@@ -1,13 +1,15 @@
-use cosmwasm_std::{Addr, Event};
-use cw_storage_plus::Item;
-use hpl_interface::warp::TokenMode;
+use cosmwasm_std::{Addr, Event, HexBinary};
+use cw_storage_plus::{Item, Map};
 
 pub mod contract;
-mod conv;
 pub mod error;
 
+// upper bound of the decimals a token can have. keeps 10^decimals within Uint128
+pub const MAX_DECIMALS: u8 = 38;
+
 // reply message
 pub const REPLY_ID_CREATE_DENOM: u64 = 0;
+pub const REPLY_ID_DISPATCH: u64 = 1;
 
 // version info for migration info
 pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
@@ -17,10 +19,6 @@ pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 const TOKEN_KEY: &str = "token";
 const TOKEN: Item<Addr> = Item::new(TOKEN_KEY);
 
-// storage definition for token mode
-const MODE_KEY: &str = "mode";
-const MODE: Item<TokenMode> = Item::new(MODE_KEY);
-
 // storage definition for token hrp
 const HRP_KEY: &str = "hrp";
 const HRP: Item<String> = Item::new(HRP_KEY);
@@ -29,6 +27,10 @@ const HRP: Item<String> = Item::new(HRP_KEY);
 const MAILBOX_KEY: &str = "mailbox";
 const MAILBOX: Item<Addr> = Item::new(MAILBOX_KEY);
 
+// storage definition for accepted origin senders per domain
+const ALLOWED_SENDERS_PREFIX: &str = "allowed_senders";
+const ALLOWED_SENDERS: Map<u32, Vec<HexBinary>> = Map::new(ALLOWED_SENDERS_PREFIX);
+
 fn new_event(name: &str) -> Event {
     Event::new(format!("hpl_warp_cw20::{name}"))
 }
@@ -1,8 +1,9 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure, ensure_eq, to_json_binary, CosmosMsg, Deps, DepsMut, Empty, Env, HexBinary,
-    MessageInfo, QueryResponse, Reply, Response, StdError, SubMsg, Uint128, Uint256,
+    ensure, ensure_eq, from_json, to_json_binary, Addr, BankMsg, CosmosMsg, Deps, DepsMut, Empty,
+    Env, HexBinary, MessageInfo, QueryResponse, Reply, Response, StdError, Storage, SubMsg,
+    Uint128, Uint256,
 };
 use hpl_connection::{get_hook, get_ism};
 use hpl_interface::{
@@ -15,15 +16,22 @@ use hpl_interface::{
         native::{ExecuteMsg, InstantiateMsg, QueryMsg},
     },
     warp::{TokenMode, TokenModeMsg, TokenModeResponse, TokenTypeResponse},
+    EventVerbosity,
 };
 use hpl_router::get_route;
+use hpl_warp::{
+    admin, conv,
+    proto::{self, MsgCreateDenom, MsgCreateDenomResponse},
+    split_gas,
+    state::{
+        ensure_mode, record_inbound, record_outbound, EMBED_NONCE, EVENT_VERBOSITY, MODE,
+        OUTSTANDING, OUTSTANDING_CAP,
+    },
+};
 
 use crate::{
-    conv,
-    error::ContractError,
-    new_event,
-    proto::{MsgCreateDenom, MsgCreateDenomResponse},
-    CONTRACT_NAME, CONTRACT_VERSION, HRP, MAILBOX, MODE, REPLY_ID_CREATE_DENOM, TOKEN,
+    error::ContractError, new_event, CONTRACT_NAME, CONTRACT_VERSION, HRP, MAILBOX,
+    REPLY_ID_CREATE_DENOM, REPLY_ID_DISPATCH, TOKEN,
 };
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -58,7 +66,7 @@ pub fn instantiate(
             ));
 
             if let Some(metadata) = token.metadata {
-                msgs.push(SubMsg::new(conv::to_set_metadata_msg(
+                msgs.push(SubMsg::new(conv::native::to_set_metadata_msg(
                     &env.contract.address,
                     metadata,
                 )));
@@ -115,6 +123,16 @@ pub fn execute(
             hook,
             metadata,
         ),
+        SetMinRefund { min_refund } => {
+            Ok(admin::set_min_refund(deps, info, new_event, min_refund)?)
+        }
+        SetEmbedNonce { embed_nonce } => {
+            Ok(admin::set_embed_nonce(deps, info, new_event, embed_nonce)?)
+        }
+        SetOutstandingCap { cap } => Ok(admin::set_outstanding_cap(deps, info, new_event, cap)?),
+        SetEventVerbosity { verbosity } => Ok(admin::set_event_verbosity(
+            deps, info, new_event, verbosity,
+        )?),
     }
 }
 
@@ -139,6 +157,22 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
             Ok(resp)
         }
 
+        REPLY_ID_DISPATCH => {
+            let exec_resp = cw_utils::parse_execute_response_data(&reply_data)?;
+            let dispatch: mailbox::DispatchResponse = from_json(
+                exec_resp
+                    .data
+                    .ok_or(StdError::generic_err("no dispatch data"))?,
+            )?;
+
+            let resp = Response::new().add_event(
+                new_event("transfer-remote-dispatched")
+                    .add_attribute("message_id", dispatch.message_id.to_hex()),
+            );
+
+            Ok(resp)
+        }
+
         _ => Err(ContractError::InvalidReplyId),
     }
 }
@@ -165,33 +199,66 @@ fn mailbox_handle(
     );
 
     let token_msg: warp::Message = msg.body.into();
-    let recipient = bech32_encode(&HRP.load(deps.storage)?, &token_msg.recipient)?;
+    let recipient =
+        bech32_encode(&HRP.load(deps.storage)?, &token_msg.recipient).map_err(|_| {
+            ContractError::InvalidRecipient {
+                recipient: token_msg.recipient.to_hex(),
+            }
+        })?;
 
     let token = TOKEN.load(deps.storage)?;
     let mode = MODE.load(deps.storage)?;
 
+    record_inbound(deps.storage, token_msg.amount)?;
+
     let mut msgs: Vec<CosmosMsg> = vec![];
 
     if mode == TokenMode::Bridged {
         // push token mint msg if token is bridged
-        msgs.push(conv::to_mint_msg(&env.contract.address, &token, token_msg.amount).into());
+        msgs.push(
+            mint_msg(
+                deps.storage,
+                &env.contract.address,
+                &token,
+                token_msg.amount,
+            )?
+            .into(),
+        );
     }
 
     // push token send msg
     msgs.push(
-        conv::to_send_msg(
+        conv::native::to_send_msg(
             &recipient,
-            vec![conv::to_coin_u256(token_msg.amount, &token)?],
+            vec![conv::native::to_coin_u256(token_msg.amount, &token)?],
         )
         .into(),
     );
 
-    Ok(Response::new().add_messages(msgs).add_event(
-        new_event("handle")
+    let verbosity = EVENT_VERBOSITY.may_load(deps.storage)?.unwrap_or_default();
+
+    let mut event = new_event("handle");
+    // recorded by the mailbox right before it hands the message over.
+    // mailboxes without the LatestProcessId query leave it out
+    if let Ok(message_id) = mailbox::latest_process_id(&deps.querier, &info.sender) {
+        event = event.add_attribute("message_id", message_id.to_hex());
+    }
+    if verbosity >= EventVerbosity::Standard {
+        event = event
             .add_attribute("recipient", recipient)
             .add_attribute("token", token)
-            .add_attribute("amount", token_msg.amount),
-    ))
+            .add_attribute("amount", token_msg.amount);
+        if let Some(nonce) = token_msg.nonce() {
+            event = event.add_attribute("nonce", nonce.to_string());
+        }
+    }
+    if verbosity >= EventVerbosity::Verbose {
+        event = event
+            .add_attribute("origin", msg.origin.to_string())
+            .add_attribute("origin_sender", msg.sender.to_hex());
+    }
+
+    Ok(Response::new().add_messages(msgs).add_event(event))
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -223,6 +290,8 @@ fn transfer_remote(
 
     funds[token_index].amount -= transfer_amount;
 
+    record_outbound(deps.storage, Uint256::from_uint128(transfer_amount))?;
+
     let dest_router = get_route::<HexBinary>(deps.storage, dest_domain)?
         .route
         .expect("route not found");
@@ -236,35 +305,93 @@ fn transfer_remote(
 
     if mode == TokenMode::Bridged {
         // push token burn msg if token is bridged
-        msgs.push(conv::to_burn_msg(&env.contract.address, &token, transfer_amount).into());
+        msgs.push(burn_msg(deps.storage, &env.contract.address, &token, transfer_amount)?.into());
     }
 
-    let dispatch_payload = warp::Message {
+    let mut dispatch_payload = warp::Message {
         recipient: recipient.clone(),
         amount: Uint256::from_uint128(transfer_amount),
         metadata: HexBinary::default(),
     };
+    if EMBED_NONCE.may_load(deps.storage)?.unwrap_or_default() {
+        dispatch_payload = dispatch_payload.with_nonce(mailbox::nonce(&deps.querier, &mailbox)?);
+    }
 
-    // push mailbox dispatch msg
-    msgs.push(mailbox::dispatch(
-        mailbox,
+    let dispatch_msg = mailbox::DispatchMsg {
         dest_domain,
-        dest_router,
-        dispatch_payload.into(),
-        hook.clone().or(get_hook(deps.storage)?.map(|v| v.into())),
-        metadata.clone(),
-        funds,
-    )?);
-
-    Ok(Response::new().add_messages(msgs).add_event(
-        new_event("transfer-remote")
+        recipient_addr: dest_router.clone(),
+        msg_body: dispatch_payload.into(),
+        hook: hook.clone().or(get_hook(deps.storage)?.map(|v| v.into())),
+        metadata: metadata.clone(),
+    };
+
+    let (funds, refund) = split_gas(deps.as_ref(), &env, &mailbox, &dispatch_msg, funds)?;
+
+    // push mailbox dispatch msg. the reply picks up the id the mailbox assigns to it
+    let dispatch = SubMsg::reply_on_success(
+        mailbox::dispatch(
+            mailbox,
+            dispatch_msg.dest_domain,
+            dispatch_msg.recipient_addr,
+            dispatch_msg.msg_body,
+            dispatch_msg.hook,
+            dispatch_msg.metadata,
+            funds,
+        )?,
+        REPLY_ID_DISPATCH,
+    );
+    let mut resp = Response::new().add_messages(msgs).add_submessage(dispatch);
+
+    // refund gas overpayment
+    if !refund.is_empty() {
+        resp = resp.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: refund,
+        });
+    }
+
+    let verbosity = EVENT_VERBOSITY.may_load(deps.storage)?.unwrap_or_default();
+
+    // at Minimal only the id is emitted, by the transfer-remote-dispatched event of the reply
+    if verbosity >= EventVerbosity::Standard {
+        let mut event = new_event("transfer-remote")
             .add_attribute("sender", info.sender)
+            .add_attribute("amount", transfer_amount)
             .add_attribute("recipient", recipient.to_hex())
             .add_attribute("token", token)
-            .add_attribute("amount", transfer_amount.to_string())
             .add_attribute("hook", hook.unwrap_or_default())
-            .add_attribute("metadata", metadata.unwrap_or_default().to_string()),
-    ))
+            .add_attribute("metadata", metadata.unwrap_or_default().to_string());
+        if verbosity >= EventVerbosity::Verbose {
+            event = event
+                .add_attribute("dest_domain", dest_domain.to_string())
+                .add_attribute("dest_router", dest_router.to_hex());
+        }
+        resp = resp.add_event(event);
+    }
+
+    Ok(resp)
+}
+
+fn mint_msg(
+    storage: &dyn Storage,
+    sender: &Addr,
+    denom: &str,
+    amount: impl ToString,
+) -> Result<proto::MsgMint, ContractError> {
+    ensure_mode(storage, TokenMode::Bridged)?;
+
+    Ok(conv::native::to_mint_msg(sender, denom, amount))
+}
+
+fn burn_msg(
+    storage: &dyn Storage,
+    sender: &Addr,
+    denom: &str,
+    amount: impl ToString,
+) -> Result<proto::MsgBurn, ContractError> {
+    ensure_mode(storage, TokenMode::Bridged)?;
+
+    Ok(conv::native::to_burn_msg(sender, denom, amount))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -278,6 +405,9 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, Contr
         QueryMsg::TokenDefault(msg) => match msg {
             TokenType {} => to_binary(get_token_type(deps)),
             TokenMode {} => to_binary(get_token_mode(deps)),
+            PreviewRecipient { recipient } => to_binary(preview_recipient(deps, recipient)),
+            CollateralDenom {} => to_binary(get_collateral_denom(deps)),
+            Outstanding {} => to_binary(get_outstanding(deps)),
         },
         QueryMsg::IsmSpecifier(IsmSpecifierQueryMsg::InterchainSecurityModule()) => {
             Ok(to_json_binary(&InterchainSecurityModuleResponse {
@@ -301,6 +431,32 @@ fn get_token_mode(deps: Deps) -> Result<TokenModeResponse, ContractError> {
     Ok(TokenModeResponse { mode })
 }
 
+fn get_collateral_denom(deps: Deps) -> Result<warp::CollateralDenomResponse, ContractError> {
+    ensure_mode(deps.storage, TokenMode::Collateral)?;
+
+    Ok(warp::CollateralDenomResponse {
+        denom: TOKEN.load(deps.storage)?.to_string(),
+    })
+}
+
+fn get_outstanding(deps: Deps) -> Result<warp::OutstandingResponse, ContractError> {
+    Ok(warp::OutstandingResponse {
+        outstanding: OUTSTANDING.may_load(deps.storage)?.unwrap_or_default(),
+        cap: OUTSTANDING_CAP.may_load(deps.storage)?.flatten(),
+    })
+}
+
+fn preview_recipient(
+    deps: Deps,
+    recipient: HexBinary,
+) -> Result<warp::PreviewRecipientResponse, ContractError> {
+    let recipient = bech32_encode(&HRP.load(deps.storage)?, &recipient)?;
+
+    Ok(warp::PreviewRecipientResponse {
+        recipient: recipient.into(),
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
     hpl_utils::migrate(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
@@ -310,17 +466,20 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, Contra
 #[cfg(test)]
 mod test {
     use cosmwasm_std::{
-        coin,
+        coin, from_json,
         testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
-        Coin, OwnedDeps, Uint128,
+        Binary, Coin, ContractResult, OwnedDeps, QuerierResult, SubMsgResponse, SubMsgResult,
+        SystemResult, Uint128, WasmQuery,
     };
     use hpl_interface::{
         build_test_executor, build_test_querier,
         core::HandleMsg,
+        hook::QuoteDispatchResponse,
         router::DomainRouteSet,
         warp::native::{Metadata, NativeModeBriged, NativeModeCollateral},
     };
     use hpl_router::set_route;
+    use hpl_warp::WarpError;
     use ibcx_test_utils::{addr, gen_bz};
     use rstest::{fixture, rstest};
 
@@ -389,6 +548,8 @@ mod test {
         )
         .unwrap();
 
+        mock_quote(&mut deps, vec![]);
+
         deps
     }
 
@@ -513,7 +674,7 @@ mod test {
 
         assert_eq!(
             msgs.pop().unwrap(),
-            conv::to_send_msg(
+            conv::native::to_send_msg(
                 &bech32_encode("osmo", recipient.as_slice()).unwrap(),
                 vec![coin(100, DENOM)]
             )
@@ -523,13 +684,87 @@ mod test {
         if mode == TokenMode::Bridged {
             assert_eq!(
                 msgs.pop().unwrap(),
-                conv::to_mint_msg(&mock_env().contract.address, DENOM, "100").into()
+                conv::native::to_mint_msg(&mock_env().contract.address, DENOM, "100").into()
             );
         } else {
             assert!(msgs.is_empty());
         }
     }
 
+    #[rstest]
+    fn test_mailbox_handle_invalid_recipient(
+        #[with(token_mode_collateral(), "")] mut deps: TestDeps,
+    ) {
+        let route = gen_bz(32);
+        let recipient = gen_bz(32);
+
+        set_route(
+            deps.as_mut().storage,
+            &addr(OWNER),
+            DomainRouteSet {
+                domain: 1,
+                route: Some(route.clone()),
+            },
+        )
+        .unwrap();
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MAILBOX, &[]),
+            ExecuteMsg::Handle(HandleMsg {
+                origin: 1,
+                sender: route,
+                body: warp::Message {
+                    recipient: recipient.clone(),
+                    amount: Uint256::from_u128(100),
+                    metadata: HexBinary::default(),
+                }
+                .into(),
+            }),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err,
+            ContractError::InvalidRecipient {
+                recipient: recipient.to_hex()
+            }
+        );
+    }
+
+    const MESSAGE_ID: [u8; 32] = [0xab; 32];
+
+    fn mailbox_query(msg: &Binary, fees: &[Coin]) -> QuerierResult {
+        let res = match from_json(msg).unwrap() {
+            mailbox::QueryMsg::Hook(mailbox::MailboxHookQueryMsg::QuoteDispatch { .. }) => {
+                to_json_binary(&QuoteDispatchResponse {
+                    fees: fees.to_vec(),
+                })
+            }
+            mailbox::QueryMsg::Mailbox(mailbox::MailboxQueryMsg::Nonce {}) => {
+                to_json_binary(&mailbox::NonceResponse { nonce: 7 })
+            }
+            mailbox::QueryMsg::Mailbox(mailbox::MailboxQueryMsg::LatestProcessId {}) => {
+                to_json_binary(&mailbox::LatestProcessedIdResponse {
+                    message_id: MESSAGE_ID.into(),
+                })
+            }
+            _ => unreachable!("unexpected query"),
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    }
+
+    fn mock_quote(deps: &mut TestDeps, fees: Vec<Coin>) {
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, msg } => {
+                assert_eq!(contract_addr, MAILBOX);
+                mailbox_query(msg, &fees)
+            }
+            _ => unreachable!("unexpected query"),
+        });
+    }
+
     #[rstest]
     #[case(1, gen_bz(32), gen_bz(32), vec![coin(100, DENOM)], Some(CUSTOM_HOOK), None)]
     #[case(1, gen_bz(32), gen_bz(32), vec![coin(100, DENOM), coin(100, "uatom")], None, Some(gen_bz(100)))]
@@ -558,6 +793,16 @@ mod test {
         )
         .unwrap();
 
+        // quoted exactly what's forwarded, so nothing is refunded
+        mock_quote(
+            &mut deps,
+            [
+                vec![coin(50, DENOM)],
+                funds.iter().filter(|v| v.denom != DENOM).cloned().collect(),
+            ]
+            .concat(),
+        );
+
         let res = test_execute(
             deps.as_mut(),
             &addr("sender"),
@@ -601,10 +846,429 @@ mod test {
         if mode == TokenMode::Bridged {
             assert_eq!(
                 msgs.pop().unwrap(),
-                conv::to_burn_msg(&mock_env().contract.address, DENOM, "100").into()
+                conv::native::to_burn_msg(&mock_env().contract.address, DENOM, "100").into()
             );
         } else {
             assert!(msgs.is_empty());
         }
     }
+
+    #[rstest]
+    #[case(0, vec![coin(50, DENOM), coin(30, "uatom")], vec![coin(70, "uatom")])]
+    #[case(70, vec![coin(50, DENOM), coin(30, "uatom")], vec![coin(70, "uatom")])]
+    #[case(71, vec![coin(50, DENOM), coin(100, "uatom")], vec![])]
+    fn test_transfer_remote_min_refund(
+        mut deps: TestDeps,
+        #[case] min_refund: u128,
+        #[case] forwarded: Vec<Coin>,
+        #[case] refunded: Vec<Coin>,
+    ) {
+        set_route(
+            deps.as_mut().storage,
+            &addr(OWNER),
+            DomainRouteSet {
+                domain: 1,
+                route: Some(gen_bz(32)),
+            },
+        )
+        .unwrap();
+
+        test_execute(
+            deps.as_mut(),
+            &addr(OWNER),
+            ExecuteMsg::SetMinRefund {
+                min_refund: Uint128::new(min_refund),
+            },
+            vec![],
+        );
+
+        mock_quote(&mut deps, vec![coin(50, DENOM), coin(30, "uatom")]);
+
+        let res = test_execute(
+            deps.as_mut(),
+            &addr("sender"),
+            ExecuteMsg::TransferRemote {
+                dest_domain: 1,
+                recipient: gen_bz(32),
+                amount: Uint128::new(50),
+                hook: None,
+                metadata: None,
+            },
+            vec![coin(100, DENOM), coin(100, "uatom")],
+        );
+        let mut msgs: Vec<_> = res.messages.into_iter().map(|v| v.msg).collect();
+
+        if !refunded.is_empty() {
+            assert_eq!(
+                msgs.pop().unwrap(),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "sender".to_string(),
+                    amount: refunded,
+                })
+            );
+        }
+
+        let CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute { funds, .. }) = msgs.pop().unwrap()
+        else {
+            unreachable!("unexpected msg")
+        };
+        assert_eq!(funds, forwarded);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "unauthorized")]
+    fn test_set_min_refund_unauthorized(mut deps: TestDeps) {
+        test_execute(
+            deps.as_mut(),
+            &addr("someone"),
+            ExecuteMsg::SetMinRefund {
+                min_refund: Uint128::one(),
+            },
+            vec![],
+        );
+    }
+
+    #[rstest]
+    fn test_embed_nonce(mut deps: TestDeps) {
+        let route = gen_bz(32);
+
+        set_route(
+            deps.as_mut().storage,
+            &addr(OWNER),
+            DomainRouteSet {
+                domain: 1,
+                route: Some(route.clone()),
+            },
+        )
+        .unwrap();
+
+        test_execute(
+            deps.as_mut(),
+            &addr(OWNER),
+            ExecuteMsg::SetEmbedNonce { embed_nonce: true },
+            vec![],
+        );
+
+        mock_quote(&mut deps, vec![]);
+
+        let res = test_execute(
+            deps.as_mut(),
+            &addr("sender"),
+            ExecuteMsg::TransferRemote {
+                dest_domain: 1,
+                recipient: gen_bz(32),
+                amount: Uint128::new(100),
+                hook: None,
+                metadata: None,
+            },
+            vec![coin(100, DENOM)],
+        );
+
+        let CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute { msg, .. }) =
+            res.messages.last().unwrap().msg.clone()
+        else {
+            unreachable!("unexpected msg")
+        };
+        let mailbox::ExecuteMsg::Dispatch(dispatch) = from_json(msg).unwrap() else {
+            unreachable!("unexpected msg")
+        };
+        let warp_msg: warp::Message = dispatch.msg_body.clone().into();
+        assert_eq!(warp_msg.nonce(), Some(7));
+
+        // the receiving route surfaces it on handle
+        let res = test_execute(
+            deps.as_mut(),
+            &addr(MAILBOX),
+            ExecuteMsg::Handle(HandleMsg {
+                origin: 1,
+                sender: route,
+                body: dispatch.msg_body,
+            }),
+            vec![],
+        );
+        assert!(res.events[0]
+            .attributes
+            .iter()
+            .any(|v| v.key == "nonce" && v.value == "7"));
+    }
+
+    #[rstest]
+    fn test_wrong_mode(#[with(token_mode_collateral())] deps: TestDeps) {
+        let storage = deps.as_ref().storage;
+        let contract = mock_env().contract.address;
+        let err = ContractError::WarpError(WarpError::WrongMode {
+            expected: TokenMode::Bridged,
+            got: TokenMode::Collateral,
+        });
+
+        assert_eq!(
+            mint_msg(storage, &contract, DENOM, 100u128).unwrap_err(),
+            err
+        );
+        assert_eq!(
+            burn_msg(storage, &contract, DENOM, 100u128).unwrap_err(),
+            err
+        );
+    }
+
+    #[rstest]
+    fn test_collateral_denom() {
+        let collateral = deps(token_mode_collateral(), "osmo");
+
+        let res: warp::CollateralDenomResponse = test_query(
+            collateral.as_ref(),
+            QueryMsg::TokenDefault(warp::TokenWarpDefaultQueryMsg::CollateralDenom {}),
+        );
+        assert_eq!(res.denom, DENOM);
+
+        let bridged = deps(token_mode_bridged(metadata(true)), "osmo");
+
+        let err = query(
+            bridged.as_ref(),
+            mock_env(),
+            QueryMsg::TokenDefault(warp::TokenWarpDefaultQueryMsg::CollateralDenom {}),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::WarpError(WarpError::WrongMode {
+                expected: TokenMode::Collateral,
+                got: TokenMode::Bridged,
+            })
+        );
+    }
+
+    #[rstest]
+    #[case("osmo", "osmo1d6a3j0kkpc8eac0j8h6ypyevfz8hd3qnuqjrc5")]
+    #[case("neutron", "neutron1d6a3j0kkpc8eac0j8h6ypyevfz8hd3qnsyg35p")]
+    fn test_preview_recipient(#[case] hrp: &str, #[case] expected: &str) {
+        let deps = deps(token_mode_collateral(), hrp);
+
+        let res: warp::PreviewRecipientResponse = test_query(
+            deps.as_ref(),
+            QueryMsg::TokenDefault(warp::TokenWarpDefaultQueryMsg::PreviewRecipient {
+                recipient: HexBinary::from_hex(
+                    "0000000000000000000000006ebb193ed60e0f9ee1f23df440932c488f76c413",
+                )
+                .unwrap(),
+            }),
+        );
+        assert_eq!(res.recipient, expected);
+    }
+
+    #[rstest]
+    #[case(token_mode_bridged(metadata(true)))]
+    #[case(token_mode_collateral())]
+    fn test_outstanding_cap(#[case] token_mode: NativeTokenMode) {
+        let mut deps = deps(token_mode, "osmo");
+        let route = gen_bz(32);
+
+        TOKEN.save(deps.as_mut().storage, &DENOM.into()).unwrap();
+        set_route(
+            deps.as_mut().storage,
+            &addr(OWNER),
+            DomainRouteSet {
+                domain: 1,
+                route: Some(route.clone()),
+            },
+        )
+        .unwrap();
+        mock_quote(&mut deps, vec![]);
+
+        test_execute(
+            deps.as_mut(),
+            &addr(OWNER),
+            ExecuteMsg::SetOutstandingCap {
+                cap: Some(Uint256::from_u128(100)),
+            },
+            vec![],
+        );
+
+        let transfer = |deps: &mut TestDeps, amount: u128| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("sender", &[coin(amount, DENOM)]),
+                ExecuteMsg::TransferRemote {
+                    dest_domain: 1,
+                    recipient: gen_bz(32),
+                    amount: Uint128::new(amount),
+                    hook: None,
+                    metadata: None,
+                },
+            )
+        };
+        let outstanding = |deps: &TestDeps| -> warp::OutstandingResponse {
+            test_query(
+                deps.as_ref(),
+                QueryMsg::TokenDefault(warp::TokenWarpDefaultQueryMsg::Outstanding {}),
+            )
+        };
+
+        // bridge up to the cap
+        transfer(&mut deps, 60).unwrap();
+        transfer(&mut deps, 40).unwrap();
+        assert_eq!(
+            outstanding(&deps),
+            warp::OutstandingResponse {
+                outstanding: Uint256::from_u128(100),
+                cap: Some(Uint256::from_u128(100)),
+            }
+        );
+
+        // over the cap
+        assert_eq!(
+            transfer(&mut deps, 1).unwrap_err(),
+            ContractError::WarpError(WarpError::OutstandingCapExceeded {
+                cap: Uint256::from_u128(100),
+                outstanding: Uint256::from_u128(100),
+                amount: Uint256::from_u128(1),
+            })
+        );
+
+        // bridging back in frees capacity
+        test_execute(
+            deps.as_mut(),
+            &addr(MAILBOX),
+            ExecuteMsg::Handle(HandleMsg {
+                origin: 1,
+                sender: route,
+                body: warp::Message {
+                    recipient: gen_bz(32),
+                    amount: Uint256::from_u128(30),
+                    metadata: HexBinary::default(),
+                }
+                .into(),
+            }),
+            vec![],
+        );
+        assert_eq!(outstanding(&deps).outstanding, Uint256::from_u128(70));
+
+        transfer(&mut deps, 30).unwrap();
+        assert_eq!(outstanding(&deps).outstanding, Uint256::from_u128(100));
+    }
+
+    #[rstest]
+    #[should_panic(expected = "unauthorized")]
+    fn test_set_outstanding_cap_unauthorized(mut deps: TestDeps) {
+        test_execute(
+            deps.as_mut(),
+            &addr("someone"),
+            ExecuteMsg::SetOutstandingCap { cap: None },
+            vec![],
+        );
+    }
+
+    #[rstest]
+    #[case(EventVerbosity::Minimal, vec!["message_id"], vec![])]
+    #[case(
+        EventVerbosity::Standard,
+        vec!["message_id", "recipient", "token", "amount"],
+        vec!["sender", "amount", "recipient", "token", "hook", "metadata"]
+    )]
+    #[case(
+        EventVerbosity::Verbose,
+        vec!["message_id", "recipient", "token", "amount", "origin", "origin_sender"],
+        vec!["sender", "amount", "recipient", "token", "hook", "metadata", "dest_domain", "dest_router"]
+    )]
+    fn test_event_verbosity(
+        mut deps: TestDeps,
+        #[case] verbosity: EventVerbosity,
+        #[case] handle_keys: Vec<&str>,
+        #[case] transfer_keys: Vec<&str>,
+    ) {
+        let route = gen_bz(32);
+        let keys = |res: &Response| {
+            res.events
+                .iter()
+                .flat_map(|v| v.attributes.iter().map(|v| v.key.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        set_route(
+            deps.as_mut().storage,
+            &addr(OWNER),
+            DomainRouteSet {
+                domain: 1,
+                route: Some(route.clone()),
+            },
+        )
+        .unwrap();
+        mock_quote(&mut deps, vec![]);
+
+        test_execute(
+            deps.as_mut(),
+            &addr(OWNER),
+            ExecuteMsg::SetEventVerbosity { verbosity },
+            vec![],
+        );
+
+        let res = test_execute(
+            deps.as_mut(),
+            &addr(MAILBOX),
+            ExecuteMsg::Handle(HandleMsg {
+                origin: 1,
+                sender: route,
+                body: warp::Message {
+                    recipient: gen_bz(32),
+                    amount: Uint256::from_u128(100),
+                    metadata: HexBinary::default(),
+                }
+                .into(),
+            }),
+            vec![],
+        );
+        assert_eq!(keys(&res), handle_keys);
+        assert_eq!(
+            res.events[0].attributes[0].value,
+            HexBinary::from(MESSAGE_ID).to_hex()
+        );
+
+        let res = test_execute(
+            deps.as_mut(),
+            &addr("sender"),
+            ExecuteMsg::TransferRemote {
+                dest_domain: 1,
+                recipient: gen_bz(32),
+                amount: Uint128::new(100),
+                hook: None,
+                metadata: None,
+            },
+            vec![coin(100, DENOM)],
+        );
+        assert_eq!(keys(&res), transfer_keys);
+    }
+
+    #[rstest]
+    fn test_reply_dispatch(mut deps: TestDeps) {
+        let message_id = gen_bz(32);
+        let data = to_json_binary(&mailbox::DispatchResponse {
+            message_id: message_id.clone(),
+        })
+        .unwrap();
+
+        // protobuf encoded MsgExecuteContractResponse { data }
+        let mut reply_data = vec![0x0a, data.len() as u8];
+        reply_data.extend_from_slice(&data);
+
+        let res = reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: REPLY_ID_DISPATCH,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: Some(reply_data.into()),
+                }),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            res.events[0].ty,
+            "hpl_warp_native::transfer-remote-dispatched"
+        );
+        assert_eq!(res.events[0].attributes[0].key, "message_id");
+        assert_eq!(res.events[0].attributes[0].value, message_id.to_hex());
+    }
 }
@@ -9,12 +9,21 @@ pub enum ContractError {
     #[error("{0}")]
     PaymentError(#[from] cw_utils::PaymentError),
 
+    #[error("{0}")]
+    ParseReplyError(#[from] cw_utils::ParseReplyError),
+
     #[error("{0}")]
     RecoverPubkeyError(#[from] RecoverPubkeyError),
 
     #[error("{0}")]
     MigrationError(#[from] hpl_utils::MigrationError),
 
+    #[error("{0}")]
+    RouterError(#[from] hpl_router::RouterError),
+
+    #[error("{0}")]
+    WarpError(#[from] hpl_warp::WarpError),
+
     #[error("unauthorized")]
     Unauthorized,
 
@@ -29,4 +38,7 @@ pub enum ContractError {
 
     #[error("no route for domain {domain:?}")]
     NoRouter { domain: u32 },
+
+    #[error("invalid recipient: {recipient}")]
+    InvalidRecipient { recipient: String },
 }
@@ -0,0 +1,1502 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    ensure, ensure_eq, from_json, to_json_binary, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Empty,
+    Env, HexBinary, MessageInfo, QueryResponse, Reply, Response, StdError, SubMsg, Uint128,
+    Uint256, WasmMsg,
+};
+use cw20::Cw20QueryMsg;
+use hpl_connection::{get_hook, get_ism};
+use hpl_interface::{
+    core::mailbox,
+    ism::{InterchainSecurityModuleResponse, IsmSpecifierQueryMsg},
+    to_binary,
+    types::bech32_encode,
+    warp::{
+        self,
+        unified::{
+            ExecuteMsg, InstantiateMsg, QueryMsg, UnifiedModeBridged, UnifiedModeCollateral,
+        },
+    },
+    warp::{TokenMode, TokenModeMsg, TokenModeResponse, TokenTypeResponse},
+    EventVerbosity,
+};
+use hpl_router::get_route;
+use hpl_warp::{
+    admin, conv,
+    proto::{MsgCreateDenom, MsgCreateDenomResponse},
+    split_gas,
+    state::{
+        ensure_mode, record_inbound, record_outbound, EMBED_NONCE, EVENT_VERBOSITY, MODE,
+        OUTSTANDING, OUTSTANDING_CAP, REMOTE_DECIMALS,
+    },
+    to_local_amount, to_remote_amount,
+};
+
+use crate::{
+    error::ContractError, new_event, TokenKind, ALLOWED_SENDERS, CONTRACT_NAME, CONTRACT_VERSION,
+    HRP, KIND, MAILBOX, MAX_DECIMALS, REPLY_ID_CREATE_DENOM, REPLY_ID_DISPATCH, TOKEN,
+};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let mode: TokenMode = msg.token.clone().into();
+    let owner = deps.api.addr_validate(&msg.owner)?;
+
+    HRP.save(deps.storage, &msg.hrp)?;
+    MODE.save(deps.storage, &mode)?;
+    MAILBOX.save(deps.storage, &deps.api.addr_validate(&msg.mailbox)?)?;
+
+    hpl_ownable::initialize(deps.storage, &owner)?;
+
+    let (kind, msgs, denom) = match msg.token {
+        // instantiate a fresh cw20 minted by this route
+        TokenModeMsg::Bridged(UnifiedModeBridged::Cw20(token)) => {
+            // supply of bridged token must be backed by collateral on the remote side
+            ensure!(
+                token.init_msg.initial_balances.is_empty(),
+                ContractError::InitialBalances
+            );
+            if let Some(allowed) = msg.allowed_code_ids {
+                ensure!(
+                    allowed.contains(&token.code_id),
+                    ContractError::CodeIdNotAllowed {
+                        code_id: token.code_id
+                    }
+                );
+            }
+
+            let mut token_init_msg = token.init_msg;
+            token_init_msg.mint = Some(cw20::MinterResponse {
+                minter: env.contract.address.to_string(),
+                cap: None,
+            });
+
+            let msgs = vec![SubMsg::reply_on_success(
+                WasmMsg::Instantiate {
+                    admin: Some(env.contract.address.to_string()),
+                    code_id: token.code_id,
+                    msg: to_json_binary(&token_init_msg)?,
+                    funds: vec![],
+                    label: "token warp cw20".to_string(),
+                },
+                REPLY_ID_CREATE_DENOM,
+            )];
+
+            (TokenKind::Cw20, msgs, token_init_msg.name)
+        }
+        // create native denom if token is bridged
+        TokenModeMsg::Bridged(UnifiedModeBridged::Native(token)) => {
+            let mut msgs = vec![SubMsg::reply_on_success(
+                MsgCreateDenom {
+                    sender: env.contract.address.to_string(),
+                    subdenom: token.denom.clone(),
+                },
+                REPLY_ID_CREATE_DENOM,
+            )];
+
+            if let Some(metadata) = token.metadata {
+                msgs.push(SubMsg::new(conv::native::to_set_metadata_msg(
+                    &env.contract.address,
+                    metadata,
+                )));
+            }
+
+            (
+                TokenKind::Native,
+                msgs,
+                format!("factory/{}/{}", env.contract.address, token.denom),
+            )
+        }
+        TokenModeMsg::Collateral(UnifiedModeCollateral::Cw20(token)) => {
+            let token_addr = deps.api.addr_validate(&token.address)?;
+            TOKEN.save(deps.storage, &token_addr.to_string())?;
+            (TokenKind::Cw20, vec![], token_addr.into())
+        }
+        // use denom directly if token is native
+        TokenModeMsg::Collateral(UnifiedModeCollateral::Native(token)) => {
+            TOKEN.save(deps.storage, &token.denom)?;
+            (TokenKind::Native, vec![], token.denom)
+        }
+    };
+
+    KIND.save(deps.storage, &kind)?;
+
+    Ok(Response::new().add_submessages(msgs).add_event(
+        new_event("instantiate")
+            .add_attribute("sender", info.sender)
+            .add_attribute("owner", owner)
+            .add_attribute("kind", format!("{kind:?}").to_lowercase())
+            .add_attribute("mode", format!("{}", mode))
+            .add_attribute("denom", denom),
+    ))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    use ExecuteMsg::*;
+
+    match msg {
+        Ownable(msg) => Ok(hpl_ownable::handle(deps, env, info, msg)?),
+        Router(msg) => Ok(hpl_router::handle(deps, env, info, msg)?),
+        Connection(msg) => Ok(hpl_connection::handle(deps, env, info, msg)?),
+        Handle(msg) => mailbox_handle(deps, env, info, msg),
+        TransferRemote {
+            dest_domain,
+            recipient,
+            amount,
+            hook,
+            metadata,
+        } => transfer_remote(
+            deps,
+            env,
+            info,
+            dest_domain,
+            recipient,
+            amount,
+            hook,
+            metadata,
+        ),
+        SetRemoteDecimals {
+            dest_domain,
+            decimals,
+        } => set_remote_decimals(deps, info, dest_domain, decimals),
+        SetAllowedSenders { domain, senders } => set_allowed_senders(deps, info, domain, senders),
+        SetMinRefund { min_refund } => {
+            Ok(admin::set_min_refund(deps, info, new_event, min_refund)?)
+        }
+        SetEmbedNonce { embed_nonce } => {
+            Ok(admin::set_embed_nonce(deps, info, new_event, embed_nonce)?)
+        }
+        SetOutstandingCap { cap } => Ok(admin::set_outstanding_cap(deps, info, new_event, cap)?),
+        SetEventVerbosity { verbosity } => Ok(admin::set_event_verbosity(
+            deps, info, new_event, verbosity,
+        )?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let reply_data = msg
+        .result
+        .into_result()
+        .map_err(StdError::generic_err)?
+        .data
+        .ok_or(StdError::generic_err("no reply data"))?;
+
+    match msg.id {
+        REPLY_ID_CREATE_DENOM => reply_init(deps, env, reply_data),
+        REPLY_ID_DISPATCH => {
+            let exec_resp = cw_utils::parse_execute_response_data(&reply_data)?;
+            let dispatch: mailbox::DispatchResponse = from_json(
+                exec_resp
+                    .data
+                    .ok_or(StdError::generic_err("no dispatch data"))?,
+            )?;
+
+            let resp = Response::new().add_event(
+                new_event("transfer-remote-dispatched")
+                    .add_attribute("message_id", dispatch.message_id.to_hex()),
+            );
+
+            Ok(resp)
+        }
+
+        _ => Err(ContractError::InvalidReplyId),
+    }
+}
+
+fn reply_init(deps: DepsMut, env: Env, reply_data: Binary) -> Result<Response, ContractError> {
+    let token = match KIND.load(deps.storage)? {
+        TokenKind::Cw20 => {
+            let init_resp = cw_utils::parse_instantiate_response_data(&reply_data)?;
+            let init_addr = deps.api.addr_validate(&init_resp.contract_address)?;
+
+            // catch misconfigured init_msg here rather than at the first mint
+            let minter: Option<cw20::MinterResponse> = deps
+                .querier
+                .query_wasm_smart(&init_addr, &Cw20QueryMsg::Minter {})?;
+            let minter = minter.map(|v| v.minter);
+            ensure!(
+                minter.as_deref() == Some(env.contract.address.as_str()),
+                ContractError::InvalidMinter {
+                    expected: env.contract.address.into(),
+                    got: minter,
+                }
+            );
+
+            init_addr.into_string()
+        }
+        TokenKind::Native => {
+            let reply: MsgCreateDenomResponse = reply_data.try_into()?;
+            reply.new_token_denom
+        }
+    };
+
+    TOKEN.save(deps.storage, &token)?;
+
+    Ok(Response::new().add_event(new_event("reply-init").add_attribute("denom", token)))
+}
+
+fn mailbox_handle(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: hpl_interface::core::HandleMsg,
+) -> Result<Response, ContractError> {
+    // validate mailbox
+    ensure_eq!(
+        info.sender,
+        MAILBOX.load(deps.storage)?,
+        ContractError::Unauthorized
+    );
+    // validate origin chain router
+    ensure!(
+        is_allowed_sender(deps.as_ref(), msg.origin, &msg.sender)?,
+        ContractError::Unauthorized
+    );
+
+    let token_msg: warp::Message = msg.body.into();
+    let recipient =
+        bech32_encode(&HRP.load(deps.storage)?, &token_msg.recipient).map_err(|_| {
+            ContractError::InvalidRecipient {
+                recipient: token_msg.recipient.to_hex(),
+            }
+        })?;
+
+    let token = TOKEN.load(deps.storage)?;
+    let local_amount = to_local_amount(deps.as_ref(), &token, msg.origin, token_msg.amount)?;
+    let amount = conv::to_uint128(local_amount)?;
+
+    record_inbound(deps.storage, local_amount)?;
+
+    let msgs: Vec<CosmosMsg> = match (KIND.load(deps.storage)?, MODE.load(deps.storage)?) {
+        (TokenKind::Cw20, TokenMode::Bridged) => {
+            vec![conv::cw20::to_mint_msg(&token, &recipient, amount)?.into()]
+        }
+        (TokenKind::Cw20, TokenMode::Collateral) => {
+            vec![conv::cw20::to_send_msg(&token, &recipient, amount)?.into()]
+        }
+        // mint to the route first, then send it out like the collateral
+        (TokenKind::Native, TokenMode::Bridged) => vec![
+            conv::native::to_mint_msg(&env.contract.address, &token, amount).into(),
+            conv::native::to_send_msg(&recipient, vec![conv::native::to_coin(amount, &token)])
+                .into(),
+        ],
+        (TokenKind::Native, TokenMode::Collateral) => {
+            vec![
+                conv::native::to_send_msg(&recipient, vec![conv::native::to_coin(amount, &token)])
+                    .into(),
+            ]
+        }
+    };
+
+    let verbosity = EVENT_VERBOSITY.may_load(deps.storage)?.unwrap_or_default();
+
+    let mut event = new_event("handle");
+    // recorded by the mailbox right before it hands the message over.
+    // mailboxes without the LatestProcessId query leave it out
+    if let Ok(message_id) = mailbox::latest_process_id(&deps.querier, &info.sender) {
+        event = event.add_attribute("message_id", message_id.to_hex());
+    }
+    if verbosity >= EventVerbosity::Standard {
+        event = event
+            .add_attribute("recipient", recipient)
+            .add_attribute("token", token)
+            .add_attribute("amount", amount);
+        if let Some(nonce) = token_msg.nonce() {
+            event = event.add_attribute("nonce", nonce.to_string());
+        }
+    }
+    if verbosity >= EventVerbosity::Verbose {
+        event = event
+            .add_attribute("origin", msg.origin.to_string())
+            .add_attribute("origin_sender", msg.sender.to_hex());
+    }
+
+    Ok(Response::new().add_messages(msgs).add_event(event))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn transfer_remote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    dest_domain: u32,
+    recipient: HexBinary,
+    transfer_amount: Uint128,
+    hook: Option<String>,
+    metadata: Option<HexBinary>,
+) -> Result<Response, ContractError> {
+    let token = TOKEN.load(deps.storage)?;
+    let mode = MODE.load(deps.storage)?;
+    let mailbox = MAILBOX.load(deps.storage)?;
+
+    let dest_router = get_route::<HexBinary>(deps.storage, dest_domain)?
+        .route
+        .ok_or(ContractError::NoRouter {
+            domain: dest_domain,
+        })?;
+
+    // validate hook if present
+    if let Some(ref custom_hook) = hook {
+        let _ = deps.api.addr_validate(custom_hook)?;
+    }
+
+    record_outbound(deps.storage, Uint256::from_uint128(transfer_amount))?;
+
+    let mut funds = info.funds.clone();
+    let mut msgs: Vec<CosmosMsg> = vec![];
+
+    match KIND.load(deps.storage)? {
+        TokenKind::Cw20 => {
+            // pull the tokens in. the sender must have granted an allowance
+            msgs.push(
+                conv::cw20::to_pull_msg(
+                    &token,
+                    &info.sender,
+                    &env.contract.address,
+                    transfer_amount,
+                )?
+                .into(),
+            );
+
+            if mode == TokenMode::Bridged {
+                msgs.push(conv::cw20::to_burn_msg(&token, transfer_amount)?.into());
+            }
+        }
+        TokenKind::Native => {
+            let received = funds
+                .iter()
+                .find(|v| v.denom == token)
+                .map(|v| v.amount)
+                .unwrap_or_default();
+            ensure!(
+                received >= transfer_amount,
+                ContractError::InsufficientFunds
+            );
+
+            // whatever is left over pays for the dispatch
+            for coin in funds.iter_mut().filter(|v| v.denom == token) {
+                coin.amount -= transfer_amount;
+            }
+            funds.retain(|v| !v.amount.is_zero());
+
+            if mode == TokenMode::Bridged {
+                msgs.push(
+                    conv::native::to_burn_msg(&env.contract.address, &token, transfer_amount)
+                        .into(),
+                );
+            }
+        }
+    }
+
+    let remote_amount = to_remote_amount(
+        deps.as_ref(),
+        &token,
+        dest_domain,
+        Uint256::from_uint128(transfer_amount),
+    )?;
+
+    let mut dispatch_payload = warp::Message {
+        recipient: recipient.clone(),
+        amount: remote_amount,
+        metadata: HexBinary::default(),
+    };
+    if EMBED_NONCE.may_load(deps.storage)?.unwrap_or_default() {
+        dispatch_payload = dispatch_payload.with_nonce(mailbox::nonce(&deps.querier, &mailbox)?);
+    }
+
+    let dispatch_msg = mailbox::DispatchMsg {
+        dest_domain,
+        recipient_addr: dest_router.clone(),
+        msg_body: dispatch_payload.into(),
+        hook: hook.clone().or(get_hook(deps.storage)?.map(|v| v.into())),
+        metadata: metadata.clone(),
+    };
+
+    let (funds, refund) = split_gas(deps.as_ref(), &env, &mailbox, &dispatch_msg, funds)?;
+
+    // push mailbox dispatch msg. the reply picks up the id the mailbox assigns to it
+    let dispatch = SubMsg::reply_on_success(
+        mailbox::dispatch(
+            mailbox,
+            dispatch_msg.dest_domain,
+            dispatch_msg.recipient_addr,
+            dispatch_msg.msg_body,
+            dispatch_msg.hook,
+            dispatch_msg.metadata,
+            funds,
+        )?,
+        REPLY_ID_DISPATCH,
+    );
+    let mut resp = Response::new().add_messages(msgs).add_submessage(dispatch);
+
+    // refund gas overpayment
+    if !refund.is_empty() {
+        resp = resp.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: refund,
+        });
+    }
+
+    let verbosity = EVENT_VERBOSITY.may_load(deps.storage)?.unwrap_or_default();
+
+    // at Minimal only the id is emitted, by the transfer-remote-dispatched event of the reply
+    if verbosity >= EventVerbosity::Standard {
+        let mut event = new_event("transfer-remote")
+            .add_attribute("sender", info.sender)
+            .add_attribute("amount", transfer_amount)
+            .add_attribute("dest_domain", dest_domain.to_string())
+            .add_attribute("recipient", recipient.to_hex())
+            .add_attribute("token", token)
+            .add_attribute("remote_amount", remote_amount)
+            .add_attribute("hook", hook.unwrap_or_default())
+            .add_attribute("metadata", metadata.unwrap_or_default().to_string());
+        if verbosity >= EventVerbosity::Verbose {
+            event = event.add_attribute("dest_router", dest_router.to_hex());
+        }
+        resp = resp.add_event(event);
+    }
+
+    Ok(resp)
+}
+
+fn set_allowed_senders(
+    deps: DepsMut,
+    info: MessageInfo,
+    domain: u32,
+    senders: Vec<HexBinary>,
+) -> Result<Response, ContractError> {
+    ensure_eq!(
+        hpl_ownable::get_owner(deps.storage)?,
+        info.sender,
+        ContractError::Unauthorized
+    );
+
+    if senders.is_empty() {
+        ALLOWED_SENDERS.remove(deps.storage, domain);
+    } else {
+        ALLOWED_SENDERS.save(deps.storage, domain, &senders)?;
+    }
+
+    Ok(Response::new().add_event(
+        new_event("set-allowed-senders")
+            .add_attribute("sender", info.sender)
+            .add_attribute("domain", domain.to_string())
+            .add_attribute(
+                "senders",
+                senders
+                    .iter()
+                    .map(|v| v.to_hex())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+    ))
+}
+
+fn is_allowed_sender(deps: Deps, origin: u32, sender: &HexBinary) -> Result<bool, ContractError> {
+    match ALLOWED_SENDERS.may_load(deps.storage, origin)? {
+        Some(senders) => Ok(senders.contains(sender)),
+        // defaults to the enrolled route. unknown origins are rejected
+        None => {
+            let route = get_route::<HexBinary>(deps.storage, origin)?.route;
+
+            Ok(route.as_ref() == Some(sender))
+        }
+    }
+}
+
+fn set_remote_decimals(
+    deps: DepsMut,
+    info: MessageInfo,
+    dest_domain: u32,
+    decimals: u8,
+) -> Result<Response, ContractError> {
+    ensure_eq!(
+        hpl_ownable::get_owner(deps.storage)?,
+        info.sender,
+        ContractError::Unauthorized
+    );
+    // bank denoms don't expose their decimals, so there is nothing to scale from
+    ensure_eq!(
+        KIND.load(deps.storage)?,
+        TokenKind::Cw20,
+        ContractError::DecimalsUnsupported
+    );
+    ensure!(
+        decimals <= MAX_DECIMALS,
+        ContractError::InvalidDecimals {
+            decimals,
+            max: MAX_DECIMALS
+        }
+    );
+
+    REMOTE_DECIMALS.save(deps.storage, dest_domain, &decimals)?;
+
+    Ok(Response::new().add_event(
+        new_event("set-remote-decimals")
+            .add_attribute("sender", info.sender)
+            .add_attribute("dest_domain", dest_domain.to_string())
+            .add_attribute("decimals", decimals.to_string()),
+    ))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, ContractError> {
+    use warp::TokenWarpDefaultQueryMsg::*;
+
+    match msg {
+        QueryMsg::Ownable(msg) => Ok(hpl_ownable::handle_query(deps, env, msg)?),
+        QueryMsg::Router(msg) => Ok(hpl_router::handle_query(deps, env, msg)?),
+        QueryMsg::Connection(msg) => Ok(hpl_connection::handle_query(deps, env, msg)?),
+        QueryMsg::TokenDefault(msg) => match msg {
+            TokenType {} => to_binary(get_token_type(deps)),
+            TokenMode {} => to_binary(get_token_mode(deps)),
+            PreviewRecipient { recipient } => to_binary(preview_recipient(deps, recipient)),
+            CollateralDenom {} => to_binary(get_collateral_denom(deps)),
+            Outstanding {} => to_binary(get_outstanding(deps)),
+        },
+        QueryMsg::IsmSpecifier(IsmSpecifierQueryMsg::InterchainSecurityModule()) => {
+            Ok(to_json_binary(&InterchainSecurityModuleResponse {
+                ism: get_ism(deps.storage)?,
+            })?)
+        }
+    }
+}
+
+fn get_token_type(deps: Deps) -> Result<TokenTypeResponse, ContractError> {
+    let token = TOKEN.load(deps.storage)?;
+
+    let typ = match KIND.load(deps.storage)? {
+        TokenKind::Cw20 => warp::TokenType::CW20 { contract: token },
+        TokenKind::Native => {
+            warp::TokenType::Native(warp::TokenTypeNative::Fungible { denom: token })
+        }
+    };
+
+    Ok(TokenTypeResponse { typ })
+}
+
+fn get_token_mode(deps: Deps) -> Result<TokenModeResponse, ContractError> {
+    let mode = MODE.load(deps.storage)?;
+
+    Ok(TokenModeResponse { mode })
+}
+
+fn get_collateral_denom(deps: Deps) -> Result<warp::CollateralDenomResponse, ContractError> {
+    ensure_mode(deps.storage, TokenMode::Collateral)?;
+
+    Ok(warp::CollateralDenomResponse {
+        denom: TOKEN.load(deps.storage)?,
+    })
+}
+
+fn get_outstanding(deps: Deps) -> Result<warp::OutstandingResponse, ContractError> {
+    Ok(warp::OutstandingResponse {
+        outstanding: OUTSTANDING.may_load(deps.storage)?.unwrap_or_default(),
+        cap: OUTSTANDING_CAP.may_load(deps.storage)?.flatten(),
+    })
+}
+
+fn preview_recipient(
+    deps: Deps,
+    recipient: HexBinary,
+) -> Result<warp::PreviewRecipientResponse, ContractError> {
+    let recipient = bech32_encode(&HRP.load(deps.storage)?, &recipient)?;
+
+    Ok(warp::PreviewRecipientResponse {
+        recipient: recipient.into(),
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
+    hpl_utils::migrate(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::default())
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{
+        coin, from_json,
+        testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
+        BankMsg, Coin, ContractResult, OwnedDeps, QuerierResult, SubMsgResponse, SubMsgResult,
+        SystemResult, WasmQuery,
+    };
+    use cw20::{Cw20ExecuteMsg, TokenInfoResponse};
+    use hpl_interface::{
+        build_test_executor, build_test_querier,
+        core::HandleMsg,
+        hook::QuoteDispatchResponse,
+        router::DomainRouteSet,
+        warp::{
+            cw20::{Cw20ModeBridged, Cw20ModeCollateral},
+            native::{NativeModeBriged, NativeModeCollateral},
+        },
+    };
+    use hpl_router::set_route;
+    use ibcx_test_utils::{addr, gen_bz};
+    use rstest::{fixture, rstest};
+
+    use hpl_warp::proto;
+
+    use super::*;
+
+    build_test_executor!(super::execute);
+    build_test_querier!(super::query);
+
+    type UnifiedTokenMode = TokenModeMsg<UnifiedModeBridged, UnifiedModeCollateral>;
+    type TestDeps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+    const DEPLOYER: &str = "deployer";
+    const OWNER: &str = "owner";
+    const MAILBOX: &str = "mailbox";
+    const DENOM: &str = "utest";
+    const CW20: &str = "cw20";
+    const DOMAIN: u32 = 1;
+
+    fn cw20_bridged() -> UnifiedTokenMode {
+        TokenModeMsg::Bridged(UnifiedModeBridged::Cw20(Cw20ModeBridged {
+            code_id: 1,
+            init_msg: cw20_base::msg::InstantiateMsg {
+                name: CW20.to_string(),
+                symbol: CW20.to_string(),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: None,
+                marketing: None,
+            }
+            .into(),
+        }))
+    }
+
+    fn cw20_collateral() -> UnifiedTokenMode {
+        TokenModeMsg::Collateral(UnifiedModeCollateral::Cw20(Cw20ModeCollateral {
+            address: CW20.to_string(),
+        }))
+    }
+
+    fn native_bridged() -> UnifiedTokenMode {
+        TokenModeMsg::Bridged(UnifiedModeBridged::Native(NativeModeBriged {
+            denom: DENOM.to_string(),
+            metadata: None,
+        }))
+    }
+
+    fn native_collateral() -> UnifiedTokenMode {
+        TokenModeMsg::Collateral(UnifiedModeCollateral::Native(NativeModeCollateral {
+            denom: DENOM.to_string(),
+        }))
+    }
+
+    #[fixture]
+    fn deps(
+        #[default(native_collateral())] token_mode: UnifiedTokenMode,
+        #[default(gen_bz(32))] route: HexBinary,
+    ) -> TestDeps {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(DEPLOYER, &[]),
+            InstantiateMsg {
+                token: token_mode.clone(),
+                hrp: "osmo".into(),
+                owner: OWNER.into(),
+                mailbox: MAILBOX.into(),
+                allowed_code_ids: None,
+            },
+        )
+        .unwrap();
+
+        // stands in for the reply of the token creation
+        if let TokenModeMsg::Bridged(token) = token_mode {
+            let token = match token {
+                UnifiedModeBridged::Cw20(_) => CW20,
+                UnifiedModeBridged::Native(_) => DENOM,
+            };
+            TOKEN.save(deps.as_mut().storage, &token.into()).unwrap();
+        }
+
+        set_route(
+            deps.as_mut().storage,
+            &addr(OWNER),
+            DomainRouteSet {
+                domain: DOMAIN,
+                route: Some(route),
+            },
+        )
+        .unwrap();
+
+        mock_quote(&mut deps, vec![]);
+
+        deps
+    }
+
+    #[rstest]
+    #[case(cw20_bridged(), TokenKind::Cw20, 1)]
+    #[case(cw20_collateral(), TokenKind::Cw20, 0)]
+    #[case(native_bridged(), TokenKind::Native, 1)]
+    #[case(native_collateral(), TokenKind::Native, 0)]
+    fn test_init(
+        #[case] token_mode: UnifiedTokenMode,
+        #[case] kind: TokenKind,
+        #[case] submsgs: usize,
+    ) {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let res = instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(DEPLOYER, &[]),
+            InstantiateMsg {
+                token: token_mode.clone(),
+                hrp: "osmo".into(),
+                owner: OWNER.into(),
+                mailbox: MAILBOX.into(),
+                allowed_code_ids: None,
+            },
+        )
+        .unwrap();
+
+        let storage = deps.as_ref().storage;
+        let mode = TokenMode::from(token_mode.clone());
+
+        assert_eq!(KIND.load(storage).unwrap(), kind);
+        assert_eq!(MODE.load(storage).unwrap(), mode);
+        assert_eq!(res.messages.len(), submsgs);
+
+        match token_mode {
+            TokenModeMsg::Bridged(UnifiedModeBridged::Cw20(_)) => {
+                let CosmosMsg::Wasm(WasmMsg::Instantiate { msg, .. }) = res.messages[0].msg.clone()
+                else {
+                    unreachable!("not an instantiate msg")
+                };
+                let init_msg: cw20_base::msg::InstantiateMsg =
+                    cosmwasm_std::from_json(msg).unwrap();
+                assert_eq!(
+                    init_msg.mint.unwrap().minter,
+                    env.contract.address.to_string()
+                );
+                assert!(!TOKEN.exists(storage));
+            }
+            TokenModeMsg::Bridged(UnifiedModeBridged::Native(_)) => {
+                assert_eq!(
+                    res.messages[0].msg,
+                    MsgCreateDenom {
+                        sender: env.contract.address.to_string(),
+                        subdenom: DENOM.into(),
+                    }
+                    .into()
+                );
+                assert!(!TOKEN.exists(storage));
+            }
+            TokenModeMsg::Collateral(UnifiedModeCollateral::Cw20(_)) => {
+                assert_eq!(TOKEN.load(storage).unwrap(), CW20);
+            }
+            TokenModeMsg::Collateral(UnifiedModeCollateral::Native(_)) => {
+                assert_eq!(TOKEN.load(storage).unwrap(), DENOM);
+            }
+        }
+    }
+
+    #[test]
+    fn test_init_initial_balances() {
+        let mut token_mode = cw20_bridged();
+        if let TokenModeMsg::Bridged(UnifiedModeBridged::Cw20(ref mut token)) = token_mode {
+            token.init_msg.initial_balances = vec![cw20::Cw20Coin {
+                address: "someone".into(),
+                amount: Uint128::new(100),
+            }];
+        }
+
+        let err = instantiate(
+            mock_dependencies().as_mut(),
+            mock_env(),
+            mock_info(DEPLOYER, &[]),
+            InstantiateMsg {
+                token: token_mode,
+                hrp: "osmo".into(),
+                owner: OWNER.into(),
+                mailbox: MAILBOX.into(),
+                allowed_code_ids: None,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InitialBalances);
+    }
+
+    #[rstest]
+    #[case(cw20_bridged())]
+    #[case(cw20_collateral())]
+    #[case(native_bridged())]
+    #[case(native_collateral())]
+    fn test_mailbox_handle(#[case] token_mode: UnifiedTokenMode) {
+        let route = gen_bz(32);
+        let mut deps = deps(token_mode.clone(), route.clone());
+        let env = mock_env();
+
+        let recipient = gen_bz(32);
+        let recipient_addr = bech32_encode("osmo", &recipient).unwrap();
+        let amount = Uint128::new(100);
+
+        let res = test_execute(
+            deps.as_mut(),
+            &addr(MAILBOX),
+            ExecuteMsg::Handle(HandleMsg {
+                origin: DOMAIN,
+                sender: route,
+                body: warp::Message {
+                    recipient,
+                    amount: Uint256::from_uint128(amount),
+                    metadata: HexBinary::default(),
+                }
+                .into(),
+            }),
+            vec![],
+        );
+        assert_eq!(
+            res.events[0].attributes[0].value,
+            HexBinary::from(MESSAGE_ID).to_hex()
+        );
+        let msgs: Vec<_> = res.messages.into_iter().map(|v| v.msg).collect();
+
+        let send = |denom: &str| -> CosmosMsg {
+            BankMsg::Send {
+                to_address: recipient_addr.to_string(),
+                amount: vec![coin(amount.u128(), denom)],
+            }
+            .into()
+        };
+        let cw20_exec = |msg: Cw20ExecuteMsg| -> CosmosMsg {
+            WasmMsg::Execute {
+                contract_addr: CW20.into(),
+                msg: to_json_binary(&msg).unwrap(),
+                funds: vec![],
+            }
+            .into()
+        };
+
+        let expected: Vec<CosmosMsg> = match token_mode {
+            TokenModeMsg::Bridged(UnifiedModeBridged::Cw20(_)) => {
+                vec![cw20_exec(Cw20ExecuteMsg::Mint {
+                    recipient: recipient_addr.to_string(),
+                    amount,
+                })]
+            }
+            TokenModeMsg::Collateral(UnifiedModeCollateral::Cw20(_)) => {
+                vec![cw20_exec(Cw20ExecuteMsg::Transfer {
+                    recipient: recipient_addr.to_string(),
+                    amount,
+                })]
+            }
+            TokenModeMsg::Bridged(UnifiedModeBridged::Native(_)) => vec![
+                proto::MsgMint {
+                    sender: env.contract.address.to_string(),
+                    amount: Some(proto::Coin {
+                        denom: DENOM.into(),
+                        amount: amount.to_string(),
+                    }),
+                }
+                .into(),
+                send(DENOM),
+            ],
+            TokenModeMsg::Collateral(UnifiedModeCollateral::Native(_)) => vec![send(DENOM)],
+        };
+
+        assert_eq!(msgs, expected);
+    }
+
+    #[rstest]
+    #[case(cw20_bridged(), vec![coin(10, "uosmo")])]
+    #[case(cw20_collateral(), vec![])]
+    #[case(native_bridged(), vec![coin(100, DENOM), coin(10, "uosmo")])]
+    #[case(native_collateral(), vec![coin(100, DENOM)])]
+    #[should_panic(expected = "insufficient funds")]
+    #[case(native_collateral(), vec![coin(99, DENOM)])]
+    fn test_transfer_remote(#[case] token_mode: UnifiedTokenMode, #[case] funds: Vec<Coin>) {
+        let route = gen_bz(32);
+        let mut deps = deps(token_mode.clone(), route.clone());
+        let env = mock_env();
+
+        let sender = addr("sender");
+        let recipient = gen_bz(32);
+        let amount = Uint128::new(100);
+
+        // quoted exactly the gas, so nothing is refunded
+        mock_quote(
+            &mut deps,
+            funds.iter().filter(|v| v.denom != DENOM).cloned().collect(),
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(sender.as_str(), &funds),
+            ExecuteMsg::TransferRemote {
+                dest_domain: DOMAIN,
+                recipient: recipient.clone(),
+                amount,
+                hook: None,
+                metadata: None,
+            },
+        )
+        .map_err(|e| e.to_string())
+        .unwrap();
+        let msgs: Vec<_> = res.messages.into_iter().map(|v| v.msg).collect();
+
+        let cw20_exec = |msg: Cw20ExecuteMsg| -> CosmosMsg {
+            WasmMsg::Execute {
+                contract_addr: CW20.into(),
+                msg: to_json_binary(&msg).unwrap(),
+                funds: vec![],
+            }
+            .into()
+        };
+        let pull = cw20_exec(Cw20ExecuteMsg::TransferFrom {
+            owner: sender.to_string(),
+            recipient: env.contract.address.to_string(),
+            amount,
+        });
+
+        let mut expected: Vec<CosmosMsg> = match token_mode {
+            TokenModeMsg::Bridged(UnifiedModeBridged::Cw20(_)) => {
+                vec![pull, cw20_exec(Cw20ExecuteMsg::Burn { amount })]
+            }
+            TokenModeMsg::Collateral(UnifiedModeCollateral::Cw20(_)) => vec![pull],
+            TokenModeMsg::Bridged(UnifiedModeBridged::Native(_)) => vec![proto::MsgBurn {
+                sender: env.contract.address.to_string(),
+                amount: Some(proto::Coin {
+                    denom: DENOM.into(),
+                    amount: amount.to_string(),
+                }),
+            }
+            .into()],
+            TokenModeMsg::Collateral(UnifiedModeCollateral::Native(_)) => vec![],
+        };
+
+        // only the gas is left to pay for the dispatch
+        let gas = funds.into_iter().filter(|v| v.denom != DENOM).collect();
+        expected.push(
+            mailbox::dispatch(
+                MAILBOX,
+                DOMAIN,
+                route,
+                warp::Message {
+                    recipient,
+                    amount: Uint256::from_uint128(amount),
+                    metadata: HexBinary::default(),
+                }
+                .into(),
+                None,
+                None,
+                gas,
+            )
+            .unwrap(),
+        );
+
+        assert_eq!(msgs, expected);
+    }
+
+    const MESSAGE_ID: [u8; 32] = [0xab; 32];
+
+    fn mailbox_query(msg: &Binary, fees: &[Coin]) -> QuerierResult {
+        let res = match from_json(msg).unwrap() {
+            mailbox::QueryMsg::Hook(mailbox::MailboxHookQueryMsg::QuoteDispatch { .. }) => {
+                to_json_binary(&QuoteDispatchResponse {
+                    fees: fees.to_vec(),
+                })
+            }
+            mailbox::QueryMsg::Mailbox(mailbox::MailboxQueryMsg::Nonce {}) => {
+                to_json_binary(&mailbox::NonceResponse { nonce: 7 })
+            }
+            mailbox::QueryMsg::Mailbox(mailbox::MailboxQueryMsg::LatestProcessId {}) => {
+                to_json_binary(&mailbox::LatestProcessedIdResponse {
+                    message_id: MESSAGE_ID.into(),
+                })
+            }
+            _ => unreachable!("unexpected query"),
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    }
+
+    fn mock_quote(deps: &mut TestDeps, fees: Vec<Coin>) {
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, msg } => {
+                assert_eq!(contract_addr, MAILBOX);
+                mailbox_query(msg, &fees)
+            }
+            _ => unreachable!("unexpected query"),
+        });
+    }
+
+    fn mock_token_decimals(deps: &mut TestDeps, decimals: u8) {
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == MAILBOX => {
+                mailbox_query(msg, &[])
+            }
+            WasmQuery::Smart { contract_addr, msg } => {
+                assert_eq!(contract_addr, CW20);
+                let Cw20QueryMsg::TokenInfo {} = from_json(msg).unwrap() else {
+                    unreachable!("unexpected query")
+                };
+
+                let res = TokenInfoResponse {
+                    name: CW20.to_string(),
+                    symbol: CW20.to_string(),
+                    decimals,
+                    total_supply: Uint128::zero(),
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&res).unwrap()))
+            }
+            _ => unreachable!("unexpected query"),
+        });
+    }
+
+    fn dispatched_message(res: &Response) -> warp::Message {
+        let CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) = &res.messages.last().unwrap().msg
+        else {
+            unreachable!("unexpected msg")
+        };
+        let mailbox::ExecuteMsg::Dispatch(dispatch) = from_json(msg).unwrap() else {
+            unreachable!("unexpected msg")
+        };
+
+        dispatch.msg_body.into()
+    }
+
+    #[rstest]
+    #[case(vec![1])]
+    #[should_panic(expected = "code id not allowed: 1")]
+    #[case(vec![2])]
+    fn test_init_allowed_code_ids(#[case] allowed: Vec<u64>) {
+        let res = instantiate(
+            mock_dependencies().as_mut(),
+            mock_env(),
+            mock_info(DEPLOYER, &[]),
+            InstantiateMsg {
+                token: cw20_bridged(),
+                hrp: "osmo".into(),
+                owner: OWNER.into(),
+                mailbox: MAILBOX.into(),
+                allowed_code_ids: Some(allowed),
+            },
+        )
+        .map_err(|e| e.to_string())
+        .unwrap();
+
+        assert_eq!(res.messages[0].id, REPLY_ID_CREATE_DENOM);
+    }
+
+    #[rstest]
+    #[case(mock_env().contract.address.to_string())]
+    #[should_panic(expected = "invalid minter")]
+    #[case("someone")]
+    fn test_reply_init_minter(#[case] minter: String) {
+        let mut deps = deps(cw20_bridged(), gen_bz(32));
+
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, msg } => {
+                assert_eq!(contract_addr, CW20);
+                let Cw20QueryMsg::Minter {} = from_json(msg).unwrap() else {
+                    unreachable!("unexpected query")
+                };
+
+                let res = Some(cw20::MinterResponse {
+                    minter: minter.clone(),
+                    cap: None,
+                });
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&res).unwrap()))
+            }
+            _ => unreachable!("unexpected query"),
+        });
+
+        // protobuf encoded MsgInstantiateContractResponse { address: CW20 }
+        let mut data = vec![0x0a, CW20.len() as u8];
+        data.extend_from_slice(CW20.as_bytes());
+
+        let res = reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: REPLY_ID_CREATE_DENOM,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: Some(data.into()),
+                }),
+            },
+        )
+        .map_err(|e| e.to_string())
+        .unwrap();
+
+        assert_eq!(res.events[0].ty, "hpl_warp_unified::reply-init");
+        assert_eq!(TOKEN.load(deps.as_ref().storage).unwrap(), CW20);
+    }
+
+    #[rstest]
+    fn test_reply_dispatch() {
+        let mut deps = deps(native_collateral(), gen_bz(32));
+
+        let message_id = gen_bz(32);
+        let data = to_json_binary(&mailbox::DispatchResponse {
+            message_id: message_id.clone(),
+        })
+        .unwrap();
+
+        // protobuf encoded MsgExecuteContractResponse { data }
+        let mut reply_data = vec![0x0a, data.len() as u8];
+        reply_data.extend_from_slice(&data);
+
+        let res = reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: REPLY_ID_DISPATCH,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: Some(reply_data.into()),
+                }),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            res.events[0].ty,
+            "hpl_warp_unified::transfer-remote-dispatched"
+        );
+        assert_eq!(res.events[0].attributes[0].key, "message_id");
+        assert_eq!(res.events[0].attributes[0].value, message_id.to_hex());
+    }
+
+    #[rstest]
+    #[case(cw20_collateral())]
+    #[case(native_collateral())]
+    fn test_transfer_remote_no_router(#[case] token_mode: UnifiedTokenMode) {
+        let mut deps = deps(token_mode, gen_bz(32));
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("sender", &[coin(100, DENOM)]),
+            ExecuteMsg::TransferRemote {
+                dest_domain: DOMAIN + 1,
+                recipient: gen_bz(32),
+                amount: Uint128::new(100),
+                hook: None,
+                metadata: None,
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(err, ContractError::NoRouter { domain: DOMAIN + 1 });
+    }
+
+    #[rstest]
+    #[case(vec![], 0, true)]
+    #[case(vec![], 1, false)]
+    #[case(vec![1, 2], 1, true)]
+    #[case(vec![1, 2], 2, true)]
+    #[case(vec![1, 2], 0, false)]
+    #[case(vec![1, 2], 3, false)]
+    fn test_allowed_senders(
+        #[case] allowed: Vec<usize>,
+        #[case] sender: usize,
+        #[case] accepted: bool,
+    ) {
+        let senders = (0..4).map(|_| gen_bz(32)).collect::<Vec<_>>();
+        let mut deps = deps(native_collateral(), senders[0].clone());
+
+        if !allowed.is_empty() {
+            test_execute(
+                deps.as_mut(),
+                &addr(OWNER),
+                ExecuteMsg::SetAllowedSenders {
+                    domain: DOMAIN,
+                    senders: allowed.iter().map(|i| senders[*i].clone()).collect(),
+                },
+                vec![],
+            );
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MAILBOX, &[]),
+            ExecuteMsg::Handle(HandleMsg {
+                origin: DOMAIN,
+                sender: senders[sender].clone(),
+                body: warp::Message {
+                    recipient: gen_bz(32),
+                    amount: Uint256::from_u128(100),
+                    metadata: HexBinary::default(),
+                }
+                .into(),
+            }),
+        );
+
+        match accepted {
+            true => assert!(res.is_ok()),
+            false => assert_eq!(res.unwrap_err(), ContractError::Unauthorized),
+        }
+    }
+
+    #[rstest]
+    #[should_panic(expected = "unauthorized")]
+    fn test_handle_unknown_origin() {
+        let mut deps = deps(native_collateral(), gen_bz(32));
+
+        test_execute(
+            deps.as_mut(),
+            &addr(MAILBOX),
+            ExecuteMsg::Handle(HandleMsg {
+                origin: DOMAIN + 1,
+                sender: gen_bz(32),
+                body: warp::Message {
+                    recipient: gen_bz(32),
+                    amount: Uint256::from_u128(100),
+                    metadata: HexBinary::default(),
+                }
+                .into(),
+            }),
+            vec![],
+        );
+    }
+
+    #[rstest]
+    #[case(cw20_collateral(), OWNER, 18)]
+    #[should_panic(expected = "unauthorized")]
+    #[case(cw20_collateral(), DEPLOYER, 18)]
+    #[should_panic(expected = "invalid decimals: 39. max: 38")]
+    #[case(cw20_collateral(), OWNER, 39)]
+    #[should_panic(expected = "remote decimals are only supported for cw20 tokens")]
+    #[case(native_collateral(), OWNER, 18)]
+    fn test_set_remote_decimals(
+        #[case] token_mode: UnifiedTokenMode,
+        #[case] sender: &str,
+        #[case] decimals: u8,
+    ) {
+        let mut deps = deps(token_mode, gen_bz(32));
+
+        let res = test_execute(
+            deps.as_mut(),
+            &addr(sender),
+            ExecuteMsg::SetRemoteDecimals {
+                dest_domain: DOMAIN,
+                decimals,
+            },
+            vec![],
+        );
+
+        assert_eq!(res.events[0].ty, "hpl_warp_unified::set-remote-decimals");
+        assert_eq!(
+            REMOTE_DECIMALS.load(deps.as_ref().storage, DOMAIN).unwrap(),
+            decimals
+        );
+    }
+
+    #[rstest]
+    #[case(100, 18, Some(100 * 10u128.pow(12)))]
+    #[case(1_000, 3, Some(1))]
+    #[should_panic(expected = "amount 1001 can't be bridged exactly with 3 remote decimals")]
+    #[case(1_001, 3, None)]
+    fn test_remote_decimals_scaling(
+        #[case] amount: u128,
+        #[case] decimals: u8,
+        #[case] expected: Option<u128>,
+    ) {
+        let route = gen_bz(32);
+        let mut deps = deps(cw20_collateral(), route.clone());
+        mock_token_decimals(&mut deps, 6);
+
+        test_execute(
+            deps.as_mut(),
+            &addr(OWNER),
+            ExecuteMsg::SetRemoteDecimals {
+                dest_domain: DOMAIN,
+                decimals,
+            },
+            vec![],
+        );
+
+        let res = test_execute(
+            deps.as_mut(),
+            &addr("sender"),
+            ExecuteMsg::TransferRemote {
+                dest_domain: DOMAIN,
+                recipient: gen_bz(32),
+                amount: Uint128::new(amount),
+                hook: None,
+                metadata: None,
+            },
+            vec![],
+        );
+        let sent = dispatched_message(&res);
+        assert_eq!(sent.amount, Uint256::from_u128(expected.unwrap()));
+
+        // inbound amounts are scaled back to the local decimals
+        let recipient = gen_bz(32);
+        let res = test_execute(
+            deps.as_mut(),
+            &addr(MAILBOX),
+            ExecuteMsg::Handle(HandleMsg {
+                origin: DOMAIN,
+                sender: route,
+                body: warp::Message {
+                    recipient: recipient.clone(),
+                    amount: sent.amount,
+                    metadata: HexBinary::default(),
+                }
+                .into(),
+            }),
+            vec![],
+        );
+
+        assert_eq!(
+            res.messages[0].msg,
+            conv::cw20::to_send_msg(
+                CW20,
+                bech32_encode("osmo", recipient.as_slice()).unwrap(),
+                Uint128::new(amount)
+            )
+            .unwrap()
+            .into()
+        );
+    }
+
+    #[rstest]
+    #[case(0, vec![coin(30, "uosmo")], vec![coin(70, "uosmo")])]
+    #[case(70, vec![coin(30, "uosmo")], vec![coin(70, "uosmo")])]
+    #[case(71, vec![coin(100, "uosmo")], vec![])]
+    fn test_transfer_remote_min_refund(
+        #[case] min_refund: u128,
+        #[case] forwarded: Vec<Coin>,
+        #[case] refunded: Vec<Coin>,
+    ) {
+        let mut deps = deps(native_collateral(), gen_bz(32));
+
+        test_execute(
+            deps.as_mut(),
+            &addr(OWNER),
+            ExecuteMsg::SetMinRefund {
+                min_refund: Uint128::new(min_refund),
+            },
+            vec![],
+        );
+
+        mock_quote(&mut deps, vec![coin(30, "uosmo")]);
+
+        let res = test_execute(
+            deps.as_mut(),
+            &addr("sender"),
+            ExecuteMsg::TransferRemote {
+                dest_domain: DOMAIN,
+                recipient: gen_bz(32),
+                amount: Uint128::new(100),
+                hook: None,
+                metadata: None,
+            },
+            vec![coin(100, DENOM), coin(100, "uosmo")],
+        );
+        let mut msgs: Vec<_> = res.messages.into_iter().map(|v| v.msg).collect();
+
+        if !refunded.is_empty() {
+            assert_eq!(
+                msgs.pop().unwrap(),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: addr("sender").to_string(),
+                    amount: refunded,
+                })
+            );
+        }
+
+        let CosmosMsg::Wasm(WasmMsg::Execute { funds, .. }) = msgs.pop().unwrap() else {
+            unreachable!("unexpected msg")
+        };
+        assert_eq!(funds, forwarded);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "unauthorized")]
+    #[case(ExecuteMsg::SetMinRefund { min_refund: Uint128::one() })]
+    #[should_panic(expected = "unauthorized")]
+    #[case(ExecuteMsg::SetEmbedNonce { embed_nonce: true })]
+    #[should_panic(expected = "unauthorized")]
+    #[case(ExecuteMsg::SetAllowedSenders { domain: DOMAIN, senders: vec![gen_bz(32)] })]
+    fn test_setters_unauthorized(#[case] msg: ExecuteMsg) {
+        let mut deps = deps(native_collateral(), gen_bz(32));
+
+        test_execute(deps.as_mut(), &addr(DEPLOYER), msg, vec![]);
+    }
+
+    #[rstest]
+    fn test_embed_nonce() {
+        let route = gen_bz(32);
+        let mut deps = deps(native_collateral(), route.clone());
+
+        test_execute(
+            deps.as_mut(),
+            &addr(OWNER),
+            ExecuteMsg::SetEmbedNonce { embed_nonce: true },
+            vec![],
+        );
+
+        mock_quote(&mut deps, vec![]);
+
+        let res = test_execute(
+            deps.as_mut(),
+            &addr("sender"),
+            ExecuteMsg::TransferRemote {
+                dest_domain: DOMAIN,
+                recipient: gen_bz(32),
+                amount: Uint128::new(100),
+                hook: None,
+                metadata: None,
+            },
+            vec![coin(100, DENOM)],
+        );
+
+        assert_eq!(dispatched_message(&res).nonce(), Some(7));
+    }
+
+    #[rstest]
+    #[case(cw20_bridged(), warp::TokenType::CW20 { contract: CW20.into() })]
+    #[case(cw20_collateral(), warp::TokenType::CW20 { contract: CW20.into() })]
+    #[case(
+        native_bridged(),
+        warp::TokenType::Native(warp::TokenTypeNative::Fungible { denom: DENOM.into() })
+    )]
+    #[case(
+        native_collateral(),
+        warp::TokenType::Native(warp::TokenTypeNative::Fungible { denom: DENOM.into() })
+    )]
+    fn test_queries(#[case] token_mode: UnifiedTokenMode, #[case] typ: warp::TokenType) {
+        let deps = deps(token_mode.clone(), gen_bz(32));
+
+        let res: warp::TokenTypeResponse = test_query(
+            deps.as_ref(),
+            QueryMsg::TokenDefault(warp::TokenWarpDefaultQueryMsg::TokenType {}),
+        );
+        assert_eq!(res.typ, typ);
+
+        let res: warp::TokenModeResponse = test_query(
+            deps.as_ref(),
+            QueryMsg::TokenDefault(warp::TokenWarpDefaultQueryMsg::TokenMode {}),
+        );
+        assert_eq!(res.mode, token_mode.into());
+    }
+}
@@ -0,0 +1,56 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, PartialEq, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    PaymentError(#[from] cw_utils::PaymentError),
+
+    #[error("{0}")]
+    ParseReplyError(#[from] cw_utils::ParseReplyError),
+
+    #[error("{0}")]
+    MigrationError(#[from] hpl_utils::MigrationError),
+
+    #[error("{0}")]
+    RouterError(#[from] hpl_router::RouterError),
+
+    #[error("{0}")]
+    WarpError(#[from] hpl_warp::WarpError),
+
+    #[error("unauthorized")]
+    Unauthorized,
+
+    #[error("invalid reply id")]
+    InvalidReplyId,
+
+    #[error("insufficient funds")]
+    InsufficientFunds,
+
+    #[error("bridged token must not have initial balances")]
+    InitialBalances,
+
+    #[error("no router for domain {domain:?}")]
+    NoRouter { domain: u32 },
+
+    #[error("invalid decimals: {decimals}. max: {max}")]
+    InvalidDecimals { decimals: u8, max: u8 },
+
+    #[error("remote decimals are only supported for cw20 tokens")]
+    DecimalsUnsupported,
+
+    #[error("code id not allowed: {code_id}")]
+    CodeIdNotAllowed { code_id: u64 },
+
+    #[error("invalid minter. expected: {expected}, got: {got:?}")]
+    InvalidMinter {
+        expected: String,
+        got: Option<String>,
+    },
+
+    #[error("invalid recipient: {recipient}")]
+    InvalidRecipient { recipient: String },
+}
@@ -0,0 +1,48 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Event, HexBinary};
+use cw_storage_plus::{Item, Map};
+
+pub mod contract;
+pub mod error;
+
+// upper bound of the decimals a token can have. keeps 10^decimals within Uint128
+pub const MAX_DECIMALS: u8 = 38;
+
+// reply message
+pub const REPLY_ID_CREATE_DENOM: u64 = 0;
+pub const REPLY_ID_DISPATCH: u64 = 1;
+
+// version info for migration info
+pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Kind of token the route moves
+#[cw_serde]
+pub enum TokenKind {
+    Cw20,
+    Native,
+}
+
+// storage definition for token denomination. bank denom or cw20 address
+const TOKEN_KEY: &str = "token";
+const TOKEN: Item<String> = Item::new(TOKEN_KEY);
+
+// storage definition for token kind
+const KIND_KEY: &str = "kind";
+const KIND: Item<TokenKind> = Item::new(KIND_KEY);
+
+// storage definition for token hrp
+const HRP_KEY: &str = "hrp";
+const HRP: Item<String> = Item::new(HRP_KEY);
+
+// storage definition for mailbox
+const MAILBOX_KEY: &str = "mailbox";
+const MAILBOX: Item<Addr> = Item::new(MAILBOX_KEY);
+
+// storage definition for accepted origin senders per domain
+const ALLOWED_SENDERS_PREFIX: &str = "allowed_senders";
+const ALLOWED_SENDERS: Map<u32, Vec<HexBinary>> = Map::new(ALLOWED_SENDERS_PREFIX);
+
+fn new_event(name: &str) -> Event {
+    Event::new(format!("hpl_warp_unified::{name}"))
+}
@@ -167,6 +167,7 @@ pub fn deploy_warp_route_bridged<'a, R: Runner<'a>>(
                 hrp: hrp.to_string(),
                 owner: owner.address(),
                 mailbox: mailbox.to_string(),
+                allowed_code_ids: None,
             },
         ),
         warp::TokenType::CW721 { .. } => todo!(),
@@ -197,6 +198,7 @@ pub fn deploy_warp_route_collateral<'a, R: Runner<'a>>(
                 hrp: hrp.to_string(),
                 owner: owner.address(),
                 mailbox: mailbox.to_string(),
+                allowed_code_ids: None,
             },
         )
     } else {
@@ -40,6 +40,7 @@ pub enum Hook {
 
     Aggregate {
         hooks: Vec<Self>,
+        aggregate_metadata: bool,
     },
 }
 
@@ -99,7 +100,9 @@ impl Hook {
             .instantiate(
                 codes.hook_merkle,
                 &hook::merkle::InstantiateMsg {
+                    owner: owner.address(),
                     mailbox,
+                    trusted_hooks: vec![],
                 },
                 Some(deployer.address().as_str()),
                 Some("cw-hpl-hook-merkle"),
@@ -182,17 +185,24 @@ impl Hook {
         Ok(hook)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn deploy_aggregate<'a, R: Runner<'a>>(
         wasm: &Wasm<'a, R>,
         code: u64,
         codes: &Codes,
         mailbox: String,
         hooks: Vec<Self>,
+        aggregate_metadata: bool,
         owner: &SigningAccount,
         deployer: &SigningAccount,
     ) -> eyre::Result<String> {
         use hpl_interface::hook::aggregate::*;
 
+        let merkle_hooks = hooks
+            .iter()
+            .map(|hook| matches!(hook, Hook::Merkle {}))
+            .collect::<Vec<_>>();
+
         let hook_addrs = hooks
             .into_iter()
             .map(|hook| hook.deploy(wasm, codes, mailbox.clone(), owner, deployer))
@@ -206,9 +216,30 @@ impl Hook {
             &InstantiateMsg {
                 owner: owner.address(),
                 hooks: hook_addrs,
+                continue_on_error: false,
+                forward_funds: true,
+                allow_empty: false,
+                aggregate_metadata,
             },
         );
 
+        // merkle hooks only take post_dispatch from the mailbox and trusted parent hooks
+        for (merkle, _) in hook_addrs
+            .iter()
+            .zip(merkle_hooks)
+            .filter(|(_, is_merkle)| *is_merkle)
+        {
+            wasm.execute(
+                merkle,
+                &hook::merkle::ExecuteMsg::SetTrustedHook {
+                    hook: hook.data.address.clone(),
+                    trusted: true,
+                },
+                &[],
+                owner,
+            )?;
+        }
+
         Ok(hook.data.address)
     }
 
@@ -295,12 +326,16 @@ impl Hook {
 
                 Ok(hook_addr)
             }
-            Hook::Aggregate { hooks } => Self::deploy_aggregate(
+            Hook::Aggregate {
+                hooks,
+                aggregate_metadata,
+            } => Self::deploy_aggregate(
                 wasm,
                 codes.hook_aggregate,
                 codes,
                 mailbox,
                 hooks,
+                aggregate_metadata,
                 owner,
                 deployer,
             ),
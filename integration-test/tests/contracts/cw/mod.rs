@@ -1,6 +1,6 @@
 mod deploy;
 mod hook;
-mod igp;
+pub mod igp;
 mod ism;
 mod setup;
 mod store;
@@ -1,7 +1,15 @@
 use std::{collections::BTreeMap, path::PathBuf};
 
-use cosmwasm_std::{coin, Coin, Uint256};
-use hpl_interface::igp::oracle::RemoteGasDataConfig;
+use cosmwasm_std::{coin, Coin, HexBinary, Uint256};
+use hpl_interface::{
+    hook::aggregate::{AggregateHookQueryMsg, HooksResponse},
+    igp::oracle::RemoteGasDataConfig,
+    ism::{
+        aggregate::{AggregateIsmQueryMsg, IsmsResponse},
+        routing::{RouteResponse, RoutingIsmQueryMsg},
+    },
+    types::Message,
+};
 use test_tube::{Account, Module, Runner, SigningAccount, Wasm};
 
 use crate::validator::TestValidators;
@@ -37,6 +45,66 @@ impl<'a, R: Runner<'a>> Env<'a, R> {
             .ok_or(eyre::eyre!("no validator set found"))
     }
 
+    /// deployed contract addresses keyed by name. contracts nested in the required hook
+    /// and the default ism are keyed by their path, e.g. `default_ism/{domain}/multisig`
+    #[allow(dead_code)]
+    pub fn addresses(&self) -> eyre::Result<BTreeMap<String, String>> {
+        let wasm = Wasm::new(self.app);
+
+        let mut addresses = BTreeMap::from([
+            ("mailbox".to_string(), self.core.mailbox.clone()),
+            ("default_ism".to_string(), self.core.default_ism.clone()),
+            ("default_hook".to_string(), self.core.default_hook.clone()),
+            ("required_hook".to_string(), self.core.required_hook.clone()),
+            ("msg_receiver".to_string(), self.core.msg_receiver.clone()),
+        ]);
+
+        // the required hook aggregates a merkle hook and an igp, in that order
+        let HooksResponse { hooks } = wasm.query(
+            &self.core.required_hook,
+            &hpl_interface::hook::aggregate::QueryMsg::AggregateHook(
+                AggregateHookQueryMsg::Hooks {},
+            ),
+        )?;
+        for (name, hook) in ["merkle", "igp"].into_iter().zip(hooks) {
+            addresses.insert(format!("required_hook/{name}"), hook);
+        }
+
+        // the default ism routes each origin to an aggregate of a single multisig ism
+        for domain in self.validators.keys() {
+            let message: HexBinary = Message {
+                version: 3,
+                nonce: 0,
+                origin_domain: *domain,
+                sender: vec![0u8; 32].into(),
+                dest_domain: self.domain,
+                recipient: vec![0u8; 32].into(),
+                body: HexBinary::default(),
+            }
+            .into();
+
+            let RouteResponse { ism } = wasm.query(
+                &self.core.default_ism,
+                &hpl_interface::ism::routing::QueryMsg::RoutingIsm(RoutingIsmQueryMsg::Route {
+                    message,
+                }),
+            )?;
+            let IsmsResponse { isms, .. } = wasm.query(
+                &ism,
+                &hpl_interface::ism::aggregate::QueryMsg::AggregateIsm(
+                    AggregateIsmQueryMsg::Isms {},
+                ),
+            )?;
+
+            addresses.insert(format!("default_ism/{domain}"), ism);
+            if let Some(multisig) = isms.into_iter().next() {
+                addresses.insert(format!("default_ism/{domain}/multisig"), multisig);
+            }
+        }
+
+        Ok(addresses)
+    }
+
     #[allow(dead_code)]
     pub fn gen_account(&'a self, coins: &'a [Coin]) -> SigningAccount {
         (self.acc_gen)(self.app, coins)
@@ -71,6 +139,7 @@ pub fn setup_env<'a, R: Runner<'a>>(
                 oracle_configs: oracle_config.to_vec(),
             }),
         ],
+        aggregate_metadata: false,
     };
 
     let wasm = Wasm::new(app);
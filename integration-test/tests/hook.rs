@@ -0,0 +1,119 @@
+#[allow(dead_code)]
+mod constants;
+#[allow(dead_code)]
+mod contracts;
+mod validator;
+
+use cosmwasm_std::{coin, Event, Uint128};
+use hpl_interface::{
+    core::mailbox::{self, DispatchMsg},
+    hook::aggregate::{AggregateHookQueryMsg, HooksResponse, QueryMsg},
+    igp::oracle::RemoteGasDataConfig,
+    types::AggregateMetadata,
+};
+use ibcx_test_utils::{addr, hex};
+use osmosis_test_tube::OsmosisTestApp;
+use test_tube::{Account, Module, Wasm};
+
+use crate::{constants::*, contracts::cw, validator::TestValidators};
+
+fn executed_contracts(events: &[Event]) -> Vec<String> {
+    events
+        .iter()
+        .filter(|v| v.ty == "execute")
+        .flat_map(|v| v.attributes.iter())
+        .filter(|v| v.key == "_contract_address")
+        .map(|v| v.value.clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_aggregate_hook_fan_out() -> eyre::Result<()> {
+    let oracle_config = RemoteGasDataConfig {
+        remote_domain: DOMAIN_EVM,
+        token_exchange_rate: Uint128::from(10u128.pow(4)),
+        gas_price: Uint128::from(10u128.pow(9)),
+    };
+
+    let osmo_app = OsmosisTestApp::new();
+    let osmo = cw::setup_env(
+        &osmo_app,
+        |app, coins| app.init_account(coins).unwrap(),
+        None::<&str>,
+        "osmo",
+        DOMAIN_OSMO,
+        &[TestValidators::new(DOMAIN_EVM, 5, 3)],
+        std::slice::from_ref(&oracle_config),
+    )?;
+
+    let wasm = Wasm::new(&osmo_app);
+
+    let aggregate = cw::Hook::Aggregate {
+        hooks: vec![
+            cw::Hook::Pausable {},
+            cw::Hook::Igp(cw::igp::Igp {
+                hrp: "osmo".to_string(),
+                gas_token: "uosmo".to_string(),
+                beneficiary: osmo.acc_deployer.address(),
+                oracle_configs: vec![oracle_config],
+            }),
+        ],
+        // metadata is split into a segment per sub-hook
+        aggregate_metadata: true,
+    }
+    .deploy(
+        &wasm,
+        &osmo.codes,
+        osmo.core.mailbox.clone(),
+        &osmo.acc_owner,
+        &osmo.acc_deployer,
+    )?;
+
+    let sub_hooks = wasm
+        .query::<_, HooksResponse>(
+            &aggregate,
+            &QueryMsg::AggregateHook(AggregateHookQueryMsg::Hooks {}),
+        )?
+        .hooks;
+
+    let dispatch = |metadata| {
+        wasm.execute(
+            &osmo.core.mailbox,
+            &mailbox::ExecuteMsg::Dispatch(DispatchMsg {
+                dest_domain: DOMAIN_EVM,
+                recipient_addr: [0u8; 32].to_vec().into(),
+                msg_body: b"hello world".to_vec().into(),
+                hook: Some(aggregate.clone()),
+                metadata,
+            }),
+            &[coin(56_000_000, "uosmo")],
+            &osmo.acc_tester,
+        )
+    };
+
+    // fan out to every sub-hook
+    let res = dispatch(None)?;
+
+    let executed = executed_contracts(&res.events);
+    assert!(executed.contains(&aggregate));
+    for hook in &sub_hooks {
+        assert!(executed.contains(hook), "{hook} not dispatched");
+    }
+
+    assert!(res
+        .events
+        .iter()
+        .any(|v| v.ty == "wasm-hpl_hook_aggregate::post_dispatch"));
+    assert!(res
+        .events
+        .iter()
+        .any(|v| v.ty == "wasm-igp-core-pay-for-gas"));
+
+    // a single metadata segment for two sub-hooks
+    let metadata = AggregateMetadata::new(vec![(addr(&sub_hooks[0]), hex("01"))]);
+
+    let err = dispatch(Some(metadata.into())).err().unwrap();
+    assert!(err.to_string().contains("invalid aggregate metadata"));
+
+    Ok(())
+}
@@ -4,11 +4,11 @@ mod contracts;
 mod event;
 mod validator;
 
-use cosmwasm_std::{attr, coin, Attribute, Binary, Uint128};
+use cosmwasm_std::{attr, coin, Attribute, Binary, HexBinary, Uint128};
 use ethers::{
     prelude::parse_log, providers::Middleware, signers::Signer, types::TransactionReceipt,
 };
-use ibcx_test_utils::addr;
+use ibcx_test_utils::{addr, gen_bz};
 use osmosis_test_tube::{
     osmosis_std::types::cosmwasm::wasm::v1::MsgExecuteContractResponse, Account, Module,
     OsmosisTestApp, Wasm,
@@ -17,7 +17,7 @@ use osmosis_test_tube::{
 use hpl_interface::{
     core::mailbox::{self, DispatchMsg},
     igp::oracle::RemoteGasDataConfig,
-    types::{bech32_decode, bech32_encode, bech32_to_h256, AggregateMetadata},
+    types::{bech32_decode, bech32_encode, bech32_to_h256, AggregateMetadata, Message},
 };
 use test_tube::{ExecuteResponse, Runner};
 
@@ -31,7 +31,7 @@ use crate::{
         },
     },
     event::{parse_dispatch_from_res, parse_dispatch_id_from_res},
-    validator::TestValidators,
+    validator::{Checkpoint, TestValidators},
 };
 
 fn sorted(mut attrs: Vec<Attribute>) -> Vec<Attribute> {
@@ -203,3 +203,106 @@ async fn test_mailbox_evm_to_cw() -> eyre::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_mailbox_process_multisig() -> eyre::Result<()> {
+    let osmo_app = OsmosisTestApp::new();
+    let osmo = cw::setup_env(
+        &osmo_app,
+        |app, coins| app.init_account(coins).unwrap(),
+        None::<&str>,
+        "osmo",
+        DOMAIN_OSMO,
+        &[TestValidators::new(DOMAIN_EVM, 5, 3)],
+        &[RemoteGasDataConfig {
+            remote_domain: DOMAIN_EVM,
+            token_exchange_rate: Uint128::from(10u128.pow(4)),
+            gas_price: Uint128::from(10u128.pow(9)),
+        }],
+    )?;
+
+    let validators = osmo.get_validator_set(DOMAIN_EVM)?;
+
+    let message = Message {
+        version: 3,
+        nonce: 0,
+        origin_domain: DOMAIN_EVM,
+        sender: gen_bz(32),
+        dest_domain: DOMAIN_OSMO,
+        recipient: bech32_to_h256(&osmo.core.msg_receiver)?.to_vec().into(),
+        body: b"hello world".to_vec().into(),
+    };
+
+    let checkpoint = Checkpoint {
+        origin_merkle_tree: gen_bz(32).as_slice().try_into()?,
+        merkle_root: gen_bz(32).as_slice().try_into()?,
+        merkle_index: 0,
+        message_id: message.id().as_slice().try_into()?,
+    };
+
+    let wasm = Wasm::new(osmo.app);
+    let process = |metadata: HexBinary| {
+        // default ism is routing -> aggregate -> multisig
+        let metadata = AggregateMetadata::new(vec![(addr(&osmo.core.default_ism), metadata)]);
+
+        wasm.execute(
+            &osmo.core.mailbox,
+            &mailbox::ExecuteMsg::Process {
+                metadata: metadata.into(),
+                message: message.clone().into(),
+            },
+            &[],
+            &osmo.acc_owner,
+        )
+    };
+
+    // one signature short of the threshold
+    assert!(process(validators.sign_checkpoint(&checkpoint, validators.threshold - 1)?).is_err());
+
+    let res = process(validators.sign_checkpoint(&checkpoint, validators.threshold)?)?;
+    assert!(res
+        .events
+        .iter()
+        .any(|v| v.ty == "wasm-mailbox_msg_received"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_env_addresses() -> eyre::Result<()> {
+    let osmo_app = OsmosisTestApp::new();
+    let osmo = cw::setup_env(
+        &osmo_app,
+        |app, coins| app.init_account(coins).unwrap(),
+        None::<&str>,
+        "osmo",
+        DOMAIN_OSMO,
+        &[TestValidators::new(DOMAIN_EVM, 5, 3)],
+        &[RemoteGasDataConfig {
+            remote_domain: DOMAIN_EVM,
+            token_exchange_rate: Uint128::from(10u128.pow(4)),
+            gas_price: Uint128::from(10u128.pow(9)),
+        }],
+    )?;
+
+    let addresses = osmo.addresses()?;
+
+    assert_eq!(
+        addresses.keys().cloned().collect::<Vec<_>>(),
+        vec![
+            "default_hook".to_string(),
+            "default_ism".to_string(),
+            format!("default_ism/{DOMAIN_EVM}"),
+            format!("default_ism/{DOMAIN_EVM}/multisig"),
+            "mailbox".to_string(),
+            "msg_receiver".to_string(),
+            "required_hook".to_string(),
+            "required_hook/igp".to_string(),
+            "required_hook/merkle".to_string(),
+        ]
+    );
+    assert_eq!(addresses["mailbox"], osmo.core.mailbox);
+    assert!(addresses.values().all(|v| v.starts_with("osmo1")));
+
+    Ok(())
+}
@@ -7,7 +7,8 @@ use ethers::utils::hex::FromHex;
 use hpl_interface::{
     ism::multisig::ValidatorSet,
     types::{
-        bech32_encode, eth_addr, eth_hash, pub_to_addr, Message, MessageIdMultisigIsmMetadata,
+        bech32_encode, domain_hash, eth_addr, eth_hash, multisig_hash, pub_to_addr, Message,
+        MessageIdMultisigIsmMetadata,
     },
 };
 use ibcx_test_utils::{addr, gen_bz};
@@ -71,6 +72,29 @@ impl TestValidator {
     }
 }
 
+/// checkpoint of the origin merkle tree hook signed by the validators
+#[derive(Clone)]
+pub struct Checkpoint {
+    pub origin_merkle_tree: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub merkle_index: u32,
+    pub message_id: [u8; 32],
+}
+
+#[allow(dead_code)]
+impl Checkpoint {
+    pub fn digest(&self, origin_domain: u32) -> eyre::Result<[u8; 32]> {
+        let multisig_hash = multisig_hash(
+            domain_hash(origin_domain, self.origin_merkle_tree.to_vec().into()).to_vec(),
+            self.merkle_root.to_vec(),
+            self.merkle_index,
+            self.message_id.to_vec(),
+        );
+
+        Ok(eth_hash(multisig_hash)?.as_slice().try_into()?)
+    }
+}
+
 #[derive(Clone)]
 pub struct TestValidators {
     pub domain: u32,
@@ -133,6 +157,23 @@ impl TestValidators {
         signatures.clone()
     }
 
+    /// message id multisig ism metadata signed by the first `num` validators.
+    /// origin merkle tree (32) | merkle root (32) | merkle index (4) | signatures (65 each)
+    #[allow(dead_code)]
+    pub fn sign_checkpoint(&self, checkpoint: &Checkpoint, num: u8) -> eyre::Result<HexBinary> {
+        let signatures = self.sign(num, checkpoint.digest(self.domain)?);
+
+        Ok(checkpoint
+            .origin_merkle_tree
+            .iter()
+            .chain(checkpoint.merkle_root.iter())
+            .chain(checkpoint.merkle_index.to_be_bytes().iter())
+            .chain(signatures.iter().flat_map(|v| v.as_slice()))
+            .cloned()
+            .collect::<Vec<u8>>()
+            .into())
+    }
+
     pub fn make_metadata(
         &self,
         origin_merkle_tree: Address,
@@ -144,19 +185,18 @@ impl TestValidators {
         let mut addr = [0u8; 32];
         addr[32 - origin_merkle_tree.0.len()..].copy_from_slice(&origin_merkle_tree.0);
 
-        let multisig_hash = hpl_ism_multisig::multisig_hash(
-            hpl_ism_multisig::domain_hash(self.domain, addr.to_vec().into())?.to_vec(),
-            merkle_root.to_vec(),
+        let digest = Checkpoint {
+            origin_merkle_tree: addr,
+            merkle_root,
             merkle_index,
-            message_id.to_vec(),
-        )?;
-
-        let hashed_message = eth_hash(multisig_hash)?;
+            message_id,
+        }
+        .digest(self.domain)?;
 
         let signatures = if is_passed {
-            self.sign(self.threshold, hashed_message.as_slice().try_into()?)
+            self.sign(self.threshold, digest)
         } else {
-            self.sign(self.threshold - 1, hashed_message.as_slice().try_into()?)
+            self.sign(self.threshold - 1, digest)
         };
 
         Ok(MessageIdMultisigIsmMetadata {
@@ -208,8 +248,78 @@ fn test_validator() {
         )
         .unwrap();
 
-    let res =
-        hpl_ism_multisig::query::verify_message(deps.as_ref(), metadata.into(), message.into())
-            .unwrap();
+    let res = hpl_ism_multisig::query::verify_message(
+        deps.as_ref(),
+        mock_env(),
+        metadata.into(),
+        message.into(),
+    )
+    .unwrap();
+    assert!(res.verified);
+}
+
+#[test]
+fn test_sign_checkpoint() {
+    let owner = addr("owner");
+    let validators = TestValidators::new(2, 5, 3);
+
+    let mut deps = mock_dependencies();
+
+    hpl_ownable::initialize(deps.as_mut().storage, &owner).unwrap();
+
+    hpl_ism_multisig::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(owner.as_str(), &[]),
+        hpl_interface::ism::multisig::ExecuteMsg::SetValidators {
+            domain: validators.domain,
+            threshold: validators.threshold,
+            validators: validators
+                .to_set()
+                .iter()
+                .map(|v| v.validator.clone())
+                .collect(),
+        },
+    )
+    .unwrap();
+
+    let mut message: Message = gen_bz(100).into();
+    message.origin_domain = validators.domain;
+
+    let checkpoint = Checkpoint {
+        origin_merkle_tree: gen_bz(32).as_slice().try_into().unwrap(),
+        merkle_root: gen_bz(32).as_slice().try_into().unwrap(),
+        merkle_index: 7,
+        message_id: message.id().as_slice().try_into().unwrap(),
+    };
+
+    // the raw bytes must match the layout the ism parses
+    let metadata = validators.sign_checkpoint(&checkpoint, 3).unwrap();
+    let parsed: MessageIdMultisigIsmMetadata = metadata.clone().into();
+    assert_eq!(
+        parsed.origin_merkle_tree.as_slice(),
+        checkpoint.origin_merkle_tree
+    );
+    assert_eq!(parsed.merkle_root.as_slice(), checkpoint.merkle_root);
+    assert_eq!(parsed.merkle_index(), checkpoint.merkle_index);
+    assert_eq!(parsed.signatures.len(), 3);
+
+    let res = hpl_ism_multisig::query::verify_message(
+        deps.as_ref(),
+        mock_env(),
+        metadata,
+        message.clone().into(),
+    )
+    .unwrap();
     assert!(res.verified);
+
+    let metadata = validators.sign_checkpoint(&checkpoint, 2).unwrap();
+    let res = hpl_ism_multisig::query::verify_message(
+        deps.as_ref(),
+        mock_env(),
+        metadata,
+        message.into(),
+    )
+    .unwrap();
+    assert!(!res.verified);
 }
@@ -1,7 +1,8 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{wasm_execute, Addr, Api, Coin, CosmosMsg, HexBinary, StdResult};
+use cosmwasm_std::{
+    wasm_execute, Addr, Api, Coin, CosmosMsg, CustomQuery, HexBinary, QuerierWrapper, StdResult,
+};
 
-#[allow(unused_imports)]
 use crate::{
     hook::QuoteDispatchResponse,
     ownable::{OwnableMsg, OwnableQueryMsg},
@@ -83,10 +84,26 @@ pub enum ExecuteMsg {
     Ownable(OwnableMsg),
 
     // Mailbox
+    /// sets the default ism right away if none is set yet. otherwise schedules
+    /// the change, which can be applied with `ApplyDefaultIsm` after the delay
     SetDefaultIsm {
         ism: String,
     },
 
+    ApplyDefaultIsm {},
+
+    /// drops the scheduled default ism change
+    CancelDefaultIsm {},
+
+    /// delay in seconds between scheduling and applying a default ism change.
+    /// increases apply right away. decreases are scheduled behind the current delay
+    /// and applied with `ApplyDefaultIsmDelay`
+    SetDefaultIsmDelay {
+        delay: u64,
+    },
+
+    ApplyDefaultIsmDelay {},
+
     SetDefaultHook {
         hook: String,
     },
@@ -95,6 +112,19 @@ pub enum ExecuteMsg {
         hook: String,
     },
 
+    /// fee charged to the relayer on every `Process`. `None` makes processing free
+    SetProcessFee {
+        fee: Option<Coin>,
+    },
+
+    /// sends the collected process fees to the owner
+    ClaimProcessFees {},
+
+    /// keeps every dispatched message around for the `Dispatched` query. off by default
+    SetStoreDispatches {
+        enabled: bool,
+    },
+
     Dispatch(DispatchMsg),
 
     Process {
@@ -126,6 +156,41 @@ pub fn dispatch(
     .into())
 }
 
+pub fn quote_dispatch<C: CustomQuery>(
+    querier: &QuerierWrapper<C>,
+    mailbox: impl Into<String>,
+    sender: impl Into<String>,
+    msg: DispatchMsg,
+) -> StdResult<QuoteDispatchResponse> {
+    querier.query_wasm_smart(
+        mailbox,
+        &QueryMsg::Hook(MailboxHookQueryMsg::QuoteDispatch {
+            sender: sender.into(),
+            msg,
+        }),
+    )
+}
+
+pub fn nonce<C: CustomQuery>(
+    querier: &QuerierWrapper<C>,
+    mailbox: impl Into<String>,
+) -> StdResult<u32> {
+    let res: NonceResponse =
+        querier.query_wasm_smart(mailbox, &MailboxQueryMsg::Nonce {}.wrap())?;
+
+    Ok(res.nonce)
+}
+
+pub fn latest_process_id<C: CustomQuery>(
+    querier: &QuerierWrapper<C>,
+    mailbox: impl Into<String>,
+) -> StdResult<HexBinary> {
+    let res: LatestProcessedIdResponse =
+        querier.query_wasm_smart(mailbox, &MailboxQueryMsg::LatestProcessId {}.wrap())?;
+
+    Ok(res.message_id)
+}
+
 pub fn process(mailbox: impl Into<String>, metadata: HexBinary, message: HexBinary) -> CosmosMsg {
     wasm_execute(mailbox, &ExecuteMsg::Process { metadata, message }, vec![])
         .unwrap()
@@ -172,6 +237,9 @@ pub enum MailboxQueryMsg {
     #[returns(DefaultIsmResponse)]
     DefaultIsm {},
 
+    #[returns(PendingDefaultIsmResponse)]
+    PendingDefaultIsm {},
+
     #[returns(DefaultHookResponse)]
     DefaultHook {},
 
@@ -181,11 +249,28 @@ pub enum MailboxQueryMsg {
     #[returns(NonceResponse)]
     Nonce {},
 
+    #[returns(ProcessFeeResponse)]
+    ProcessFee {},
+
     #[returns(RecipientIsmResponse)]
     RecipientIsm { recipient_addr: String },
 
+    /// isms that verify a message from `origin` to `recipient`, in the order they are
+    /// reached. routing isms are followed to their route, aggregation isms expand to their set
+    #[returns(SecurityPathResponse)]
+    SecurityPath { origin: u32, recipient: String },
+
     #[returns(LatestDispatchedIdResponse)]
     LatestDispatchId {},
+
+    /// id of the message most recently passed to a recipient's `Handle`
+    #[returns(LatestProcessedIdResponse)]
+    LatestProcessId {},
+
+    /// payload of the dispatch event emitted for the message with `nonce`.
+    /// only messages dispatched while `SetStoreDispatches` is on are kept
+    #[returns(DispatchedResponse)]
+    Dispatched { nonce: u32 },
 }
 impl MailboxQueryMsg {
     pub fn wrap(self) -> QueryMsg {
@@ -213,6 +298,17 @@ pub struct DefaultIsmResponse {
     pub default_ism: String,
 }
 
+#[cw_serde]
+pub struct PendingDefaultIsmResponse {
+    pub ism: Option<String>,
+    /// unix timestamp in seconds from which the pending ism can be applied
+    pub effective_at: Option<u64>,
+    pub delay: u64,
+    pub pending_delay: Option<u64>,
+    /// unix timestamp in seconds from which the pending delay can be applied
+    pub pending_delay_effective_at: Option<u64>,
+}
+
 #[cw_serde]
 pub struct DefaultHookResponse {
     pub default_hook: String,
@@ -228,12 +324,36 @@ pub struct RecipientIsmResponse {
     pub ism: String,
 }
 
+#[cw_serde]
+pub struct SecurityPathResponse {
+    pub isms: Vec<String>,
+}
+
 #[cw_serde]
 pub struct NonceResponse {
     pub nonce: u32,
 }
 
+#[cw_serde]
+pub struct ProcessFeeResponse {
+    pub fee: Option<Coin>,
+}
+
 #[cw_serde]
 pub struct LatestDispatchedIdResponse {
     pub message_id: HexBinary,
 }
+
+#[cw_serde]
+pub struct LatestProcessedIdResponse {
+    pub message_id: HexBinary,
+}
+
+#[cw_serde]
+pub struct DispatchedResponse {
+    pub message_id: HexBinary,
+    pub sender: HexBinary,
+    pub destination: u32,
+    pub recipient: HexBinary,
+    pub message: HexBinary,
+}
@@ -1,4 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::HexBinary;
 
 use crate::ownable::{OwnableMsg, OwnableQueryMsg};
 
@@ -10,13 +11,46 @@ pub const TREE_DEPTH: usize = 32;
 pub struct InstantiateMsg {
     pub owner: String,
     pub hooks: Vec<String>,
+    /// best-effort mode. keep dispatching to the remaining hooks when one of them fails,
+    /// skipping hooks that are no longer contracts. strict (default) aborts on any failure
+    #[serde(default)]
+    pub continue_on_error: bool,
+    /// forward funds attached to post_dispatch to the sub-hooks. when off, post_dispatch
+    /// rejects attached funds instead of trapping them in the contract
+    #[serde(default = "default_forward_funds")]
+    pub forward_funds: bool,
+    /// accept an empty hook list at instantiate and on `SetHooks`, which turns
+    /// post_dispatch into a no-op. off by default
+    #[serde(default)]
+    pub allow_empty: bool,
+    /// decode post_dispatch metadata as `AggregateMetadata` and forward each sub-hook its
+    /// own segment. off by default, forwarding the metadata to every sub-hook as is
+    #[serde(default)]
+    pub aggregate_metadata: bool,
+}
+
+fn default_forward_funds() -> bool {
+    true
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
     Ownable(OwnableMsg),
     PostDispatch(PostDispatchMsg),
-    SetHooks { hooks: Vec<String> },
+    SetHooks {
+        hooks: Vec<String>,
+    },
+    SetContinueOnError {
+        continue_on_error: bool,
+    },
+    SetForwardFunds {
+        forward_funds: bool,
+    },
+    /// static metadata prepended to the metadata forwarded to `hook`. empty to unset
+    SetHookMetadata {
+        hook: String,
+        metadata: HexBinary,
+    },
 }
 
 #[cw_serde]
@@ -33,6 +67,24 @@ pub enum QueryMsg {
 pub enum AggregateHookQueryMsg {
     #[returns(HooksResponse)]
     Hooks {},
+
+    #[returns(ContinueOnErrorResponse)]
+    ContinueOnError {},
+
+    #[returns(ForwardFundsResponse)]
+    ForwardFunds {},
+
+    #[returns(HookMetadataResponse)]
+    HookMetadata { hook: String },
+
+    /// distinct denoms the sub-hooks quote in for `message`. defaults to a blank message
+    /// to domain 0. sub-hooks that can't quote the message are left out
+    #[returns(QuoteDenomsResponse)]
+    QuoteDenoms { message: Option<HexBinary> },
+
+    /// outcome of the latest continue-on-error dispatch of each sub-hook
+    #[returns(LastOutcomesResponse)]
+    LastOutcomes {},
 }
 
 #[cw_serde]
@@ -40,10 +92,47 @@ pub struct HooksResponse {
     pub hooks: Vec<String>,
 }
 
+#[cw_serde]
+pub struct ContinueOnErrorResponse {
+    pub continue_on_error: bool,
+}
+
+#[cw_serde]
+pub struct ForwardFundsResponse {
+    pub forward_funds: bool,
+}
+
+#[cw_serde]
+pub struct HookMetadataResponse {
+    pub metadata: HexBinary,
+}
+
+#[cw_serde]
+pub struct QuoteDenomsResponse {
+    pub denoms: Vec<String>,
+}
+
+#[cw_serde]
+pub struct LastOutcome {
+    pub hook: String,
+    pub success: bool,
+    pub height: u64,
+}
+
+#[cw_serde]
+pub struct LastOutcomesResponse {
+    pub outcomes: Vec<LastOutcome>,
+}
+
+#[cw_serde]
+pub struct HookOutcome {
+    pub hook: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
 #[cfg(test)]
 mod test {
-    use cosmwasm_std::HexBinary;
-
     use super::*;
     use crate::{
         hook::{ExpectedHookQueryMsg, PostDispatchMsg, QuoteDispatchMsg},
@@ -43,7 +43,15 @@ pub enum QueryMsg {
 #[derive(QueryResponses)]
 pub enum FeeHookQueryMsg {
     #[returns(FeeResponse)]
-    Fee {}
+    Fee {},
+
+    /// fees collected since the last claim
+    #[returns(CollectedResponse)]
+    Collected {},
+
+    /// fees collected over the lifetime of the hook, claims don't reset it
+    #[returns(TotalCollectedEverResponse)]
+    TotalCollectedEver {},
 }
 
 #[cw_serde]
@@ -51,6 +59,16 @@ pub struct FeeResponse {
     pub fee: Coin,
 }
 
+#[cw_serde]
+pub struct CollectedResponse {
+    pub collected: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct TotalCollectedEverResponse {
+    pub total: Vec<Coin>,
+}
+
 #[cfg(test)]
 mod test {
     use cosmwasm_std::HexBinary;
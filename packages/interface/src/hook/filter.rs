@@ -0,0 +1,87 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+
+use crate::ownable::{OwnableMsg, OwnableQueryMsg};
+
+use super::{HookQueryMsg, PostDispatchMsg};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub owner: String,
+    pub hook: String,
+    #[serde(default)]
+    pub blocked_domains: Vec<u32>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    Ownable(OwnableMsg),
+    PostDispatch(PostDispatchMsg),
+    SetHook {
+        hook: String,
+    },
+    /// messages to blocked destinations are dropped instead of being forwarded
+    SetBlocked {
+        domain: u32,
+        blocked: bool,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+#[query_responses(nested)]
+pub enum QueryMsg {
+    Ownable(OwnableQueryMsg),
+    Hook(HookQueryMsg),
+    FilterHook(FilterHookQueryMsg),
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum FilterHookQueryMsg {
+    #[returns(InnerHookResponse)]
+    InnerHook {},
+
+    #[returns(BlockedDomainsResponse)]
+    BlockedDomains {},
+}
+
+#[cw_serde]
+pub struct InnerHookResponse {
+    pub hook: String,
+}
+
+#[cw_serde]
+pub struct BlockedDomainsResponse {
+    pub domains: Vec<u32>,
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::HexBinary;
+
+    use super::*;
+    use crate::{
+        hook::{ExpectedHookQueryMsg, PostDispatchMsg, QuoteDispatchMsg},
+        msg_checker,
+    };
+
+    #[test]
+    fn test_hook_interface() {
+        let _checked: ExecuteMsg = msg_checker(
+            PostDispatchMsg {
+                metadata: HexBinary::default(),
+                message: HexBinary::default(),
+            }
+            .wrap(),
+        );
+
+        let _checked: QueryMsg = msg_checker(ExpectedHookQueryMsg::Hook(HookQueryMsg::Mailbox {}));
+        let _checked: QueryMsg = msg_checker(
+            QuoteDispatchMsg {
+                metadata: HexBinary::default(),
+                message: HexBinary::default(),
+            }
+            .request(),
+        );
+    }
+}
@@ -1,24 +1,43 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::HexBinary;
 
+use crate::ownable::{OwnableMsg, OwnableQueryMsg};
+
 use super::{HookQueryMsg, PostDispatchMsg};
 
 pub const TREE_DEPTH: usize = 32;
 
 #[cw_serde]
 pub struct InstantiateMsg {
+    pub owner: String,
     pub mailbox: String,
+    /// parent hooks (aggregate, routing, ...) allowed to post_dispatch besides the mailbox
+    #[serde(default)]
+    pub trusted_hooks: Vec<String>,
+}
+
+/// hooks deployed before post_dispatch was restricted have no owner to manage the
+/// trusted hooks. both fields are optional for hooks that already have one
+#[cw_serde]
+pub struct MigrateMsg {
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub trusted_hooks: Vec<String>,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
+    Ownable(OwnableMsg),
     PostDispatch(PostDispatchMsg),
+    SetTrustedHook { hook: String, trusted: bool },
 }
 
 #[cw_serde]
 #[derive(QueryResponses)]
 #[query_responses(nested)]
 pub enum QueryMsg {
+    Ownable(OwnableQueryMsg),
     Hook(HookQueryMsg),
     MerkleHook(MerkleHookQueryMsg),
 }
@@ -40,6 +59,17 @@ pub enum MerkleHookQueryMsg {
 
     #[returns(CheckPointResponse)]
     CheckPoint {},
+
+    #[returns(CheckpointDigestPreimageResponse)]
+    CheckpointDigestPreimage {},
+
+    #[returns(TrustedHooksResponse)]
+    TrustedHooks {},
+}
+
+#[cw_serde]
+pub struct TrustedHooksResponse {
+    pub hooks: Vec<String>,
 }
 
 #[cw_serde]
@@ -69,6 +99,13 @@ pub struct CheckPointResponse {
     pub count: u32,
 }
 
+/// `domain_hash || root || index` of the latest checkpoint.
+/// Validators sign `eth_hash(keccak256(preimage || message_id))`
+#[cw_serde]
+pub struct CheckpointDigestPreimageResponse {
+    pub preimage: HexBinary,
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,4 +1,5 @@
 pub mod aggregate;
+pub mod filter;
 pub mod merkle;
 pub mod pausable;
 pub mod routing;
@@ -54,6 +55,9 @@ pub enum HookQueryMsg {
 
     #[returns(MailboxResponse)]
     Mailbox {},
+
+    #[returns(MetadataFormatResponse)]
+    MetadataFormat {},
 }
 
 #[cw_serde]
@@ -73,6 +77,35 @@ pub struct QuoteDispatchResponse {
     pub fees: Vec<Coin>,
 }
 
+#[cw_serde]
+pub enum MetadataFormat {
+    /// metadata is ignored
+    None,
+    /// forwarded as-is to the hook selected for the message
+    Passthrough,
+    /// `AggregateMetadata` with a segment per sub-hook
+    Aggregate,
+    /// `IGPMetadata`. a big-endian u256 gas limit followed by an optional 20 or 32 byte
+    /// refund address. shorter metadata falls back to the default gas
+    Igp,
+}
+
+#[cw_serde]
+pub struct MetadataFormatResponse {
+    pub format: MetadataFormat,
+    /// metadata lengths in bytes the hook interprets. empty if the length is unconstrained
+    pub lengths: Vec<u32>,
+}
+
+impl From<MetadataFormat> for MetadataFormatResponse {
+    fn from(format: MetadataFormat) -> Self {
+        Self {
+            format,
+            lengths: vec![],
+        }
+    }
+}
+
 pub fn post_dispatch(
     hook: impl Into<String>,
     metadata: impl Into<HexBinary>,
@@ -4,6 +4,7 @@ use cosmwasm_std::Addr;
 use crate::{
     ownable::{OwnableMsg, OwnableQueryMsg},
     router::{RouterMsg, RouterQuery},
+    EventVerbosity,
 };
 
 use super::{HookQueryMsg, PostDispatchMsg};
@@ -18,6 +19,7 @@ pub enum ExecuteMsg {
     Ownable(OwnableMsg),
     PostDispatch(PostDispatchMsg),
     Router(RouterMsg<Addr>),
+    SetEventVerbosity { verbosity: EventVerbosity },
 }
 
 #[cw_serde]
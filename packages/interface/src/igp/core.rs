@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, HexBinary, Uint256};
+use cosmwasm_std::{Addr, Coin, HexBinary, Uint128, Uint256};
 
 use crate::{
     hook::{HookQueryMsg, PostDispatchMsg},
@@ -64,6 +64,15 @@ pub enum ExecuteMsg {
     SetBeneficiary {
         beneficiary: String,
     },
+    // overpayment below this threshold is retained for the beneficiary instead of refunded
+    SetMinRefund {
+        min_refund: Uint128,
+    },
+    // quotes never use a gas price below this floor. None to unset
+    SetMinGasPrice {
+        domain: u32,
+        min_gas_price: Option<Uint128>,
+    },
     PayForGas {
         message_id: HexBinary,
         dest_domain: u32,
@@ -106,11 +115,24 @@ pub enum IgpQueryMsg {
     #[returns(BeneficiaryResponse)]
     Beneficiary {},
 
+    #[returns(MinRefundResponse)]
+    MinRefund {},
+
+    #[returns(MinGasPriceResponse)]
+    MinGasPrice { domain: u32 },
+
     #[returns(QuoteGasPaymentResponse)]
     QuoteGasPayment {
         dest_domain: u32,
         gas_amount: Uint256,
     },
+
+    #[returns(QuoteRefundResponse)]
+    QuoteRefund {
+        dest_domain: u32,
+        gas_limit: Uint256,
+        paid: Vec<Coin>,
+    },
 }
 
 impl IgpQueryMsg {
@@ -134,11 +156,26 @@ pub struct BeneficiaryResponse {
     pub beneficiary: String,
 }
 
+#[cw_serde]
+pub struct MinRefundResponse {
+    pub min_refund: Uint128,
+}
+
+#[cw_serde]
+pub struct MinGasPriceResponse {
+    pub min_gas_price: Option<Uint128>,
+}
+
 #[cw_serde]
 pub struct QuoteGasPaymentResponse {
     pub gas_needed: Uint256,
 }
 
+#[cw_serde]
+pub struct QuoteRefundResponse {
+    pub refund: Vec<Coin>,
+}
+
 #[cfg(test)]
 mod test {
     use cosmwasm_std::HexBinary;
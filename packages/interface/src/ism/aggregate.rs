@@ -1,4 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::HexBinary;
 
 use crate::ownable::{OwnableMsg, OwnableQueryMsg};
 
@@ -34,6 +35,17 @@ pub enum QueryMsg {
 pub enum AggregateIsmQueryMsg {
     #[returns(IsmsResponse)]
     Isms {},
+
+    /// full security configuration of the ism
+    #[returns(ConfigResponse)]
+    Config {},
+
+    /// how many sub-isms pass for the given metadata, even below the threshold
+    #[returns(ProgressResponse)]
+    Progress {
+        metadata: HexBinary,
+        message: HexBinary,
+    },
 }
 
 #[cw_serde]
@@ -42,10 +54,23 @@ pub struct IsmsResponse {
     pub threshold: u8,
 }
 
+#[cw_serde]
+pub struct ConfigResponse {
+    pub owner: String,
+    pub isms: Vec<String>,
+    pub threshold: u8,
+}
+
+#[cw_serde]
+pub struct ProgressResponse {
+    pub passed: u8,
+    pub threshold: u8,
+    /// additional sub-isms that must pass to meet the threshold
+    pub needed: u8,
+}
+
 #[cfg(test)]
 mod test {
-    use cosmwasm_std::HexBinary;
-
     use super::*;
     use crate::{ism::IsmQueryMsg, msg_checker};
 
@@ -3,13 +3,20 @@ use cosmwasm_std::HexBinary;
 
 use crate::ownable::{OwnableMsg, OwnableQueryMsg};
 
-use super::IsmQueryMsg;
+use super::{IsmQueryMsg, IsmType};
 #[allow(unused_imports)]
 use super::{ModuleTypeResponse, VerifyInfoResponse, VerifyResponse};
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub owner: String,
+    /// metadata format the ism verifies. `MessageIdMultisig` (default) or `MerkleRootMultisig`
+    #[serde(default = "default_module_type")]
+    pub module_type: IsmType,
+}
+
+fn default_module_type() -> IsmType {
+    IsmType::MessageIdMultisig
 }
 
 #[cw_serde]
@@ -30,6 +37,15 @@ pub enum ExecuteMsg {
     UnsetDomain {
         domain: u32,
     },
+    ScheduleValidators {
+        domain: u32,
+        threshold: u8,
+        validators: Vec<HexBinary>,
+        effective_at: u64, // unix timestamp in seconds
+    },
+    CancelScheduledValidators {
+        domain: u32,
+    },
 }
 
 #[cw_serde]
@@ -46,6 +62,9 @@ pub enum QueryMsg {
 pub enum MultisigIsmQueryMsg {
     #[returns(EnrolledValidatorsResponse)]
     EnrolledValidators { domain: u32 },
+
+    #[returns(ScheduledValidatorsResponse)]
+    ScheduledValidators { domain: u32 },
 }
 
 #[cw_serde]
@@ -54,6 +73,13 @@ pub struct EnrolledValidatorsResponse {
     pub threshold: u8,
 }
 
+#[cw_serde]
+pub struct ScheduledValidatorsResponse {
+    pub validators: Vec<HexBinary>,
+    pub threshold: u8,
+    pub effective_at: u64,
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
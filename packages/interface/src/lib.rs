@@ -16,20 +16,14 @@ pub mod router;
 pub mod types;
 pub mod warp;
 
-pub use macros::*;
-
 #[cw_serde]
+#[derive(Default)]
 pub enum Order {
+    #[default]
     Asc,
     Desc,
 }
 
-impl Default for Order {
-    fn default() -> Self {
-        Self::Asc
-    }
-}
-
 impl From<Order> for cosmwasm_std::Order {
     fn from(v: Order) -> Self {
         match v {
@@ -39,6 +33,31 @@ impl From<Order> for cosmwasm_std::Order {
     }
 }
 
+/// How many attributes hot path events carry. Levels are ordered, so
+/// `verbosity >= EventVerbosity::Standard` reads as "at least standard"
+#[cw_serde]
+#[derive(Default, Copy, Eq, PartialOrd, Ord)]
+pub enum EventVerbosity {
+    Minimal,
+    #[default]
+    Standard,
+    Verbose,
+}
+
+impl std::fmt::Display for EventVerbosity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Minimal => "minimal",
+                Self::Standard => "standard",
+                Self::Verbose => "verbose",
+            }
+        )
+    }
+}
+
 // Settings for pagination
 pub const MAX_LIMIT: u32 = 30;
 pub const DEFAULT_LIMIT: u32 = 10;
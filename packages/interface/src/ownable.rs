@@ -1,11 +1,17 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Timestamp};
 
 #[cw_serde]
 pub enum OwnableMsg {
-    InitOwnershipTransfer { next_owner: String },
+    InitOwnershipTransfer {
+        next_owner: String,
+    },
     RevokeOwnershipTransfer {},
     ClaimOwnership {},
+    /// minimum seconds between owner-gated config changes. can only be set once
+    SetCooldown {
+        cooldown: u64,
+    },
 }
 
 #[cw_serde]
@@ -16,6 +22,9 @@ pub enum OwnableQueryMsg {
 
     #[returns(PendingOwnerResponse)]
     GetPendingOwner {},
+
+    #[returns(CooldownResponse)]
+    GetCooldown {},
 }
 
 #[cw_serde]
@@ -27,3 +36,9 @@ pub struct OwnerResponse {
 pub struct PendingOwnerResponse {
     pub pending_owner: Option<Addr>,
 }
+
+#[cw_serde]
+pub struct CooldownResponse {
+    pub cooldown: Option<u64>,
+    pub last_change: Option<Timestamp>,
+}
@@ -1,8 +1,9 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Timestamp;
 
 #[cw_serde]
 pub enum PausableMsg {
-    Pause {},
+    Pause { reason: Option<String> },
     Release {},
 }
 
@@ -11,9 +12,19 @@ pub enum PausableMsg {
 pub enum PausableQueryMsg {
     #[returns(PauseInfoResponse)]
     PauseInfo {},
+
+    #[returns(PauseStatusResponse)]
+    PauseStatus {},
 }
 
 #[cw_serde]
 pub struct PauseInfoResponse {
     pub paused: bool,
 }
+
+#[cw_serde]
+pub struct PauseStatusResponse {
+    pub paused: bool,
+    pub reason: Option<String>,
+    pub paused_at: Option<Timestamp>,
+}
@@ -1,6 +1,7 @@
 use std::marker::PhantomData;
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Binary, Timestamp};
 
 use crate::Order;
 
@@ -12,8 +13,17 @@ pub struct DomainRouteSet<T> {
 
 #[cw_serde]
 pub enum RouterMsg<T> {
-    SetRoute { set: DomainRouteSet<T> },
-    SetRoutes { set: Vec<DomainRouteSet<T>> },
+    SetRoute {
+        set: DomainRouteSet<T>,
+    },
+    SetRoutes {
+        set: Vec<DomainRouteSet<T>>,
+    },
+    /// bulk-loads a blob produced by `RouterQuery::ExportRoutes`
+    ImportRoutes {
+        blob: Binary,
+        overwrite: bool,
+    },
 }
 
 #[cw_serde]
@@ -32,6 +42,12 @@ pub enum RouterQuery<T> {
         order: Option<Order>,
     },
 
+    #[returns(ExportRoutesResponse)]
+    ExportRoutes {},
+
+    #[returns(RouteInfoResponse<T>)]
+    RouteInfo { domain: u32 },
+
     #[serde(skip)]
     #[returns(cosmwasm_std::Empty)]
     Placeholder(PhantomData<T>),
@@ -51,3 +67,22 @@ pub struct RouteResponse<T> {
 pub struct RoutesResponse<T> {
     pub routes: Vec<DomainRouteSet<T>>,
 }
+
+#[cw_serde]
+pub struct RouteModification {
+    pub modified_at: Timestamp,
+    pub modified_by: Addr,
+}
+
+#[cw_serde]
+pub struct RouteInfoResponse<T> {
+    pub route: DomainRouteSet<T>,
+    /// none if the route was never changed through `RouterMsg`
+    pub last_modified: Option<RouteModification>,
+}
+
+#[cw_serde]
+pub struct ExportRoutesResponse {
+    /// json encoded `Vec<DomainRouteSet<T>>` of the whole route table
+    pub blob: Binary,
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::{HexBinary, StdError, StdResult};
+use cosmwasm_std::{HexBinary, StdResult};
 
 const PREFIX: &str = "\x19Ethereum Signed Message:\n";
 
@@ -12,6 +12,33 @@ pub fn keccak256_hash(bz: &[u8]) -> HexBinary {
     hash.into()
 }
 
+/// Domain separator of checkpoints signed by validators.
+pub fn domain_hash(local_domain: u32, address: HexBinary) -> HexBinary {
+    let mut bz = vec![];
+    bz.append(&mut local_domain.to_be_bytes().to_vec());
+    bz.append(&mut address.to_vec());
+    bz.append(&mut "HYPERLANE".as_bytes().to_vec());
+
+    keccak256_hash(&bz)
+}
+
+/// Checkpoint digest validators sign, before the ethereum message prefix is applied.
+pub fn multisig_hash(
+    mut domain_hash: Vec<u8>,
+    mut root: Vec<u8>,
+    index: u32,
+    mut message_id: Vec<u8>,
+) -> HexBinary {
+    let mut bz = vec![];
+
+    bz.append(&mut domain_hash);
+    bz.append(&mut root);
+    bz.append(&mut index.to_be_bytes().to_vec());
+    bz.append(&mut message_id);
+
+    keccak256_hash(&bz)
+}
+
 pub fn eth_hash(message: HexBinary) -> StdResult<HexBinary> {
     let mut eth_message = format!("{PREFIX}{}", message.len()).into_bytes();
     eth_message.extend_from_slice(&message);
@@ -37,11 +64,7 @@ pub fn sha256_digest(bz: impl AsRef<[u8]>) -> StdResult<[u8; 32]> {
 
     hasher.update(bz);
 
-    hasher
-        .finalize()
-        .as_slice()
-        .try_into()
-        .map_err(|_| StdError::generic_err("wrong length"))
+    Ok(hasher.finalize().into())
 }
 
 pub fn ripemd160_digest(bz: impl AsRef<[u8]>) -> StdResult<[u8; 20]> {
@@ -51,11 +74,7 @@ pub fn ripemd160_digest(bz: impl AsRef<[u8]>) -> StdResult<[u8; 20]> {
 
     hasher.update(bz);
 
-    hasher
-        .finalize()
-        .as_slice()
-        .try_into()
-        .map_err(|_| StdError::generic_err("wrong length"))
+    Ok(hasher.finalize().into())
 }
 
 pub fn pub_to_addr(pub_key: HexBinary) -> StdResult<HexBinary> {
@@ -1,9 +1,13 @@
 use std::collections::BTreeMap;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, HexBinary, Uint256};
+use cosmwasm_std::{Addr, HexBinary, StdError, StdResult, Uint256};
 
-use super::bech32_encode;
+use super::{
+    bech32_encode,
+    merkle::{HASH_LENGTH, TREE_DEPTH},
+    MerkleTree,
+};
 
 const SIGNATURE_LENGTH: usize = 65;
 
@@ -11,11 +15,13 @@ const SIGNATURE_LENGTH: usize = 65;
 pub struct MerkleRootMultisigIsmMetadata {
     pub origin_mailbox: HexBinary,
     // bytes32
-    pub checkpoint_index: u32,
+    pub message_index: u32,
+    // leaf index of the message in the origin merkle tree
     pub message_id: HexBinary,
     // bytes32
     pub proof: HexBinary,
     // bytes32[32]
+    pub checkpoint_index: u32,
     pub signatures: HexBinary, // threshold * 65
 }
 
@@ -24,9 +30,10 @@ impl From<MerkleRootMultisigIsmMetadata> for HexBinary {
         v.origin_mailbox
             .to_vec()
             .iter()
-            .chain(v.checkpoint_index.to_be_bytes().iter())
+            .chain(v.message_index.to_be_bytes().iter())
             .chain(v.message_id.to_vec().iter())
             .chain(v.proof.to_vec().iter())
+            .chain(v.checkpoint_index.to_be_bytes().iter())
             .chain(v.signatures.to_vec().iter())
             .cloned()
             .collect::<Vec<u8>>()
@@ -38,17 +45,18 @@ impl From<HexBinary> for MerkleRootMultisigIsmMetadata {
     fn from(v: HexBinary) -> Self {
         Self {
             origin_mailbox: v[0..32].to_vec().into(),
-            checkpoint_index: u32::from_be_bytes(v[32..36].try_into().unwrap()),
+            message_index: u32::from_be_bytes(v[32..36].try_into().unwrap()),
             message_id: v[36..68].to_vec().into(),
             proof: v[68..1092].to_vec().into(),
-            signatures: v[1092..].to_vec().into(),
+            checkpoint_index: u32::from_be_bytes(v[1092..1096].try_into().unwrap()),
+            signatures: v[1096..].to_vec().into(),
         }
     }
 }
 
 impl MerkleRootMultisigIsmMetadata {
     pub fn signatures_len(&self) -> Result<usize, &'static str> {
-        if self.signatures.len() % SIGNATURE_LENGTH != 0 {
+        if !self.signatures.len().is_multiple_of(SIGNATURE_LENGTH) {
             return Err("Invalid signatures length");
         }
 
@@ -61,6 +69,29 @@ impl MerkleRootMultisigIsmMetadata {
             .to_vec()
             .into()
     }
+
+    pub fn branch(&self) -> StdResult<[HexBinary; TREE_DEPTH]> {
+        if self.proof.len() != HASH_LENGTH * TREE_DEPTH {
+            return Err(StdError::generic_err("invalid proof length"));
+        }
+
+        Ok(self
+            .proof
+            .chunks_exact(HASH_LENGTH)
+            .map(HexBinary::from)
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap())
+    }
+
+    /// root of the tree containing `leaf` at `message_index`, according to the proof
+    pub fn branch_root(&self, leaf: HexBinary) -> StdResult<HexBinary> {
+        Ok(MerkleTree::branch_root(
+            leaf,
+            self.branch()?,
+            self.message_index as u128,
+        ))
+    }
 }
 
 #[cw_serde]
@@ -154,7 +185,17 @@ impl Iterator for AggregateMetadata {
 
 impl AggregateMetadata {
     pub fn from_hex(v: HexBinary, addrs: Vec<Addr>) -> Self {
-        Self(
+        Self::try_from_hex(v, addrs).expect("invalid aggregate metadata")
+    }
+
+    pub fn try_from_hex(v: HexBinary, addrs: Vec<Addr>) -> StdResult<Self> {
+        let invalid = || StdError::generic_err("invalid aggregate metadata");
+
+        if v.len() < addrs.len() * Self::RANGE_SIZE * 2 {
+            return Err(invalid());
+        }
+
+        Ok(Self(
             addrs
                 .into_iter()
                 .enumerate()
@@ -171,10 +212,12 @@ impl AggregateMetadata {
                     let meta_start = u32::from_be_bytes(meta_start) as usize;
                     let meta_end = u32::from_be_bytes(meta_end) as usize;
 
-                    (ism, v[meta_start..meta_end].to_vec().into())
+                    let meta = v.get(meta_start..meta_end).ok_or_else(invalid)?;
+
+                    Ok((ism, meta.to_vec().into()))
                 })
-                .collect(),
-        )
+                .collect::<StdResult<_>>()?,
+        ))
     }
 }
 
@@ -288,6 +331,50 @@ mod test {
         assert_eq!(metadata, new_metadata);
     }
 
+    #[test]
+    fn test_aggregate_invalid() {
+        let isms = vec![addr("test1"), addr("test2")];
+
+        // shorter than the range header
+        assert!(AggregateMetadata::try_from_hex(gen_bz(12), isms.clone()).is_err());
+
+        // range points outside of the metadata
+        let metadata_bz = hex("00000010000000ff0000001000000010");
+        assert!(AggregateMetadata::try_from_hex(metadata_bz, isms).is_err());
+    }
+
+    #[test]
+    fn test_merkle_root_multisig_metadata() {
+        let metadata = MerkleRootMultisigIsmMetadata {
+            origin_mailbox: gen_bz(32),
+            message_index: 5,
+            message_id: gen_bz(32),
+            proof: gen_bz(32 * 32),
+            checkpoint_index: 7,
+            signatures: gen_bz(SIGNATURE_LENGTH * 2),
+        };
+
+        let bz: HexBinary = metadata.clone().into();
+        assert_eq!(bz.len(), 1096 + SIGNATURE_LENGTH * 2);
+
+        let parsed: MerkleRootMultisigIsmMetadata = bz.into();
+        assert_eq!(parsed, metadata);
+        assert_eq!(parsed.signatures_len().unwrap(), 2);
+
+        let leaf = gen_bz(32);
+        let root = parsed.branch_root(leaf.clone()).unwrap();
+        assert_eq!(
+            root,
+            MerkleTree::branch_root(leaf.clone(), parsed.branch().unwrap(), 5)
+        );
+
+        let shifted = MerkleRootMultisigIsmMetadata {
+            message_index: 6,
+            ..parsed
+        };
+        assert_ne!(shifted.branch_root(leaf).unwrap(), root);
+    }
+
     #[test]
     fn test_message_id_multisig_metadata() {
         let testdata = hex("fadafdf4db5e6264d450bafa5951b2180b8fe8aac2e012f280784ae841e9a7f732a2601709a27a5e370a59f98a67b5da6baa522b6421edf2ea240d94d84511a800000000df4eaf1947af0858139b90054561d5ab2a423b4ad8d75a5ec7f9e860fd3de1bb3924e2593e29b595aae2717538c0af6d6ae9fc20477da49d223a0d928a1efb311bdf4eaf1947af0858139b90054561d5ab2a423b4ad8d75a5ec7f9e860fd3de1bb3924e2593e29b595aae2717538c0af6d6ae9fc20477da49d223a0d928a1efb311b");
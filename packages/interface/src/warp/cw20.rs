@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{HexBinary, Uint128};
+use cosmwasm_std::{HexBinary, StdError, StdResult, Uint128, Uint256};
 
 use crate::{
     connection::{ConnectionMsg, ConnectionQueryMsg},
@@ -7,6 +7,7 @@ use crate::{
     ism::IsmSpecifierQueryMsg,
     ownable::{OwnableMsg, OwnableQueryMsg},
     router::{self, RouterQuery},
+    EventVerbosity,
 };
 
 use super::{TokenModeMsg, TokenWarpDefaultQueryMsg};
@@ -42,6 +43,73 @@ pub struct InstantiateMsg {
     pub hrp: String,
     pub owner: String,
     pub mailbox: String,
+
+    // code ids permitted for the bridged token. unrestricted if not set
+    #[serde(default)]
+    pub allowed_code_ids: Option<Vec<u64>>,
+}
+
+impl InstantiateMsg {
+    /// Builds a bridged route that creates a fresh cw20 token.
+    /// Minter is filled by the warp contract at instantiation, so it's left empty here.
+    pub fn bridged(
+        code_id: u64,
+        name: impl Into<String>,
+        symbol: impl Into<String>,
+        decimals: u8,
+        hrp: impl Into<String>,
+        owner: impl Into<String>,
+        mailbox: impl Into<String>,
+    ) -> Self {
+        Self {
+            token: TokenModeMsg::Bridged(Cw20ModeBridged {
+                code_id,
+                init_msg: Box::new(Cw20InitMsg {
+                    name: name.into(),
+                    symbol: symbol.into(),
+                    decimals,
+                    initial_balances: vec![],
+                    mint: None,
+                    marketing: None,
+                }),
+            }),
+            hrp: hrp.into(),
+            owner: owner.into(),
+            mailbox: mailbox.into(),
+            allowed_code_ids: None,
+        }
+    }
+
+    /// Builds a collateral route that escrows an existing cw20 token.
+    pub fn collateral(
+        address: impl Into<String>,
+        hrp: impl Into<String>,
+        owner: impl Into<String>,
+        mailbox: impl Into<String>,
+    ) -> Self {
+        Self {
+            token: TokenModeMsg::Collateral(Cw20ModeCollateral {
+                address: address.into(),
+            }),
+            hrp: hrp.into(),
+            owner: owner.into(),
+            mailbox: mailbox.into(),
+            allowed_code_ids: None,
+        }
+    }
+
+    pub fn validate(&self) -> StdResult<()> {
+        if let TokenModeMsg::Bridged(token) = &self.token {
+            // supply of bridged token must be backed by collateral on the remote side
+            if !token.init_msg.initial_balances.is_empty() {
+                return Err(StdError::generic_err(
+                    "bridged token must not have initial balances",
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[cw_serde]
@@ -61,6 +129,36 @@ pub enum ExecuteMsg {
         hook: Option<String>,
         metadata: Option<HexBinary>,
     },
+
+    // update decimals of the counterparty token on `dest_domain`.
+    // amounts are scaled between local and remote decimals once configured
+    SetRemoteDecimals {
+        dest_domain: u32,
+        decimals: u8,
+    },
+
+    // override origin senders accepted from `domain`.
+    // empty to fall back to the enrolled route
+    SetAllowedSenders {
+        domain: u32,
+        senders: Vec<HexBinary>,
+    },
+    // gas overpayment below this threshold is forwarded with the dispatch instead of refunded
+    SetMinRefund {
+        min_refund: Uint128,
+    },
+    // embed the mailbox nonce of the dispatch into the warp message metadata
+    SetEmbedNonce {
+        embed_nonce: bool,
+    },
+    // cap on the net amount bridged out. transfers beyond it are rejected
+    SetOutstandingCap {
+        cap: Option<Uint256>,
+    },
+    // how many attributes the handle and transfer-remote events carry
+    SetEventVerbosity {
+        verbosity: EventVerbosity,
+    },
 }
 
 #[cw_serde]
@@ -77,3 +175,74 @@ pub enum QueryMsg {
 
     IsmSpecifier(IsmSpecifierQueryMsg),
 }
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::Uint128;
+    use cw20::Cw20Coin;
+
+    use super::*;
+
+    #[test]
+    fn test_builder_bridged() {
+        let built = InstantiateMsg::bridged(1, "token", "TKN", 6, "osmo", "owner", "mailbox");
+
+        let manual = InstantiateMsg {
+            token: TokenModeMsg::Bridged(Cw20ModeBridged {
+                code_id: 1,
+                init_msg: Cw20InitMsg {
+                    name: "token".to_string(),
+                    symbol: "TKN".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![],
+                    mint: None,
+                    marketing: None,
+                }
+                .into(),
+            }),
+            hrp: "osmo".to_string(),
+            owner: "owner".to_string(),
+            mailbox: "mailbox".to_string(),
+            allowed_code_ids: None,
+        };
+
+        assert_eq!(built, manual);
+        built.validate().unwrap();
+    }
+
+    #[test]
+    fn test_builder_collateral() {
+        let built = InstantiateMsg::collateral("cw20", "osmo", "owner", "mailbox");
+
+        let manual = InstantiateMsg {
+            token: TokenModeMsg::Collateral(Cw20ModeCollateral {
+                address: "cw20".to_string(),
+            }),
+            hrp: "osmo".to_string(),
+            owner: "owner".to_string(),
+            mailbox: "mailbox".to_string(),
+            allowed_code_ids: None,
+        };
+
+        assert_eq!(built, manual);
+        built.validate().unwrap();
+    }
+
+    #[test]
+    fn test_validate_bridged_initial_balances() {
+        let mut msg = InstantiateMsg::bridged(1, "token", "TKN", 6, "osmo", "owner", "mailbox");
+
+        if let TokenModeMsg::Bridged(ref mut token) = msg.token {
+            token.init_msg.initial_balances = vec![Cw20Coin {
+                address: "someone".to_string(),
+                amount: Uint128::new(100),
+            }];
+        }
+
+        let err = msg.validate().unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("bridged token must not have initial balances")
+        );
+    }
+}
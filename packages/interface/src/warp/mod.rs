@@ -1,10 +1,11 @@
 pub mod cw20;
 pub mod native;
+pub mod unified;
 
 use std::fmt;
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{HexBinary, Uint256};
+use cosmwasm_std::{Coin, HexBinary, Uint128, Uint256};
 
 #[cw_serde]
 pub enum TokenTypeNative {
@@ -60,6 +61,23 @@ pub struct Message {
     pub metadata: HexBinary,
 }
 
+impl Message {
+    /// embeds the outbound dispatch nonce as 4 big-endian bytes of metadata
+    pub fn with_nonce(mut self, nonce: u32) -> Self {
+        self.metadata = nonce.to_be_bytes().to_vec().into();
+        self
+    }
+
+    /// dispatch nonce embedded by the origin route, if any
+    pub fn nonce(&self) -> Option<u32> {
+        self.metadata
+            .as_slice()
+            .try_into()
+            .ok()
+            .map(u32::from_be_bytes)
+    }
+}
+
 impl From<Message> for HexBinary {
     fn from(v: Message) -> Self {
         v.recipient
@@ -82,6 +100,45 @@ impl From<HexBinary> for Message {
     }
 }
 
+/// Splits gas `funds` into the part forwarded with the dispatch and the overpayment refunded
+/// to the sender. Denoms the quote doesn't ask for are always refunded.
+///
+/// Overpayment below `min_refund` is forwarded along instead. The mailbox passes it on to the
+/// default hook, so with an IGP it is either refunded to the route by the IGP or retained there
+/// for the beneficiary, depending on the IGP's own min refund.
+pub fn split_refund(
+    funds: Vec<Coin>,
+    fees: &[Coin],
+    min_refund: Uint128,
+) -> (Vec<Coin>, Vec<Coin>) {
+    let mut forward = vec![];
+    let mut refund = vec![];
+
+    for coin in funds.into_iter().filter(|v| !v.amount.is_zero()) {
+        let Some(required) = fees
+            .iter()
+            .find(|v| v.denom == coin.denom)
+            .map(|v| v.amount)
+        else {
+            refund.push(coin);
+            continue;
+        };
+        let excess = coin.amount.saturating_sub(required);
+
+        if excess.is_zero() || excess < min_refund {
+            forward.push(coin);
+            continue;
+        }
+
+        if !required.is_zero() {
+            forward.push(Coin::new(required.u128(), &coin.denom));
+        }
+        refund.push(Coin::new(excess.u128(), coin.denom));
+    }
+
+    (forward, refund)
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum TokenWarpDefaultQueryMsg {
@@ -90,6 +147,17 @@ pub enum TokenWarpDefaultQueryMsg {
 
     #[returns(TokenModeResponse)]
     TokenMode {},
+
+    #[returns(PreviewRecipientResponse)]
+    PreviewRecipient { recipient: HexBinary },
+
+    /// bank denom or cw20 address escrowed by a collateral route
+    #[returns(CollateralDenomResponse)]
+    CollateralDenom {},
+
+    /// net amount bridged out (out minus in) and the cap on it
+    #[returns(OutstandingResponse)]
+    Outstanding {},
 }
 
 #[cw_serde]
@@ -102,3 +170,59 @@ pub struct TokenTypeResponse {
 pub struct TokenModeResponse {
     pub mode: TokenMode,
 }
+
+#[cw_serde]
+pub struct PreviewRecipientResponse {
+    pub recipient: String,
+}
+
+#[cw_serde]
+pub struct CollateralDenomResponse {
+    pub denom: String,
+}
+
+#[cw_serde]
+pub struct OutstandingResponse {
+    pub outstanding: Uint256,
+    pub cap: Option<Uint256>,
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::coin;
+
+    use super::*;
+
+    #[test]
+    fn test_split_refund() {
+        let funds = vec![coin(100, "ua"), coin(100, "ub"), coin(100, "uc")];
+        let fees = vec![coin(100, "ua"), coin(90, "ub"), coin(50, "uc")];
+
+        // always refund
+        let (forward, refund) = split_refund(funds.clone(), &fees, Uint128::zero());
+        assert_eq!(forward, fees);
+        assert_eq!(refund, vec![coin(10, "ub"), coin(50, "uc")]);
+
+        // dust is forwarded
+        let (forward, refund) = split_refund(funds.clone(), &fees, Uint128::new(20));
+        assert_eq!(
+            forward,
+            vec![coin(100, "ua"), coin(100, "ub"), coin(50, "uc")]
+        );
+        assert_eq!(refund, vec![coin(50, "uc")]);
+
+        // coins not quoted at all
+        let (forward, refund) = split_refund(funds.clone(), &[], Uint128::zero());
+        assert!(forward.is_empty());
+        assert_eq!(refund, funds);
+
+        // unquoted coins are refunded even below the threshold
+        let (forward, refund) = split_refund(
+            vec![coin(100, "ua"), coin(5, "ud")],
+            &fees,
+            Uint128::new(20),
+        );
+        assert_eq!(forward, vec![coin(100, "ua")]);
+        assert_eq!(refund, vec![coin(5, "ud")]);
+    }
+}
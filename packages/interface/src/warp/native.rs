@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{HexBinary, Uint128};
+use cosmwasm_std::{HexBinary, Uint128, Uint256};
 
 use crate::{
     connection::{ConnectionMsg, ConnectionQueryMsg},
@@ -7,6 +7,7 @@ use crate::{
     ism::IsmSpecifierQueryMsg,
     ownable::{OwnableMsg, OwnableQueryMsg},
     router::{RouterMsg, RouterQuery},
+    EventVerbosity,
 };
 
 use super::{TokenModeMsg, TokenWarpDefaultQueryMsg};
@@ -69,6 +70,22 @@ pub enum ExecuteMsg {
         hook: Option<String>,
         metadata: Option<HexBinary>,
     },
+    // gas overpayment below this threshold is forwarded with the dispatch instead of refunded
+    SetMinRefund {
+        min_refund: Uint128,
+    },
+    // embed the mailbox nonce of the dispatch into the warp message metadata
+    SetEmbedNonce {
+        embed_nonce: bool,
+    },
+    // cap on the net amount bridged out. transfers beyond it are rejected
+    SetOutstandingCap {
+        cap: Option<Uint256>,
+    },
+    // how many attributes the handle and transfer-remote events carry
+    SetEventVerbosity {
+        verbosity: EventVerbosity,
+    },
 }
 
 #[cw_serde]
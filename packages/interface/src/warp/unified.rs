@@ -0,0 +1,107 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{HexBinary, Uint128, Uint256};
+
+use crate::{
+    connection::{ConnectionMsg, ConnectionQueryMsg},
+    core,
+    ism::IsmSpecifierQueryMsg,
+    ownable::{OwnableMsg, OwnableQueryMsg},
+    router::{RouterMsg, RouterQuery},
+    EventVerbosity,
+};
+
+use super::{
+    cw20::{Cw20ModeBridged, Cw20ModeCollateral},
+    native::{NativeModeBriged, NativeModeCollateral},
+    TokenModeMsg, TokenWarpDefaultQueryMsg,
+};
+
+#[cw_serde]
+pub enum UnifiedModeBridged {
+    Cw20(Cw20ModeBridged),
+    Native(NativeModeBriged),
+}
+
+#[cw_serde]
+pub enum UnifiedModeCollateral {
+    Cw20(Cw20ModeCollateral),
+    Native(NativeModeCollateral),
+}
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub token: TokenModeMsg<UnifiedModeBridged, UnifiedModeCollateral>,
+
+    pub hrp: String,
+    pub owner: String,
+    pub mailbox: String,
+
+    // code ids permitted for a bridged cw20 token. unrestricted if not set
+    #[serde(default)]
+    pub allowed_code_ids: Option<Vec<u64>>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    Ownable(OwnableMsg),
+    Router(RouterMsg<HexBinary>),
+    Connection(ConnectionMsg),
+
+    // handle transfer remote
+    Handle(core::HandleMsg),
+
+    // transfer to remote. cw20 routes pull `amount` with an allowance,
+    // native routes expect it in the attached funds
+    TransferRemote {
+        dest_domain: u32,
+        recipient: HexBinary,
+        amount: Uint128,
+        hook: Option<String>,
+        metadata: Option<HexBinary>,
+    },
+
+    // update decimals of the counterparty token on `dest_domain`.
+    // only cw20 routes can scale, as the decimals of a bank denom can't be queried
+    SetRemoteDecimals {
+        dest_domain: u32,
+        decimals: u8,
+    },
+
+    // override origin senders accepted from `domain`.
+    // empty to fall back to the enrolled route
+    SetAllowedSenders {
+        domain: u32,
+        senders: Vec<HexBinary>,
+    },
+    // gas overpayment below this threshold is forwarded with the dispatch instead of refunded
+    SetMinRefund {
+        min_refund: Uint128,
+    },
+    // embed the mailbox nonce of the dispatch into the warp message metadata
+    SetEmbedNonce {
+        embed_nonce: bool,
+    },
+    // cap on the net amount bridged out. transfers beyond it are rejected
+    SetOutstandingCap {
+        cap: Option<Uint256>,
+    },
+    // how many attributes the handle and transfer-remote events carry
+    SetEventVerbosity {
+        verbosity: EventVerbosity,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+#[query_responses(nested)]
+pub enum QueryMsg {
+    Ownable(OwnableQueryMsg),
+
+    Router(RouterQuery<HexBinary>),
+
+    Connection(ConnectionQueryMsg),
+
+    TokenDefault(TokenWarpDefaultQueryMsg),
+
+    IsmSpecifier(IsmSpecifierQueryMsg),
+}
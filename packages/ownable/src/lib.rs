@@ -3,10 +3,12 @@ mod test;
 
 use cosmwasm_std::{
     ensure, ensure_eq, to_json_binary, Addr, CustomQuery, Deps, DepsMut, Env, Event, MessageInfo,
-    QueryResponse, Response, StdError, StdResult, Storage,
+    QueryResponse, Response, StdError, StdResult, Storage, Timestamp, Uint64,
 };
 use cw_storage_plus::Item;
-use hpl_interface::ownable::{OwnableMsg, OwnableQueryMsg, OwnerResponse, PendingOwnerResponse};
+use hpl_interface::ownable::{
+    CooldownResponse, OwnableMsg, OwnableQueryMsg, OwnerResponse, PendingOwnerResponse,
+};
 
 const OWNER_KEY: &str = "owner";
 const OWNER: Item<Addr> = Item::new(OWNER_KEY);
@@ -14,6 +16,15 @@ const OWNER: Item<Addr> = Item::new(OWNER_KEY);
 const PENDING_OWNER_KEY: &str = "pending_owner";
 const PENDING_OWNER: Item<Addr> = Item::new(PENDING_OWNER_KEY);
 
+/// Upper bound of the cooldown, so a typo can't freeze the config for good.
+pub const MAX_COOLDOWN: u64 = 365 * 24 * 60 * 60;
+
+const COOLDOWN_KEY: &str = "cooldown";
+const COOLDOWN: Item<u64> = Item::new(COOLDOWN_KEY);
+
+const LAST_CHANGE_KEY: &str = "last_change";
+const LAST_CHANGE: Item<Timestamp> = Item::new(LAST_CHANGE_KEY);
+
 fn event_to_resp(event: Event) -> Response {
     Response::new().add_event(event)
 }
@@ -30,7 +41,7 @@ pub fn initialize(storage: &mut dyn Storage, owner: &Addr) -> StdResult<()> {
 
 pub fn handle<C: CustomQuery>(
     deps: DepsMut<'_, C>,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: OwnableMsg,
 ) -> StdResult<Response> {
@@ -54,6 +65,11 @@ pub fn handle<C: CustomQuery>(
         ClaimOwnership {} => {
             let event = claim_ownership(deps.storage, &info.sender)?;
 
+            Ok(event_to_resp(event))
+        }
+        SetCooldown { cooldown } => {
+            let event = set_cooldown(deps.storage, &env, &info.sender, cooldown)?;
+
             Ok(event_to_resp(event))
         }
     }
@@ -117,6 +133,57 @@ pub fn claim_ownership(storage: &mut dyn Storage, sender: &Addr) -> StdResult<Ev
     Ok(new_event("claim").add_attribute("owner", sender))
 }
 
+pub fn set_cooldown(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    cooldown: u64,
+) -> StdResult<Event> {
+    ensure_eq!(
+        sender,
+        OWNER.load(storage)?,
+        StdError::generic_err("unauthorized")
+    );
+
+    ensure!(
+        !COOLDOWN.exists(storage),
+        StdError::generic_err("cooldown already set")
+    );
+    ensure!(
+        cooldown <= MAX_COOLDOWN,
+        StdError::generic_err(format!("cooldown too long. max: {MAX_COOLDOWN}"))
+    );
+
+    COOLDOWN.save(storage, &cooldown)?;
+    // the first change is not allowed before a full cooldown has passed
+    LAST_CHANGE.save(storage, &env.block.time)?;
+
+    Ok(new_event("set_cooldown")
+        .add_attribute("owner", sender)
+        .add_attribute("cooldown", cooldown.to_string()))
+}
+
+/// Guard for owner-gated config changes. Returns the seconds left in the current cooldown,
+/// or zero after recording the change, which starts the next cooldown.
+pub fn consume_cooldown(storage: &mut dyn Storage, env: &Env) -> StdResult<u64> {
+    let Some(cooldown) = COOLDOWN.may_load(storage)? else {
+        return Ok(0);
+    };
+
+    let next_change = Uint64::new(LAST_CHANGE.load(storage)?.nanos())
+        .checked_add(Uint64::new(cooldown).checked_mul(Uint64::new(1_000_000_000))?)?;
+    let next_change = Timestamp::from_nanos(next_change.u64());
+    if env.block.time < next_change {
+        // rounds up, so a change less than a second early is still reported as blocked
+        let nanos_remaining = next_change.nanos() - env.block.time.nanos();
+        return Ok(nanos_remaining.div_ceil(1_000_000_000));
+    }
+
+    LAST_CHANGE.save(storage, &env.block.time)?;
+
+    Ok(0)
+}
+
 pub fn handle_query<C: CustomQuery>(
     deps: Deps<'_, C>,
     _env: Env,
@@ -129,6 +196,10 @@ pub fn handle_query<C: CustomQuery>(
         OwnableQueryMsg::GetPendingOwner {} => to_json_binary(&PendingOwnerResponse {
             pending_owner: get_pending_owner(deps.storage)?,
         }),
+        OwnableQueryMsg::GetCooldown {} => to_json_binary(&CooldownResponse {
+            cooldown: COOLDOWN.may_load(deps.storage)?,
+            last_change: LAST_CHANGE.may_load(deps.storage)?,
+        }),
     }
 }
 
@@ -3,7 +3,9 @@ use cosmwasm_std::{
     testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
     Addr, CustomQuery, Empty, Env, MessageInfo, OwnedDeps, Response, StdError, StdResult,
 };
-use hpl_interface::ownable::{OwnableMsg, OwnableQueryMsg, OwnerResponse, PendingOwnerResponse};
+use hpl_interface::ownable::{
+    CooldownResponse, OwnableMsg, OwnableQueryMsg, OwnerResponse, PendingOwnerResponse,
+};
 use rstest::rstest;
 use serde::de::DeserializeOwned;
 
@@ -53,6 +55,17 @@ where
         )
     }
 
+    pub fn set_cooldown(&mut self, sender: &Addr, cooldown: u64) -> StdResult<Response> {
+        self.handle(
+            mock_info(sender.as_str(), &[]),
+            OwnableMsg::SetCooldown { cooldown },
+        )
+    }
+
+    pub fn cooldown(&self) -> StdResult<CooldownResponse> {
+        self.query(OwnableQueryMsg::GetCooldown {})
+    }
+
     pub fn owner(&self) -> StdResult<Addr> {
         let resp: OwnerResponse = self.query(OwnableQueryMsg::GetOwner {})?;
 
@@ -192,3 +205,106 @@ fn test_claim() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[rstest]
+#[case(99, 1)]
+#[case(100, 0)]
+#[case(150, 0)]
+fn test_cooldown(#[case] elapsed: u64, #[case] seconds_remaining: u64) -> anyhow::Result<()> {
+    let deployer = Addr::unchecked("deployer");
+
+    let mut ownable = ownable_default();
+
+    crate::initialize(ownable.deps.as_mut().storage, &deployer)?;
+
+    // no cooldown configured
+    assert_eq!(
+        crate::consume_cooldown(ownable.deps.as_mut().storage, &ownable.env)?,
+        0
+    );
+
+    // fail - sender is not owner
+    let err = ownable
+        .set_cooldown(&Addr::unchecked("someone"), 100)
+        .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    // fail - longer than the bound
+    let err = ownable
+        .set_cooldown(&deployer, crate::MAX_COOLDOWN + 1)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err(format!("cooldown too long. max: {}", crate::MAX_COOLDOWN))
+    );
+
+    ownable.set_cooldown(&deployer, 100)?;
+    assert_eq!(
+        ownable.cooldown()?,
+        CooldownResponse {
+            cooldown: Some(100),
+            last_change: Some(ownable.env.block.time),
+        }
+    );
+
+    // fail - can only be set once
+    let err = ownable.set_cooldown(&deployer, 0).unwrap_err();
+    assert_eq!(err, StdError::generic_err("cooldown already set"));
+
+    let mut env = ownable.env.clone();
+    env.block.time = env.block.time.plus_seconds(elapsed);
+
+    let storage = ownable.deps.as_mut().storage;
+    assert_eq!(crate::consume_cooldown(storage, &env)?, seconds_remaining);
+
+    // a granted change starts the next cooldown
+    if seconds_remaining == 0 {
+        assert_eq!(crate::consume_cooldown(storage, &env)?, 100);
+    }
+
+    Ok(())
+}
+
+#[rstest]
+fn test_cooldown_subsecond() -> anyhow::Result<()> {
+    let deployer = Addr::unchecked("deployer");
+
+    let mut ownable = ownable_default();
+
+    crate::initialize(ownable.deps.as_mut().storage, &deployer)?;
+    ownable.set_cooldown(&deployer, 100)?;
+
+    // half a second before the cooldown ends
+    let mut env = ownable.env.clone();
+    env.block.time = env.block.time.plus_nanos(100 * 1_000_000_000 - 500_000_000);
+
+    let storage = ownable.deps.as_mut().storage;
+    assert_eq!(crate::consume_cooldown(storage, &env)?, 1);
+    assert_eq!(
+        ownable.cooldown()?.last_change,
+        Some(ownable.env.block.time)
+    );
+
+    Ok(())
+}
+
+#[rstest]
+fn test_cooldown_max() -> anyhow::Result<()> {
+    let deployer = Addr::unchecked("deployer");
+
+    let mut ownable = ownable_default();
+
+    crate::initialize(ownable.deps.as_mut().storage, &deployer)?;
+    ownable.set_cooldown(&deployer, crate::MAX_COOLDOWN)?;
+
+    let mut env = ownable.env.clone();
+    env.block.time = env.block.time.plus_seconds(crate::MAX_COOLDOWN - 1);
+
+    let storage = ownable.deps.as_mut().storage;
+    assert_eq!(crate::consume_cooldown(storage, &env)?, 1);
+
+    env.block.time = env.block.time.plus_seconds(1);
+    assert_eq!(crate::consume_cooldown(storage, &env)?, 0);
+
+    Ok(())
+}
@@ -3,14 +3,22 @@ mod test;
 
 use cosmwasm_std::{
     ensure, ensure_eq, to_json_binary, Addr, CustomQuery, Deps, DepsMut, Env, Event, MessageInfo,
-    QueryResponse, Response, StdError, StdResult, Storage,
+    QueryResponse, Response, StdError, StdResult, Storage, Timestamp,
 };
 use cw_storage_plus::Item;
-use hpl_interface::pausable::{PausableMsg, PausableQueryMsg, PauseInfoResponse};
+use hpl_interface::pausable::{
+    PausableMsg, PausableQueryMsg, PauseInfoResponse, PauseStatusResponse,
+};
 
 const PAUSE_KEY: &str = "pause";
 const PAUSE: Item<bool> = Item::new(PAUSE_KEY);
 
+const PAUSE_REASON_KEY: &str = "pause_reason";
+const PAUSE_REASON: Item<Option<String>> = Item::new(PAUSE_REASON_KEY);
+
+const PAUSED_AT_KEY: &str = "paused_at";
+const PAUSED_AT: Item<Option<Timestamp>> = Item::new(PAUSED_AT_KEY);
+
 fn event_to_resp(event: Event) -> Response {
     Response::new().add_event(event)
 }
@@ -27,7 +35,7 @@ pub fn initialize(storage: &mut dyn Storage, paused: &bool) -> StdResult<()> {
 
 pub fn handle<C: CustomQuery>(
     deps: DepsMut<'_, C>,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: PausableMsg,
 ) -> StdResult<Response> {
@@ -38,19 +46,38 @@ pub fn handle<C: CustomQuery>(
     );
 
     match msg {
-        PausableMsg::Pause {} => Ok(event_to_resp(pause(deps.storage, &info.sender)?)),
+        PausableMsg::Pause { reason } => Ok(event_to_resp(pause(
+            deps.storage,
+            &env,
+            &info.sender,
+            reason,
+        )?)),
         PausableMsg::Release {} => Ok(event_to_resp(release(deps.storage, &info.sender)?)),
     }
 }
 
-pub fn pause(storage: &mut dyn Storage, sender: &Addr) -> StdResult<Event> {
+/// also used by contracts pausing themselves, which should pass a descriptive reason
+pub fn pause(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    reason: Option<String>,
+) -> StdResult<Event> {
     ensure!(
         !PAUSE.load(storage)?,
         StdError::generic_err("already paused")
     );
 
     PAUSE.save(storage, &true)?;
-    Ok(new_event("pause").add_attribute("sender", sender))
+    PAUSE_REASON.save(storage, &reason)?;
+    PAUSED_AT.save(storage, &Some(env.block.time))?;
+
+    let mut event = new_event("pause").add_attribute("sender", sender);
+    if let Some(reason) = reason {
+        event = event.add_attribute("reason", reason);
+    }
+
+    Ok(event)
 }
 
 pub fn release(storage: &mut dyn Storage, sender: &Addr) -> StdResult<Event> {
@@ -60,6 +87,9 @@ pub fn release(storage: &mut dyn Storage, sender: &Addr) -> StdResult<Event> {
     );
 
     PAUSE.save(storage, &false)?;
+    PAUSE_REASON.remove(storage);
+    PAUSED_AT.remove(storage);
+
     Ok(new_event("release").add_attribute("sender", sender))
 }
 
@@ -72,9 +102,18 @@ pub fn handle_query<C: CustomQuery>(
         PausableQueryMsg::PauseInfo {} => to_json_binary(&PauseInfoResponse {
             paused: get_pause_info(deps.storage)?,
         }),
+        PausableQueryMsg::PauseStatus {} => to_json_binary(&get_pause_status(deps.storage)?),
     }
 }
 
 pub fn get_pause_info(storage: &dyn Storage) -> StdResult<bool> {
     PAUSE.load(storage)
 }
+
+pub fn get_pause_status(storage: &dyn Storage) -> StdResult<PauseStatusResponse> {
+    Ok(PauseStatusResponse {
+        paused: PAUSE.load(storage)?,
+        reason: PAUSE_REASON.may_load(storage)?.flatten(),
+        paused_at: PAUSED_AT.may_load(storage)?.flatten(),
+    })
+}
@@ -3,7 +3,9 @@ use cosmwasm_std::{
     testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
     Addr, CustomQuery, Empty, Env, MessageInfo, OwnedDeps, Response, StdError, StdResult,
 };
-use hpl_interface::pausable::{PausableMsg, PausableQueryMsg, PauseInfoResponse};
+use hpl_interface::pausable::{
+    PausableMsg, PausableQueryMsg, PauseInfoResponse, PauseStatusResponse,
+};
 use rstest::rstest;
 use serde::de::DeserializeOwned;
 
@@ -35,7 +37,18 @@ where
     }
 
     pub fn pause(&mut self, sender: &Addr) -> StdResult<Response> {
-        self.handle(mock_info(sender.as_str(), &[]), PausableMsg::Pause {})
+        self.pause_with_reason(sender, None)
+    }
+
+    pub fn pause_with_reason(
+        &mut self,
+        sender: &Addr,
+        reason: Option<String>,
+    ) -> StdResult<Response> {
+        self.handle(
+            mock_info(sender.as_str(), &[]),
+            PausableMsg::Pause { reason },
+        )
     }
 
     pub fn release(&mut self, sender: &Addr) -> StdResult<Response> {
@@ -47,6 +60,10 @@ where
 
         Ok(resp.paused)
     }
+
+    pub fn pause_status(&self) -> StdResult<PauseStatusResponse> {
+        self.query(PausableQueryMsg::PauseStatus {})
+    }
 }
 
 fn pausable_default() -> Pausable {
@@ -135,3 +152,59 @@ fn test_release(
 
     Ok(())
 }
+
+#[rstest]
+fn test_pause_status() -> anyhow::Result<()> {
+    let mut pausable = pausable_default();
+
+    pausable.set_owner(&Addr::unchecked("owner"))?;
+    crate::initialize(pausable.deps.as_mut().storage, &false)?;
+
+    let status = pausable.pause_status()?;
+    assert!(!status.paused);
+    assert_eq!(status.reason, None);
+    assert_eq!(status.paused_at, None);
+
+    // manual pause
+    let res = pausable.pause_with_reason(&Addr::unchecked("owner"), Some("incident".into()))?;
+    assert_eq!(
+        res,
+        event_to_resp(
+            new_event("pause")
+                .add_attribute("sender", "owner")
+                .add_attribute("reason", "incident")
+        )
+    );
+
+    let status = pausable.pause_status()?;
+    assert!(status.paused);
+    assert_eq!(status.reason, Some("incident".to_string()));
+    assert_eq!(status.paused_at, Some(pausable.env.block.time));
+
+    // release clears the reason
+    pausable.release(&Addr::unchecked("owner"))?;
+    assert_eq!(
+        pausable.pause_status()?,
+        PauseStatusResponse {
+            paused: false,
+            reason: None,
+            paused_at: None,
+        }
+    );
+
+    // contract pausing itself
+    pausable.env.block.time = pausable.env.block.time.plus_seconds(60);
+    crate::pause(
+        pausable.deps.as_mut().storage,
+        &pausable.env,
+        &Addr::unchecked("contract"),
+        Some("rate limit exceeded".into()),
+    )?;
+
+    let status = pausable.pause_status()?;
+    assert!(status.paused);
+    assert_eq!(status.reason, Some("rate limit exceeded".to_string()));
+    assert_eq!(status.paused_at, Some(pausable.env.block.time));
+
+    Ok(())
+}
@@ -2,14 +2,15 @@
 mod test;
 
 use cosmwasm_std::{
-    ensure_eq, to_json_binary, Addr, CustomQuery, Deps, DepsMut, Env, Event, MessageInfo,
-    QueryResponse, Response, StdError, StdResult, Storage,
+    ensure, ensure_eq, from_json, to_json_binary, Addr, Binary, CustomQuery, Deps, DepsMut, Env,
+    Event, MessageInfo, QueryResponse, Response, StdError, StdResult, Storage,
 };
-use cw_storage_plus::Map;
+use cw_storage_plus::{Item, Map};
 use hpl_interface::{
     range_option,
     router::{
-        DomainRouteSet, DomainsResponse, RouteResponse, RouterMsg, RouterQuery, RoutesResponse,
+        DomainRouteSet, DomainsResponse, ExportRoutesResponse, RouteInfoResponse,
+        RouteModification, RouteResponse, RouterMsg, RouterQuery, RoutesResponse,
     },
     Order,
 };
@@ -17,6 +18,24 @@ use serde::{de::DeserializeOwned, Serialize};
 
 const ROUTES_PREFIX: &str = "routes";
 
+const ROUTE_MODIFICATIONS_PREFIX: &str = "route_modifications";
+const ROUTE_MODIFICATIONS: Map<u32, RouteModification> = Map::new(ROUTE_MODIFICATIONS_PREFIX);
+
+const ROUTE_COUNT_KEY: &str = "route_count";
+const ROUTE_COUNT: Item<u32> = Item::new(ROUTE_COUNT_KEY);
+
+/// Upper bound of the route table, keeps enumeration queries affordable.
+pub const MAX_ROUTES: usize = 512;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum RouterError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("too many routes. max: {max}")]
+    TooManyRoutes { max: usize },
+}
+
 fn event_to_resp(event: Event) -> Response {
     Response::new().add_event(event)
 }
@@ -32,12 +51,81 @@ where
     Map::new(ROUTES_PREFIX)
 }
 
+fn route_count<T>(storage: &dyn Storage) -> StdResult<u32>
+where
+    T: Serialize + DeserializeOwned + Clone + Eq,
+{
+    match ROUTE_COUNT.may_load(storage)? {
+        Some(count) => Ok(count),
+        // tables written before the counter existed are counted once
+        None => Ok(get_route_map::<T>()
+            .keys(storage, None, None, Order::Asc.into())
+            .count() as u32),
+    }
+}
+
+/// Saves the route, or removes the domain if the route is unset, keeping the count in sync.
+/// Updating an existing domain never grows the table.
+fn store_route<T>(
+    storage: &mut dyn Storage,
+    domain: u32,
+    route: &Option<T>,
+) -> Result<(), RouterError>
+where
+    T: Serialize + DeserializeOwned + Clone + Eq,
+{
+    let routes = get_route_map::<Option<T>>();
+    let exists = routes.has(storage, domain);
+    let mut count = route_count::<T>(storage)?;
+
+    match route {
+        Some(_) => {
+            if !exists {
+                ensure!(
+                    (count as usize) < MAX_ROUTES,
+                    RouterError::TooManyRoutes { max: MAX_ROUTES }
+                );
+                count += 1;
+            }
+            routes.save(storage, domain, route)?;
+        }
+        None => {
+            if exists {
+                routes.remove(storage, domain);
+                count -= 1;
+            }
+        }
+    }
+
+    ROUTE_COUNT.save(storage, &count)?;
+
+    Ok(())
+}
+
+fn record_modification(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    domains: impl IntoIterator<Item = u32>,
+) -> StdResult<()> {
+    let modification = RouteModification {
+        modified_at: env.block.time,
+        modified_by: sender.clone(),
+    };
+
+    for domain in domains {
+        ROUTE_MODIFICATIONS.save(storage, domain, &modification)?;
+    }
+
+    Ok(())
+}
+
 pub fn handle<C: CustomQuery, T>(
     deps: DepsMut<'_, C>,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: RouterMsg<T>,
-) -> StdResult<Response>
+) -> Result<Response, RouterError>
 where
     T: Serialize + DeserializeOwned + Clone + Eq,
 {
@@ -51,12 +139,26 @@ where
 
     match msg {
         SetRoute { set } => {
+            let domain = set.domain;
             let event = set_route(deps.storage, &info.sender, set)?;
+            record_modification(deps.storage, &env, &info.sender, [domain])?;
 
             Ok(event_to_resp(event))
         }
         SetRoutes { set } => {
+            let domains = set.iter().map(|v| v.domain).collect::<Vec<_>>();
             let event = set_routes(deps.storage, &info.sender, set)?;
+            record_modification(deps.storage, &env, &info.sender, domains)?;
+
+            Ok(event_to_resp(event))
+        }
+        ImportRoutes { blob, overwrite } => {
+            let domains = from_json::<Vec<DomainRouteSet<T>>>(&blob)?
+                .into_iter()
+                .map(|v| v.domain)
+                .collect::<Vec<_>>();
+            let event = import_routes::<T>(deps.storage, &info.sender, blob, overwrite)?;
+            record_modification(deps.storage, &env, &info.sender, domains)?;
 
             Ok(event_to_resp(event))
         }
@@ -67,11 +169,11 @@ pub fn set_route<T>(
     storage: &mut dyn Storage,
     sender: &Addr,
     set: DomainRouteSet<T>,
-) -> StdResult<Event>
+) -> Result<Event, RouterError>
 where
     T: Serialize + DeserializeOwned + Clone + Eq,
 {
-    get_route_map().save(storage, set.domain, &set.route)?;
+    store_route(storage, set.domain, &set.route)?;
 
     Ok(new_event("set_route")
         .add_attribute("sender", sender)
@@ -86,16 +188,16 @@ pub fn set_routes<T>(
     storage: &mut dyn Storage,
     sender: &Addr,
     set: Vec<DomainRouteSet<T>>,
-) -> StdResult<Event>
+) -> Result<Event, RouterError>
 where
     T: Serialize + DeserializeOwned + Clone + Eq,
 {
     for DomainRouteSet {
         domain,
         route: router,
-    } in set.clone()
+    } in set.iter()
     {
-        get_route_map().save(storage, domain, &router)?;
+        store_route(storage, *domain, router)?;
     }
 
     Ok(new_event("set_routes")
@@ -107,6 +209,33 @@ where
         ))
 }
 
+pub fn import_routes<T>(
+    storage: &mut dyn Storage,
+    sender: &Addr,
+    blob: Binary,
+    overwrite: bool,
+) -> Result<Event, RouterError>
+where
+    T: Serialize + DeserializeOwned + Clone + Eq,
+{
+    let set: Vec<DomainRouteSet<T>> = from_json(&blob)?;
+
+    for DomainRouteSet { domain, route } in set.iter() {
+        if !overwrite && get_route_map::<T>().has(storage, *domain) {
+            return Err(
+                StdError::generic_err(format!("route already exists. domain: {domain}")).into(),
+            );
+        }
+
+        store_route(storage, *domain, route)?;
+    }
+
+    Ok(new_event("import_routes")
+        .add_attribute("sender", sender)
+        .add_attribute("count", set.len().to_string())
+        .add_attribute("overwrite", overwrite.to_string()))
+}
+
 pub fn handle_query<C: CustomQuery, T>(
     deps: Deps<'_, C>,
     _env: Env,
@@ -129,6 +258,12 @@ where
         } => to_json_binary(&RoutesResponse::<T> {
             routes: get_routes(deps.storage, offset, limit, order)?,
         }),
+        RouterQuery::ExportRoutes {} => to_json_binary(&ExportRoutesResponse {
+            blob: export_routes::<T>(deps.storage)?,
+        }),
+        RouterQuery::RouteInfo { domain } => {
+            to_json_binary(&get_route_info::<T>(deps.storage, domain)?)
+        }
         RouterQuery::Placeholder(_) => unreachable!(),
     }
 }
@@ -159,6 +294,16 @@ where
     })
 }
 
+pub fn get_route_info<T>(storage: &dyn Storage, domain: u32) -> StdResult<RouteInfoResponse<T>>
+where
+    T: Serialize + DeserializeOwned + Clone + Eq,
+{
+    Ok(RouteInfoResponse {
+        route: get_route(storage, domain)?,
+        last_modified: ROUTE_MODIFICATIONS.may_load(storage, domain)?,
+    })
+}
+
 pub fn get_routes<T>(
     storage: &dyn Storage,
     offset: Option<u32>,
@@ -182,3 +327,18 @@ where
         })
         .collect()
 }
+
+pub fn export_routes<T>(storage: &dyn Storage) -> StdResult<Binary>
+where
+    T: Serialize + DeserializeOwned + Clone + Eq,
+{
+    let routes = get_route_map()
+        .range(storage, None, None, Order::Asc.into())
+        .map(|item| {
+            let (domain, route) = item?;
+            Ok(DomainRouteSet::<T> { domain, route })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&routes)
+}
@@ -7,13 +7,14 @@ use cosmwasm_std::{
 };
 use hpl_interface::{
     router::{
-        DomainRouteSet, DomainsResponse, RouteResponse, RouterMsg, RouterQuery, RoutesResponse,
+        DomainRouteSet, DomainsResponse, ExportRoutesResponse, RouteInfoResponse,
+        RouteModification, RouteResponse, RouterMsg, RouterQuery, RoutesResponse,
     },
     Order,
 };
 use serde::{de::DeserializeOwned, Serialize};
 
-use crate::{handle, handle_query, is_router};
+use crate::{handle, handle_query, is_router, RouterError, MAX_ROUTES};
 
 pub struct Router<T>
 where
@@ -29,11 +30,16 @@ impl<T> Router<T>
 where
     T: Serialize + DeserializeOwned + Clone + Eq + Default,
 {
-    fn handle(&mut self, info: MessageInfo, msg: RouterMsg<T>) -> StdResult<Response> {
+    fn handle(&mut self, info: MessageInfo, msg: RouterMsg<T>) -> Result<Response, RouterError> {
         handle(self.deps.as_mut(), self.env.clone(), info, msg)
     }
 
-    pub fn set_route(&mut self, sender: &Addr, domain: u32, router: T) -> StdResult<Response> {
+    pub fn set_route(
+        &mut self,
+        sender: &Addr,
+        domain: u32,
+        router: T,
+    ) -> Result<Response, RouterError> {
         self.handle(
             mock_info(sender.as_str(), &[]),
             RouterMsg::SetRoute {
@@ -45,7 +51,7 @@ where
         )
     }
 
-    pub fn set_routes(&mut self, sender: &Addr, set: &[(u32, T)]) -> StdResult<Response> {
+    pub fn set_routes(&mut self, sender: &Addr, set: &[(u32, T)]) -> Result<Response, RouterError> {
         self.handle(
             mock_info(sender.as_str(), &[]),
             RouterMsg::SetRoutes {
@@ -60,6 +66,30 @@ where
         )
     }
 
+    pub fn unset_route(&mut self, sender: &Addr, domain: u32) -> Result<Response, RouterError> {
+        self.handle(
+            mock_info(sender.as_str(), &[]),
+            RouterMsg::SetRoute {
+                set: DomainRouteSet {
+                    domain,
+                    route: None,
+                },
+            },
+        )
+    }
+
+    pub fn import_routes(
+        &mut self,
+        sender: &Addr,
+        blob: Binary,
+        overwrite: bool,
+    ) -> Result<Response, RouterError> {
+        self.handle(
+            mock_info(sender.as_str(), &[]),
+            RouterMsg::ImportRoutes { blob, overwrite },
+        )
+    }
+
     fn handle_query<R: DeserializeOwned>(&self, msg: RouterQuery<T>) -> StdResult<R> {
         from_json(handle_query(self.deps.as_ref(), self.env.clone(), msg)?)
     }
@@ -72,6 +102,14 @@ where
         self.handle_query(RouterQuery::GetRoute { domain })
     }
 
+    pub fn query_route_info(&self, domain: u32) -> StdResult<RouteInfoResponse<T>> {
+        self.handle_query(RouterQuery::RouteInfo { domain })
+    }
+
+    pub fn query_export_routes(&self) -> StdResult<ExportRoutesResponse> {
+        self.handle_query(RouterQuery::ExportRoutes {})
+    }
+
     pub fn query_routes(
         &self,
         offset: Option<u32>,
@@ -159,3 +197,171 @@ fn test_check() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_max_routes() -> anyhow::Result<()> {
+    let owner = Addr::unchecked("owner");
+
+    let mut router = Router::default();
+
+    hpl_ownable::initialize(router.deps.as_mut().storage, &owner)?;
+
+    // fill to the cap
+    let set = (0..MAX_ROUTES as u32)
+        .map(|domain| (domain, Binary(domain.to_be_bytes().to_vec())))
+        .collect::<Vec<_>>();
+    router.set_routes(&owner, &set)?;
+
+    let DomainsResponse { domains } = router.query_domains()?;
+    assert_eq!(domains.len(), MAX_ROUTES);
+
+    // one more new domain
+    let err = router
+        .set_route(&owner, MAX_ROUTES as u32, Binary(b"overflow".to_vec()))
+        .unwrap_err();
+    assert_eq!(err, RouterError::TooManyRoutes { max: MAX_ROUTES });
+
+    let err = router
+        .set_routes(&owner, &[(MAX_ROUTES as u32, Binary(b"overflow".to_vec()))])
+        .unwrap_err();
+    assert_eq!(err, RouterError::TooManyRoutes { max: MAX_ROUTES });
+
+    // updating existing domains at capacity
+    router.set_route(&owner, 0, Binary(b"updated".to_vec()))?;
+    router.set_routes(&owner, &[(1, Binary(b"updated".to_vec()))])?;
+
+    let RouteResponse { route } = router.query_route(0)?;
+    assert_eq!(route.route, Some(Binary(b"updated".to_vec())));
+
+    // unsetting a domain frees its slot
+    router.unset_route(&owner, 0)?;
+    router.unset_route(&owner, 0)?;
+    router.set_route(&owner, MAX_ROUTES as u32, Binary(b"freed".to_vec()))?;
+
+    let DomainsResponse { domains } = router.query_domains()?;
+    assert_eq!(domains.len(), MAX_ROUTES);
+    assert!(!domains.contains(&0));
+
+    Ok(())
+}
+
+#[test]
+fn test_export_import() -> anyhow::Result<()> {
+    let owner = Addr::unchecked("owner");
+
+    let mut source = Router::default();
+    hpl_ownable::initialize(source.deps.as_mut().storage, &owner)?;
+
+    source.set_routes(
+        &owner,
+        &[
+            (1, Binary(b"router_a".to_vec())),
+            (2, Binary(b"router_b".to_vec())),
+            (3, Binary(b"router_c".to_vec())),
+        ],
+    )?;
+
+    let ExportRoutesResponse { blob } = source.query_export_routes()?;
+
+    let mut target = Router::<Binary>::default();
+    hpl_ownable::initialize(target.deps.as_mut().storage, &owner)?;
+
+    // only owner
+    let err = target
+        .import_routes(&Addr::unchecked("someone"), blob.clone(), false)
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: unauthorized");
+
+    target.import_routes(&owner, blob.clone(), false)?;
+
+    assert_eq!(
+        source.query_routes(None, None, None)?,
+        target.query_routes(None, None, None)?
+    );
+    assert_eq!(target.query_export_routes()?.blob, blob);
+
+    // reject existing routes without overwrite flag
+    target.set_route(&owner, 2, Binary(b"changed".to_vec()))?;
+
+    let err = target
+        .import_routes(&owner, blob.clone(), false)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: route already exists. domain: 1"
+    );
+
+    target.import_routes(&owner, blob.clone(), true)?;
+    assert_eq!(target.query_export_routes()?.blob, blob);
+
+    Ok(())
+}
+
+#[test]
+fn test_route_info() -> anyhow::Result<()> {
+    let owner = Addr::unchecked("owner");
+    let new_owner = Addr::unchecked("new_owner");
+
+    let mut router = Router::default();
+    hpl_ownable::initialize(router.deps.as_mut().storage, &owner)?;
+
+    let RouteInfoResponse {
+        route,
+        last_modified,
+    } = router.query_route_info(1)?;
+    assert_eq!(route.route, None);
+    assert_eq!(last_modified, None);
+
+    // set
+    router.set_route(&owner, 1, Binary(b"router_a".to_vec()))?;
+
+    let RouteInfoResponse {
+        route,
+        last_modified,
+    } = router.query_route_info(1)?;
+    assert_eq!(route.route, Some(Binary(b"router_a".to_vec())));
+    assert_eq!(
+        last_modified,
+        Some(RouteModification {
+            modified_at: router.env.block.time,
+            modified_by: owner.clone(),
+        })
+    );
+
+    // rotate
+    router.env.block.time = router.env.block.time.plus_seconds(60);
+    hpl_ownable::initialize(router.deps.as_mut().storage, &new_owner)?;
+    router.set_routes(&new_owner, &[(1, Binary(b"router_b".to_vec()))])?;
+
+    let RouteInfoResponse {
+        route,
+        last_modified,
+    } = router.query_route_info(1)?;
+    assert_eq!(route.route, Some(Binary(b"router_b".to_vec())));
+    assert_eq!(
+        last_modified,
+        Some(RouteModification {
+            modified_at: router.env.block.time,
+            modified_by: new_owner.clone(),
+        })
+    );
+
+    // unset
+    router.env.block.time = router.env.block.time.plus_seconds(60);
+    router.unset_route(&new_owner, 1)?;
+
+    let RouteInfoResponse {
+        route,
+        last_modified,
+    } = router.query_route_info(1)?;
+    assert_eq!(route.route, None);
+    assert_eq!(
+        last_modified,
+        Some(RouteModification {
+            modified_at: router.env.block.time,
+            modified_by: new_owner,
+        })
+    );
+
+    Ok(())
+}
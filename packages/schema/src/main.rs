@@ -60,13 +60,25 @@ pub fn main() {
         });
     }
 
+    {
+        use hpl_hook::filter::*;
+
+        apis.push(generate_api! {
+            name: "hpl_hook_filter",
+            instantiate: InstantiateMsg,
+            migrate: Empty,
+            execute: ExecuteMsg,
+            query: QueryMsg,
+        });
+    }
+
     {
         use hpl_hook::merkle::*;
 
         apis.push(generate_api! {
             name: "hpl_hook_merkle",
             instantiate: InstantiateMsg,
-            migrate: Empty,
+            migrate: MigrateMsg,
             execute: ExecuteMsg,
             query: QueryMsg,
         });
@@ -216,6 +228,18 @@ pub fn main() {
         });
     }
 
+    {
+        use hpl_warp::unified::*;
+
+        apis.push(generate_api! {
+            name: "hpl_warp_unified",
+            instantiate: InstantiateMsg,
+            migrate: Empty,
+            execute: ExecuteMsg,
+            query: QueryMsg,
+        });
+    }
+
     let mut base = current_dir().unwrap();
     base.push("schema");
 
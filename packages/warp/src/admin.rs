@@ -0,0 +1,87 @@
+use cosmwasm_std::{ensure_eq, DepsMut, Event, MessageInfo, Response, Uint128, Uint256};
+use hpl_interface::EventVerbosity;
+
+use crate::{
+    state::{EMBED_NONCE, EVENT_VERBOSITY, MIN_REFUND, OUTSTANDING_CAP},
+    WarpError,
+};
+
+// owner-gated setters shared by the routes. `new_event` puts events in the route's namespace
+
+fn ensure_owner(deps: &DepsMut, info: &MessageInfo) -> Result<(), WarpError> {
+    ensure_eq!(
+        hpl_ownable::get_owner(deps.storage)?,
+        info.sender,
+        WarpError::Unauthorized
+    );
+
+    Ok(())
+}
+
+pub fn set_min_refund(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_event: fn(&str) -> Event,
+    min_refund: Uint128,
+) -> Result<Response, WarpError> {
+    ensure_owner(&deps, &info)?;
+
+    MIN_REFUND.save(deps.storage, &min_refund)?;
+
+    Ok(Response::new().add_event(
+        new_event("set-min-refund")
+            .add_attribute("sender", info.sender)
+            .add_attribute("min_refund", min_refund),
+    ))
+}
+
+pub fn set_embed_nonce(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_event: fn(&str) -> Event,
+    embed_nonce: bool,
+) -> Result<Response, WarpError> {
+    ensure_owner(&deps, &info)?;
+
+    EMBED_NONCE.save(deps.storage, &embed_nonce)?;
+
+    Ok(Response::new().add_event(
+        new_event("set-embed-nonce")
+            .add_attribute("sender", info.sender)
+            .add_attribute("embed_nonce", embed_nonce.to_string()),
+    ))
+}
+
+pub fn set_event_verbosity(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_event: fn(&str) -> Event,
+    verbosity: EventVerbosity,
+) -> Result<Response, WarpError> {
+    ensure_owner(&deps, &info)?;
+
+    EVENT_VERBOSITY.save(deps.storage, &verbosity)?;
+
+    Ok(Response::new().add_event(
+        new_event("set-event-verbosity")
+            .add_attribute("sender", info.sender)
+            .add_attribute("verbosity", verbosity.to_string()),
+    ))
+}
+
+pub fn set_outstanding_cap(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_event: fn(&str) -> Event,
+    cap: Option<Uint256>,
+) -> Result<Response, WarpError> {
+    ensure_owner(&deps, &info)?;
+
+    OUTSTANDING_CAP.save(deps.storage, &cap)?;
+
+    Ok(Response::new().add_event(
+        new_event("set-outstanding-cap")
+            .add_attribute("sender", info.sender)
+            .add_attribute("cap", cap.map(|v| v.to_string()).unwrap_or_default()),
+    ))
+}
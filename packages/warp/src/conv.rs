@@ -0,0 +1,169 @@
+use std::{cmp::Ordering, str::FromStr};
+
+use cosmwasm_std::{StdError, StdResult, Uint128, Uint256};
+
+pub fn to_uint128(v: Uint256) -> StdResult<Uint128> {
+    Uint128::from_str(&v.to_string())
+}
+
+/// Rescales `amount` from `from` decimals to `to` decimals. Scaling down truncates.
+pub fn scale_amount(amount: Uint256, from: u8, to: u8) -> StdResult<Uint256> {
+    let factor = |diff: u8| Uint256::from(10u128.pow(diff as u32));
+
+    let scaled = match from.cmp(&to) {
+        Ordering::Less => amount
+            .checked_mul(factor(to - from))
+            .map_err(StdError::from)?,
+        Ordering::Greater => amount / factor(from - to),
+        Ordering::Equal => amount,
+    };
+
+    Ok(scaled)
+}
+
+pub mod cw20 {
+    use cosmwasm_std::{wasm_execute, StdResult, Uint128, WasmMsg};
+    use cw20::Cw20ExecuteMsg;
+
+    pub fn to_mint_msg(
+        contract: impl Into<String>,
+        recipient: impl Into<String>,
+        amount: Uint128,
+    ) -> StdResult<WasmMsg> {
+        wasm_execute(
+            contract,
+            &Cw20ExecuteMsg::Mint {
+                recipient: recipient.into(),
+                amount,
+            },
+            vec![],
+        )
+    }
+
+    pub fn to_burn_msg(contract: impl Into<String>, amount: Uint128) -> StdResult<WasmMsg> {
+        wasm_execute(contract, &Cw20ExecuteMsg::Burn { amount }, vec![])
+    }
+
+    pub fn to_send_msg(
+        contract: impl Into<String>,
+        recipient: impl Into<String>,
+        amount: Uint128,
+    ) -> StdResult<WasmMsg> {
+        wasm_execute(
+            contract,
+            &Cw20ExecuteMsg::Transfer {
+                recipient: recipient.into(),
+                amount,
+            },
+            vec![],
+        )
+    }
+
+    pub fn to_pull_msg(
+        contract: impl Into<String>,
+        owner: impl Into<String>,
+        recipient: impl Into<String>,
+        amount: Uint128,
+    ) -> StdResult<WasmMsg> {
+        wasm_execute(
+            contract,
+            &Cw20ExecuteMsg::TransferFrom {
+                owner: owner.into(),
+                recipient: recipient.into(),
+                amount,
+            },
+            vec![],
+        )
+    }
+}
+
+pub mod native {
+    use cosmwasm_std::{coin, Addr, BankMsg, Coin, StdResult, Uint256};
+    use hpl_interface::warp::native;
+
+    use crate::proto;
+
+    pub fn to_mint_msg(sender: &Addr, denom: &str, amount: impl ToString) -> proto::MsgMint {
+        proto::MsgMint {
+            sender: sender.to_string(),
+            amount: Some(proto::Coin {
+                denom: denom.to_string(),
+                amount: amount.to_string(),
+            }),
+        }
+    }
+
+    pub fn to_burn_msg(sender: &Addr, denom: &str, amount: impl ToString) -> proto::MsgBurn {
+        proto::MsgBurn {
+            sender: sender.to_string(),
+            amount: Some(proto::Coin {
+                denom: denom.to_string(),
+                amount: amount.to_string(),
+            }),
+        }
+    }
+
+    pub fn to_send_msg(recipient: &Addr, amount: Vec<Coin>) -> BankMsg {
+        BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount,
+        }
+    }
+
+    pub fn to_coin(amount: impl Into<u128>, denom: impl Into<String>) -> Coin {
+        coin(amount.into(), denom)
+    }
+
+    pub fn to_coin_u256(amount: Uint256, denom: impl Into<String>) -> StdResult<Coin> {
+        Ok(to_coin(super::to_uint128(amount)?, denom))
+    }
+
+    pub fn to_set_metadata_msg(
+        sender: &Addr,
+        data: native::Metadata,
+    ) -> proto::MsgSetDenomMetadata {
+        proto::MsgSetDenomMetadata {
+            sender: sender.to_string(),
+            metadata: Some(proto::Metadata {
+                description: data.description,
+                denom_units: data
+                    .denom_units
+                    .into_iter()
+                    .map(|v| proto::DenomUnit {
+                        denom: v.denom,
+                        exponent: v.exponent,
+                        aliases: v.aliases,
+                    })
+                    .collect(),
+                base: data.base,
+                display: data.display,
+                name: data.name,
+                symbol: data.symbol,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(100, 6, 18, 100 * 10u128.pow(12))]
+    #[case(100 * 10u128.pow(12), 18, 6, 100)]
+    #[case(123, 6, 6, 123)]
+    #[case(1234, 9, 6, 1)]
+    fn test_scale_amount(
+        #[case] amount: u128,
+        #[case] from: u8,
+        #[case] to: u8,
+        #[case] expected: u128,
+    ) {
+        assert_eq!(
+            scale_amount(Uint256::from_u128(amount), from, to).unwrap(),
+            Uint256::from_u128(expected)
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use cosmwasm_std::{StdError, Uint256};
+use hpl_interface::warp::TokenMode;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum WarpError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("unauthorized")]
+    Unauthorized,
+
+    #[error("amount {amount} can't be bridged exactly with {decimals} remote decimals")]
+    InexactAmount { amount: Uint256, decimals: u8 },
+
+    #[error("wrong mode. expected: {expected}, got: {got}")]
+    WrongMode { expected: TokenMode, got: TokenMode },
+
+    #[error("outstanding cap exceeded. cap: {cap}, outstanding: {outstanding}, amount: {amount}")]
+    OutstandingCapExceeded {
+        cap: Uint256,
+        outstanding: Uint256,
+        amount: Uint256,
+    },
+}
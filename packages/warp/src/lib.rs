@@ -0,0 +1,82 @@
+pub mod admin;
+pub mod conv;
+mod error;
+pub mod proto;
+pub mod state;
+
+use cosmwasm_std::{ensure, Addr, Coin, Deps, Env, Uint256};
+use cw20::{Cw20QueryMsg, TokenInfoResponse};
+use hpl_interface::{core::mailbox, warp};
+
+pub use crate::error::WarpError;
+
+/// Splits the attached funds into the dispatch fee and the refundable overpayment
+pub fn split_gas(
+    deps: Deps,
+    env: &Env,
+    mailbox: &Addr,
+    msg: &mailbox::DispatchMsg,
+    funds: Vec<Coin>,
+) -> Result<(Vec<Coin>, Vec<Coin>), WarpError> {
+    if funds.is_empty() {
+        return Ok((funds, vec![]));
+    }
+
+    let quote =
+        mailbox::quote_dispatch(&deps.querier, mailbox, &env.contract.address, msg.clone())?;
+    let min_refund = state::MIN_REFUND
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+
+    Ok(warp::split_refund(funds, &quote.fees, min_refund))
+}
+
+fn local_decimals(deps: Deps, token: &str) -> Result<u8, WarpError> {
+    let info: TokenInfoResponse = deps
+        .querier
+        .query_wasm_smart(token, &Cw20QueryMsg::TokenInfo {})?;
+
+    Ok(info.decimals)
+}
+
+/// Scales a local amount to the decimals of `dest_domain`. Fails if scaling would lose precision
+pub fn to_remote_amount(
+    deps: Deps,
+    token: &str,
+    dest_domain: u32,
+    amount: Uint256,
+) -> Result<Uint256, WarpError> {
+    let Some(remote) = state::REMOTE_DECIMALS.may_load(deps.storage, dest_domain)? else {
+        return Ok(amount);
+    };
+    let local = local_decimals(deps, token)?;
+
+    // the full local amount is taken from the sender, so nothing may be lost in scaling
+    let scaled = conv::scale_amount(amount, local, remote)?;
+    ensure!(
+        !scaled.is_zero() && conv::scale_amount(scaled, remote, local)? == amount,
+        WarpError::InexactAmount {
+            amount,
+            decimals: remote
+        }
+    );
+
+    Ok(scaled)
+}
+
+/// Scales an amount in the decimals of `origin` to local decimals
+pub fn to_local_amount(
+    deps: Deps,
+    token: &str,
+    origin: u32,
+    amount: Uint256,
+) -> Result<Uint256, WarpError> {
+    match state::REMOTE_DECIMALS.may_load(deps.storage, origin)? {
+        Some(remote) => Ok(conv::scale_amount(
+            amount,
+            remote,
+            local_decimals(deps, token)?,
+        )?),
+        None => Ok(amount),
+    }
+}
@@ -0,0 +1,70 @@
+use cosmwasm_std::{ensure, ensure_eq, StdError, Storage, Uint128, Uint256};
+use cw_storage_plus::{Item, Map};
+use hpl_interface::{warp::TokenMode, EventVerbosity};
+
+use crate::WarpError;
+
+// storage definition for token mode
+pub const MODE_KEY: &str = "mode";
+pub const MODE: Item<TokenMode> = Item::new(MODE_KEY);
+
+// storage definition for decimals of remote tokens
+pub const REMOTE_DECIMALS_PREFIX: &str = "remote_decimals";
+pub const REMOTE_DECIMALS: Map<u32, u8> = Map::new(REMOTE_DECIMALS_PREFIX);
+
+// storage definition for refund threshold of gas overpayment
+pub const MIN_REFUND_KEY: &str = "min_refund";
+pub const MIN_REFUND: Item<Uint128> = Item::new(MIN_REFUND_KEY);
+
+// storage definition for embedding the dispatch nonce into outbound messages
+pub const EMBED_NONCE_KEY: &str = "embed_nonce";
+pub const EMBED_NONCE: Item<bool> = Item::new(EMBED_NONCE_KEY);
+
+// storage definition for the cap on net bridged-out amount
+pub const OUTSTANDING_CAP_KEY: &str = "outstanding_cap";
+pub const OUTSTANDING_CAP: Item<Option<Uint256>> = Item::new(OUTSTANDING_CAP_KEY);
+
+// storage definition for net bridged-out amount (out minus in)
+pub const OUTSTANDING_KEY: &str = "outstanding";
+pub const OUTSTANDING: Item<Uint256> = Item::new(OUTSTANDING_KEY);
+
+// storage definition for how many attributes hot path events carry
+pub const EVENT_VERBOSITY_KEY: &str = "event_verbosity";
+pub const EVENT_VERBOSITY: Item<EventVerbosity> = Item::new(EVENT_VERBOSITY_KEY);
+
+pub fn record_outbound(storage: &mut dyn Storage, amount: Uint256) -> Result<(), WarpError> {
+    let outstanding = OUTSTANDING.may_load(storage)?.unwrap_or_default();
+    let next = outstanding.checked_add(amount).map_err(StdError::from)?;
+
+    if let Some(cap) = OUTSTANDING_CAP.may_load(storage)?.flatten() {
+        ensure!(
+            next <= cap,
+            WarpError::OutstandingCapExceeded {
+                cap,
+                outstanding,
+                amount
+            }
+        );
+    }
+
+    OUTSTANDING.save(storage, &next)?;
+
+    Ok(())
+}
+
+pub fn record_inbound(storage: &mut dyn Storage, amount: Uint256) -> Result<(), WarpError> {
+    // floors at zero, as the remote side may hold supply that never left this route
+    let outstanding = OUTSTANDING.may_load(storage)?.unwrap_or_default();
+
+    OUTSTANDING.save(storage, &outstanding.saturating_sub(amount))?;
+
+    Ok(())
+}
+
+pub fn ensure_mode(storage: &dyn Storage, expected: TokenMode) -> Result<(), WarpError> {
+    let got = MODE.load(storage)?;
+
+    ensure_eq!(got, expected, WarpError::WrongMode { expected, got });
+
+    Ok(())
+}
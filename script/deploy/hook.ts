@@ -14,6 +14,26 @@ import {
 } from '../shared/contract';
 import { deployIgp } from './igp';
 
+// merkle hooks only take post_dispatch from the mailbox and trusted parent hooks
+const trustParentHook = async (
+  client: Client,
+  parent: ContextHook,
+  children: ContextHook[],
+) => {
+  const merkleHooks = children.filter((v) => v.type === 'hpl_hook_merkle');
+  if (merkleHooks.length === 0) return;
+
+  await executeMultiMsg(
+    client,
+    merkleHooks.map((v) => ({
+      contract: v,
+      msg: {
+        set_trusted_hook: { hook: parent.address, trusted: true },
+      },
+    })),
+  );
+};
+
 const deployRoutingHook = async (
   networkId: string,
   ctx: Context,
@@ -45,6 +65,7 @@ const deployRoutingHook = async (
       },
     },
   });
+  await trustParentHook(client, routing, routes.map((v) => v.route));
 
   return {
     ...routing,
@@ -110,6 +131,10 @@ const deployCustomRoutingHook = async (
       },
     },
   ]);
+  await trustParentHook(client, routing, [
+    ...routes.map((v) => v.route),
+    ...customRoutes.map((v) => v.hook),
+  ]);
 
   return {
     ...routing,
@@ -175,6 +200,10 @@ const deployFallbackRoitingHook = async (
       },
     },
   ]);
+  await trustParentHook(client, routing, [
+    fallback,
+    ...routes.map((v) => v.route),
+  ]);
 
   return {
     ...routing,
@@ -208,6 +237,7 @@ export const deployHook = async (
     // deploy merkle hook
     case 'merkle': {
       return deployContract(ctx, client, 'hpl_hook_merkle', {
+        owner: hook.owner === '<signer>' ? client.signer : hook.owner,
         mailbox: ctx.deployments.core?.mailbox?.address,
       });
     }
@@ -247,6 +277,8 @@ export const deployHook = async (
         },
       );
 
+      await trustParentHook(client, aggregate, aggr);
+
       return { ...aggregate, hooks: aggr };
     }
 
@@ -84,6 +84,7 @@ export type HookType =
   | FeeHookType
   | {
       type: 'merkle';
+      owner: string;
     }
   | {
       type: 'mock';
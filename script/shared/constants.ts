@@ -14,6 +14,7 @@ export const contractNames = [
   'hpl_igp',
   'hpl_hook_aggregate',
   'hpl_hook_fee',
+  'hpl_hook_filter',
   'hpl_hook_merkle',
   'hpl_hook_pausable',
   'hpl_hook_routing',